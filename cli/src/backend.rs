@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use litesvm::LiteSVM;
+use quantum_markets_client::svm::deploy_upgradeable;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::account::{from_account, Account};
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+/// SOL given to the payer when an in-process SVM starts
+const IN_PROCESS_AIRDROP: u64 = 1_000 * LAMPORTS_PER_SOL;
+
+/// where transactions go: a validator over RPC, or an SVM inside this process
+pub enum Backend {
+    Rpc(RpcClient),
+    InProcess(Box<LiteSVM>),
+}
+
+impl Backend {
+    pub fn rpc(url: &str) -> Self {
+        Backend::Rpc(RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()))
+    }
+
+    /// fresh SVM with the program loaded from `program` and `payer` funded
+    /// and set as its upgrade authority, so it can run `init-global`
+    pub fn in_process(program: &Path, payer: &Pubkey) -> Result<Self> {
+        let mut svm = LiteSVM::new();
+        let elf = std::fs::read(program)
+            .with_context(|| format!("loading program from {}", program.display()))?;
+        deploy_upgradeable(&mut svm, &elf, payer).map_err(|e| anyhow!("loading program: {e}"))?;
+        svm.airdrop(payer, IN_PROCESS_AIRDROP)
+            .map_err(|e| anyhow!("airdrop failed: {:?}", e.err))?;
+        Ok(Backend::InProcess(Box::new(svm)))
+    }
+
+    pub fn account(&self, address: &Pubkey) -> Result<Option<Account>> {
+        match self {
+            Backend::Rpc(client) => Ok(client
+                .get_account_with_commitment(address, client.commitment())?
+                .value),
+            Backend::InProcess(svm) => Ok(svm.get_account(address)),
+        }
+    }
+
+    /// sign `instructions` with the payer plus `signers` and send them as one transaction
+    pub fn send(
+        &mut self,
+        payer: &Keypair,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Signature> {
+        let mut all: Vec<&Keypair> = vec![payer];
+        all.extend_from_slice(signers);
+        match self {
+            Backend::Rpc(client) => {
+                let blockhash = client.get_latest_blockhash()?;
+                let tx = Transaction::new_signed_with_payer(
+                    instructions, Some(&payer.pubkey()), &all, blockhash,
+                );
+                Ok(client.send_and_confirm_transaction(&tx)?)
+            }
+            Backend::InProcess(svm) => {
+                let tx = Transaction::new_signed_with_payer(
+                    instructions, Some(&payer.pubkey()), &all, svm.latest_blockhash(),
+                );
+                let signature = tx.signatures[0];
+                match svm.send_transaction(tx) {
+                    Ok(_) => {
+                        // identical follow-up transactions would otherwise be rejected as duplicates
+                        svm.expire_blockhash();
+                        Ok(signature)
+                    }
+                    Err(failed) => {
+                        for line in &failed.meta.logs {
+                            eprintln!("  {line}");
+                        }
+                        bail!("transaction failed: {}", failed.err)
+                    }
+                }
+            }
+        }
+    }
+
+    /// cluster time as the program sees it
+    pub fn now(&self) -> Result<i64> {
+        let clock: Clock = match self {
+            Backend::Rpc(_) => {
+                let account = self
+                    .account(&sysvar::clock::ID)?
+                    .ok_or_else(|| anyhow!("clock sysvar missing"))?;
+                from_account(&account).ok_or_else(|| anyhow!("malformed clock sysvar"))?
+            }
+            Backend::InProcess(svm) => svm.get_sysvar(),
+        };
+        Ok(clock.unix_timestamp)
+    }
+
+    /// move the in-process clock forward; a real cluster keeps its own time
+    pub fn advance_clock(&mut self, seconds: i64) -> Result<()> {
+        let Backend::InProcess(svm) = self else {
+            bail!("advance-clock only works with --in-process");
+        };
+        let mut clock: Clock = svm.get_sysvar();
+        clock.unix_timestamp = clock
+            .unix_timestamp
+            .checked_add(seconds)
+            .ok_or_else(|| anyhow!("clock overflow"))?;
+        svm.set_sysvar(&clock);
+        Ok(())
+    }
+}
@@ -0,0 +1,710 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use quantum_markets_client::instructions::{self as ix, CreateMarketParams};
+use quantum_markets_client::{
+    decode, payload, pda, ClaimRecord, DepositRecord, GlobalState, MarketConfig, MarketParameter, MarketStatus,
+    OracleConfig, OracleKind, PoolSide, ProposalConfig, ProposalEntry, ProposalPayload, SwapDirection,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_system_interface::instruction as system_instruction;
+
+use crate::backend::Backend;
+use crate::show;
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create the global state with the payer as admin; once per
+    /// deployment, by the program's upgrade authority
+    InitGlobal,
+    /// Offer the admin role to NEW_ADMIN, who must accept it
+    TransferAdmin { new_admin: Pubkey },
+    /// Withdraw a pending admin offer
+    CancelAdminTransfer,
+    /// Take the admin role offered to the payer
+    AcceptAdmin,
+    /// Set the protocol fee and the bounds on per-market fees, in basis points
+    SetFees {
+        #[arg(long)]
+        protocol_fee_bps: u16,
+        #[arg(long)]
+        max_lp_fee_bps: u16,
+        #[arg(long)]
+        max_creator_fee_bps: u16,
+    },
+    /// Send protocol fees to TREASURY
+    SetFeeTreasury { treasury: Pubkey },
+    /// Stop every state-changing instruction until `unpause`
+    Pause,
+    /// Resume after `pause`
+    Unpause,
+    /// Create an SPL mint with the payer as mint authority
+    CreateMint {
+        #[arg(long, default_value_t = 6)]
+        decimals: u8,
+        /// keypair file for the mint address, so scripts know it up front
+        #[arg(long)]
+        mint_keypair: Option<PathBuf>,
+    },
+    /// Mint tokens of a payer-controlled mint into the payer's account
+    MintTo { mint: Pubkey, amount: u64 },
+    /// Open a market paying out in REWARD_MINT
+    CreateMarket(CreateMarketArgs),
+    /// Deposit reward tokens into a market
+    Deposit { market_id: u64, amount: u64 },
+    /// Pay tokens of any mint into a market treasury, spendable only by
+    /// executing the market's accepted proposal
+    FundTreasury { market_id: u64, mint: Pubkey, amount: u64 },
+    /// Create a proposal backed by the payer's deposit
+    CreateProposal {
+        market_id: u64,
+        #[command(subcommand)]
+        payload: PayloadCommand,
+    },
+    /// Claim vUSD of a proposal against the payer's deposit
+    Claim { proposal_id: u64 },
+    /// Split vUSD into YES + NO
+    MintYesNo { proposal_id: u64, amount: u64 },
+    /// Merge YES + NO back into vUSD
+    RedeemYesNo { proposal_id: u64, amount: u64 },
+    /// Trade against a proposal's YES or NO pool
+    Swap {
+        proposal_id: u64,
+        side: Side,
+        direction: Direction,
+        amount_in: u64,
+        /// reject fills below this amount
+        #[arg(long, default_value_t = 0)]
+        min_out: u64,
+    },
+    /// Time out a market whose deadline passed without an accepted proposal
+    Expire { market_id: u64 },
+    /// Accept a proposal whose YES TWAP beats the market threshold
+    Graduate { proposal_id: u64 },
+    /// Settle an oracle market from its price feed once the deadline passed
+    ResolveOracle { market_id: u64 },
+    /// Carry out the accepted proposal's payload once its timelock passed
+    Execute { market_id: u64 },
+    /// Settle a market's accepted proposal; the payer must be the resolver
+    Resolve { market_id: u64, outcome: Outcome },
+    /// Swap winning tokens of the accepted proposal for reward tokens
+    RedeemWinning { proposal_id: u64, amount: u64 },
+    /// Pay a resolved market's creator fees to its creator
+    CollectCreatorFees { market_id: u64 },
+    /// Pay a resolved market's protocol fees to the fee treasury
+    CollectProtocolFees { market_id: u64 },
+    /// Take back the part of a deposit the market no longer needs
+    WithdrawDeposit { market_id: u64 },
+    /// Take back unclaimed deposit while the market is open
+    WithdrawUnclaimed { market_id: u64, amount: u64 },
+    /// Close a deposit record with nothing left to withdraw in a final market
+    CloseDeposit {
+        market_id: u64,
+        /// owner of the record; defaults to the payer
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+    /// Close a claim record in a final market; for the payer's own record
+    /// also closes their empty YES, NO and vUSD accounts of the proposal
+    CloseClaim {
+        proposal_id: u64,
+        /// owner of the record; defaults to the payer
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+    /// Close a proposal's config, pools and vaults once its records are closed
+    CloseProposal { proposal_id: u64 },
+    /// Close a market once its proposals and deposit records are closed
+    CloseMarket { market_id: u64 },
+    /// Move a proposal off the shared legacy authority
+    MigrateAuth {
+        market_id: u64,
+        proposal_id: u64,
+        /// user holding a claim record of the proposal; every one of them
+        #[arg(long = "claimant")]
+        claimants: Vec<Pubkey>,
+    },
+    /// Bring the global state to the current layout; upgrade authority only
+    MigrateGlobal,
+    /// Bring a legacy market to the current layout with the terms it
+    /// lacked; admin only
+    MigrateMarket {
+        market_id: u64,
+        /// seconds from now until trading ends
+        #[arg(long, default_value_t = 7 * 24 * 3600)]
+        duration: i64,
+        /// YES TWAP a proposal must beat, PRICE_SCALE decimals
+        #[arg(long)]
+        accept_threshold: u64,
+        /// seconds of TWAP used to graduate a proposal
+        #[arg(long)]
+        twap_window: i64,
+    },
+    /// Bring a legacy deposit record to the current layout; its market
+    /// must be migrated first
+    MigrateDeposit {
+        market_id: u64,
+        /// owner of the record; defaults to the payer
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+    /// Bring a legacy claim record to the current layout; its proposal
+    /// must be migrated first
+    MigrateClaim {
+        proposal_id: u64,
+        /// owner of the record; defaults to the payer
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+    /// Move the in-process clock forward
+    AdvanceClock { seconds: i64 },
+    /// Decode and print program accounts
+    #[command(subcommand)]
+    Show(ShowCommand),
+    /// Run qm commands from a file, one per line, against the same backend
+    Run { script: PathBuf },
+}
+
+#[derive(Args)]
+pub struct CreateMarketArgs {
+    pub reward_mint: Pubkey,
+    #[arg(long)]
+    pub title: String,
+    /// reward tokens locked per proposal, at least 6
+    #[arg(long)]
+    pub min_deposit: u64,
+    #[arg(long, default_value_t = 0)]
+    pub strike_price: u64,
+    /// unix timestamp at which trading ends
+    #[arg(long, conflicts_with = "duration")]
+    pub deadline: Option<i64>,
+    /// seconds from now until trading ends
+    #[arg(long, default_value_t = 7 * 24 * 3600)]
+    pub duration: i64,
+    /// YES TWAP a proposal must beat, PRICE_SCALE decimals
+    #[arg(long)]
+    pub accept_threshold: u64,
+    /// seconds of TWAP used to graduate a proposal
+    #[arg(long)]
+    pub twap_window: i64,
+    /// defaults to the payer
+    #[arg(long)]
+    pub resolver: Option<Pubkey>,
+    /// swap fee left in the pools, up to the global maximum
+    #[arg(long, default_value_t = 0)]
+    pub lp_fee_bps: u16,
+    /// swap fee paid to the payer as creator, up to the global maximum
+    #[arg(long, default_value_t = 0)]
+    pub creator_fee_bps: u16,
+    /// seconds after acceptance before the proposal may be executed
+    #[arg(long, default_value_t = 0)]
+    pub execution_delay: i64,
+    /// resolve against a price feed with this layout instead of the resolver
+    #[arg(long, requires = "price_feed")]
+    pub oracle: Option<Oracle>,
+    #[arg(long, requires = "oracle")]
+    pub price_feed: Option<Pubkey>,
+    /// oldest feed reading accepted at resolution, in seconds
+    #[arg(long, default_value_t = 60)]
+    pub max_staleness: i64,
+    /// widest confidence interval accepted, relative to the price
+    #[arg(long, default_value_t = 100)]
+    pub max_confidence_bps: u16,
+}
+
+/// what the proposal would do if accepted
+#[derive(Subcommand)]
+pub enum PayloadCommand {
+    /// Off-chain description at URI
+    Text {
+        uri: String,
+        /// hash of the content at URI, 32 bytes hex encoded
+        #[arg(long)]
+        content_hash: Option<String>,
+    },
+    /// Transfer AMOUNT of MINT from the market treasury to a token account
+    Transfer { mint: Pubkey, destination: Pubkey, amount: u64 },
+    /// Set a market parameter
+    SetParameter { parameter: Parameter, value: u64 },
+    /// Payload encoded elsewhere, e.g. an instruction list, hex encoded
+    Raw { data: String },
+}
+
+#[derive(Subcommand)]
+pub enum ShowCommand {
+    Global,
+    Market { id: u64 },
+    Proposal { id: u64 },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Side { Yes, No }
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Direction { Buy, Sell }
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Outcome { Yes, No }
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Oracle { Pyth, Switchboard }
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Parameter { MinDeposit, AcceptThreshold, TwapWindow, LpFeeBps, CreatorFeeBps }
+
+impl From<Parameter> for MarketParameter {
+    fn from(parameter: Parameter) -> Self {
+        match parameter {
+            Parameter::MinDeposit      => MarketParameter::MinDeposit,
+            Parameter::AcceptThreshold => MarketParameter::AcceptThreshold,
+            Parameter::TwapWindow      => MarketParameter::TwapWindow,
+            Parameter::LpFeeBps        => MarketParameter::LpFeeBps,
+            Parameter::CreatorFeeBps   => MarketParameter::CreatorFeeBps,
+        }
+    }
+}
+
+impl CreateMarketArgs {
+    fn oracle(&self) -> OracleConfig {
+        let (Some(oracle), Some(feed)) = (self.oracle, self.price_feed) else {
+            return OracleConfig::default();
+        };
+        OracleConfig {
+            kind: match oracle {
+                Oracle::Pyth        => OracleKind::Pyth,
+                Oracle::Switchboard => OracleKind::Switchboard,
+            },
+            feed,
+            max_staleness: self.max_staleness,
+            max_confidence_bps: self.max_confidence_bps,
+        }
+    }
+}
+
+impl PayloadCommand {
+    fn encode(self) -> Result<Vec<u8>> {
+        let payload = match self {
+            PayloadCommand::Text { uri, content_hash } => {
+                let content_hash = match content_hash {
+                    Some(hex) => parse_hex(&hex)?
+                        .try_into()
+                        .map_err(|_| anyhow!("content hash must be 32 bytes"))?,
+                    None => [0; 32],
+                };
+                ProposalPayload::Text { uri, content_hash }
+            }
+            PayloadCommand::Transfer { mint, destination, amount } => {
+                ProposalPayload::Transfer { mint, destination, amount }
+            }
+            PayloadCommand::SetParameter { parameter, value } => {
+                ProposalPayload::ParameterChange { parameter: parameter.into(), value }
+            }
+            PayloadCommand::Raw { data } => return parse_hex(&data),
+        };
+        Ok(payload.encode())
+    }
+}
+
+impl From<Side> for PoolSide {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Yes => PoolSide::Yes,
+            Side::No  => PoolSide::No,
+        }
+    }
+}
+
+impl From<Direction> for SwapDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Buy  => SwapDirection::Buy,
+            Direction::Sell => SwapDirection::Sell,
+        }
+    }
+}
+
+/// one line of a `run` script
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct ScriptLine {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// backend plus the keypair that pays for and signs everything
+pub struct Session {
+    pub backend: Backend,
+    pub payer: Keypair,
+}
+
+impl Session {
+    fn send(&mut self, instructions: &[solana_sdk::instruction::Instruction]) -> Result<()> {
+        let signature = self.backend.send(&self.payer, instructions, &[])?;
+        println!("{signature}");
+        Ok(())
+    }
+
+    fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    fn fetch(&self, address: &Pubkey, what: &str) -> Result<Vec<u8>> {
+        Ok(self
+            .backend
+            .account(address)?
+            .ok_or_else(|| anyhow!("{what} {address} not found"))?
+            .data)
+    }
+
+    pub fn global(&self) -> Result<GlobalState> {
+        let data = self.fetch(&pda::global(), "global state")?;
+        decode::global(&data).map_err(|e| anyhow!("{e}"))
+    }
+
+    pub fn market(&self, id: u64) -> Result<MarketConfig> {
+        let data = self.fetch(&pda::market(id), "market")?;
+        decode::market(&data).map_err(|e| anyhow!("{e}"))
+    }
+
+    pub fn proposal(&self, id: u64) -> Result<ProposalConfig> {
+        let data = self.fetch(&pda::proposal(id), "proposal")?;
+        decode::proposal(&data).map_err(|e| anyhow!("{e}"))
+    }
+
+    pub fn deposit_record(&self, market_id: u64, user: &Pubkey) -> Result<DepositRecord> {
+        let data = self.fetch(&pda::deposit(&pda::market(market_id), user), "deposit record")?;
+        decode::deposit_record(&data).map_err(|e| anyhow!("{e}"))
+    }
+
+    pub fn claim_record(&self, proposal_id: u64, user: &Pubkey) -> Result<ClaimRecord> {
+        let data = self.fetch(&pda::claim(&pda::proposal(proposal_id), user), "claim record")?;
+        decode::claim_record(&data).map_err(|e| anyhow!("{e}"))
+    }
+
+    /// token accounts of the payer that exist and hold nothing
+    fn empty_token_accounts(&self, mints: &[Pubkey]) -> Result<Vec<Pubkey>> {
+        let mut empty = Vec::new();
+        for mint in mints {
+            let address = pda::ata(&self.payer(), mint);
+            if let Some(account) = self.backend.account(&address)? {
+                if spl_token::state::Account::unpack(&account.data)?.amount == 0 {
+                    empty.push(address);
+                }
+            }
+        }
+        Ok(empty)
+    }
+
+    /// every proposal of `market_id`, from the market's registry
+    pub fn market_proposals(&self, market_id: u64) -> Result<Vec<ProposalEntry>> {
+        let data = self.fetch(&pda::market_proposals(&pda::market(market_id)), "proposal registry")?;
+        let (_, entries) = decode::market_proposals(&data).map_err(|e| anyhow!("{e}"))?;
+        Ok(entries)
+    }
+
+    pub fn run(&mut self, command: Command) -> Result<()> {
+        let payer = self.payer();
+        match command {
+            Command::InitGlobal => self.send(&[ix::initialize_global(&payer)]),
+
+            Command::TransferAdmin { new_admin } => self.send(&[ix::transfer_admin(&payer, &new_admin)]),
+            Command::CancelAdminTransfer => self.send(&[ix::transfer_admin(&payer, &Pubkey::default())]),
+            Command::AcceptAdmin => self.send(&[ix::accept_admin(&payer)]),
+            Command::SetFees { protocol_fee_bps, max_lp_fee_bps, max_creator_fee_bps } => {
+                self.send(&[ix::set_fees(&payer, protocol_fee_bps, max_lp_fee_bps, max_creator_fee_bps)])
+            }
+            Command::SetFeeTreasury { treasury } => self.send(&[ix::set_fee_treasury(&payer, &treasury)]),
+            Command::Pause => self.send(&[ix::set_paused(&payer, true)]),
+            Command::Unpause => self.send(&[ix::set_paused(&payer, false)]),
+
+            Command::CreateMint { decimals, mint_keypair } => {
+                let mint = match mint_keypair {
+                    Some(path) => read_keypair_file(&path)
+                        .map_err(|e| anyhow!("reading keypair {}: {e}", path.display()))?,
+                    None => Keypair::new(),
+                };
+                let space = spl_token::state::Mint::LEN;
+                let rent = rent_exempt_minimum(space);
+                let instructions = [
+                    system_instruction::create_account(
+                        &payer, &mint.pubkey(), rent, space as u64, &spl_token::ID,
+                    ),
+                    spl_token::instruction::initialize_mint2(
+                        &spl_token::ID, &mint.pubkey(), &payer, None, decimals,
+                    )?,
+                ];
+                self.backend.send(&self.payer, &instructions, &[&mint])?;
+                println!("{}", mint.pubkey());
+                Ok(())
+            }
+
+            Command::MintTo { mint, amount } => {
+                let account = pda::ata(&payer, &mint);
+                self.send(&[
+                    create_associated_token_account_idempotent(&payer, &payer, &mint, &spl_token::ID),
+                    spl_token::instruction::mint_to(&spl_token::ID, &mint, &account, &payer, &[], amount)?,
+                ])
+            }
+
+            Command::CreateMarket(args) => {
+                let deadline = match args.deadline {
+                    Some(deadline) => deadline,
+                    None => self.backend.now()? + args.duration,
+                };
+                let market_id = self.global()?.next_market_id;
+                let oracle = args.oracle();
+                let params = CreateMarketParams {
+                    min_deposit: args.min_deposit,
+                    strike_price: args.strike_price,
+                    deadline,
+                    accept_threshold: args.accept_threshold,
+                    twap_window: args.twap_window,
+                    title: args.title,
+                    lp_fee_bps: args.lp_fee_bps,
+                    creator_fee_bps: args.creator_fee_bps,
+                    execution_delay: args.execution_delay,
+                    oracle,
+                };
+                let resolver = args.resolver.unwrap_or(payer);
+                self.send(&[ix::create_market(&payer, &args.reward_mint, &resolver, market_id, &params)])?;
+                println!("market {market_id}");
+                Ok(())
+            }
+
+            Command::Deposit { market_id, amount } => {
+                let market = self.market(market_id)?;
+                self.send(&[ix::deposit_to_market(&payer, market_id, &market.market_token, amount)])
+            }
+
+            Command::FundTreasury { market_id, mint, amount } => {
+                self.send(&[ix::fund_treasury(&payer, market_id, &mint, amount)])?;
+                let treasury = pda::treasury(&pda::market(market_id));
+                println!("treasury {treasury}");
+                Ok(())
+            }
+
+            Command::CreateProposal { market_id, payload } => {
+                let market = self.market(market_id)?;
+                let data = payload.encode()?;
+                let proposal_id = self.global()?.next_proposal_id;
+                self.send(&[ix::create_proposal(&payer, market_id, &market.market_token, proposal_id, data)])?;
+                println!("proposal {proposal_id}");
+                Ok(())
+            }
+
+            Command::Claim { proposal_id } => {
+                let proposal = self.proposal(proposal_id)?;
+                self.send(&[ix::claim_for_proposal(&payer, proposal.market_id, proposal_id)])
+            }
+
+            Command::MintYesNo { proposal_id, amount } => {
+                let proposal = self.proposal(proposal_id)?;
+                self.send(&[ix::mint_yes_no(&payer, proposal.market_id, proposal_id, amount)])
+            }
+
+            Command::RedeemYesNo { proposal_id, amount } => {
+                let proposal = self.proposal(proposal_id)?;
+                self.send(&[ix::redeem_yes_no(&payer, proposal.market_id, proposal_id, amount)])
+            }
+
+            Command::Swap { proposal_id, side, direction, amount_in, min_out } => {
+                let proposal = self.proposal(proposal_id)?;
+                self.send(&[ix::swap_exact_in(
+                    &payer,
+                    proposal.market_id,
+                    proposal_id,
+                    side.into(),
+                    direction.into(),
+                    amount_in,
+                    min_out,
+                )])
+            }
+
+            Command::Expire { market_id } => self.send(&[ix::expire_market(market_id)]),
+
+            Command::Graduate { proposal_id } => {
+                let proposal = self.proposal(proposal_id)?;
+                let others: Vec<u64> = self
+                    .market_proposals(proposal.market_id)?
+                    .iter()
+                    .map(|p| p.id)
+                    .filter(|id| *id != proposal_id)
+                    .collect();
+                self.send(&[ix::graduate_proposal(proposal.market_id, proposal_id, &others)])
+            }
+
+            Command::ResolveOracle { market_id } => {
+                let market = self.market(market_id)?;
+                self.send(&[ix::resolve_with_oracle(market_id, &market.oracle.feed)])
+            }
+
+            Command::Execute { market_id } => {
+                let proposal_id = self.market(market_id)?.accepted_proposal;
+                let payload = payload::decode(&self.proposal(proposal_id)?)?;
+                self.send(&[ix::execute_proposal(market_id, proposal_id, &payload)])
+            }
+
+            Command::Resolve { market_id, outcome } => {
+                self.send(&[ix::resolve_market(&payer, market_id, matches!(outcome, Outcome::Yes))])
+            }
+
+            Command::RedeemWinning { proposal_id, amount } => {
+                let proposal = self.proposal(proposal_id)?;
+                let market = self.market(proposal.market_id)?;
+                let winning = match market.status {
+                    MarketStatus::ResolvedYes => PoolSide::Yes,
+                    MarketStatus::ResolvedNo  => PoolSide::No,
+                    _ => bail!("market {} is not resolved", proposal.market_id),
+                };
+                self.send(&[ix::redeem_winning(
+                    &payer, proposal.market_id, &market.market_token, proposal_id, winning, amount,
+                )])
+            }
+
+            Command::CollectCreatorFees { market_id } => {
+                let market = self.market(market_id)?;
+                self.send(&[ix::collect_creator_fees(
+                    &payer, market_id, &market.market_token, market.accepted_proposal, &market.creator,
+                )])
+            }
+
+            Command::CollectProtocolFees { market_id } => {
+                let market = self.market(market_id)?;
+                let treasury = self.global()?.fee_treasury;
+                self.send(&[ix::collect_protocol_fees(
+                    &payer, market_id, &market.market_token, market.accepted_proposal, &treasury,
+                )])
+            }
+
+            Command::WithdrawDeposit { market_id } => {
+                let market = self.market(market_id)?;
+                let accepted = match market.status {
+                    MarketStatus::Open | MarketStatus::Timeout => None,
+                    _ => Some(market.accepted_proposal),
+                };
+                self.send(&[ix::withdraw_deposit(&payer, market_id, &market.market_token, accepted)])
+            }
+
+            Command::WithdrawUnclaimed { market_id, amount } => {
+                let market = self.market(market_id)?;
+                self.send(&[ix::withdraw_unclaimed(&payer, market_id, &market.market_token, amount)])
+            }
+
+            Command::CloseDeposit { market_id, user } => {
+                let user = user.unwrap_or(payer);
+                let market = self.market(market_id)?;
+                let record = self.deposit_record(market_id, &user)?;
+                let accepted = match market.status {
+                    MarketStatus::Open | MarketStatus::Timeout => None,
+                    _ => Some(market.accepted_proposal),
+                };
+                self.send(&[ix::close_deposit_record(&user, market_id, accepted, &record.rent_payer)])
+            }
+
+            Command::CloseClaim { proposal_id, user } => {
+                let user = user.unwrap_or(payer);
+                let proposal = self.proposal(proposal_id)?;
+                let record = self.claim_record(proposal_id, &user)?;
+                let mut instructions =
+                    vec![ix::close_claim_record(&user, proposal.market_id, proposal_id, &record.rent_payer)];
+                if user == payer {
+                    let mints = [proposal.vusd_mint, proposal.yes_mint, proposal.no_mint];
+                    for account in self.empty_token_accounts(&mints)? {
+                        instructions.push(spl_token::instruction::close_account(
+                            &spl_token::ID, &account, &payer, &payer, &[],
+                        )?);
+                    }
+                }
+                self.send(&instructions)
+            }
+
+            Command::CloseProposal { proposal_id } => {
+                let proposal = self.proposal(proposal_id)?;
+                let market = self.market(proposal.market_id)?;
+                self.send(&[ix::close_proposal(
+                    proposal.market_id, proposal_id, &market.market_token, &proposal.creator, &proposal.rent_payer,
+                )])
+            }
+
+            Command::CloseMarket { market_id } => {
+                let market = self.market(market_id)?;
+                let treasury = self.global()?.fee_treasury;
+                self.send(&[ix::close_market(
+                    &payer, market_id, &market.market_token, &treasury, &market.rent_payer,
+                )])
+            }
+
+            Command::MigrateAuth { market_id, proposal_id, claimants } => {
+                self.send(&[ix::migrate_proposal_auth(&payer, market_id, proposal_id, &claimants)])
+            }
+
+            Command::MigrateGlobal => self.send(&[ix::migrate_global(&payer)]),
+
+            Command::MigrateMarket { market_id, duration, accept_threshold, twap_window } => {
+                let deadline = self.backend.now()? + duration;
+                self.send(&[ix::migrate_market(&payer, market_id, deadline, accept_threshold, twap_window)])
+            }
+
+            Command::MigrateDeposit { market_id, user } => {
+                let user = user.unwrap_or(payer);
+                self.send(&[ix::migrate_deposit_record(&payer, market_id, &user)])
+            }
+
+            Command::MigrateClaim { proposal_id, user } => {
+                let user = user.unwrap_or(payer);
+                self.send(&[ix::migrate_claim_record(&payer, proposal_id, &user)])
+            }
+
+            Command::AdvanceClock { seconds } => {
+                self.backend.advance_clock(seconds)?;
+                println!("now {}", self.backend.now()?);
+                Ok(())
+            }
+
+            Command::Show(what) => show::run(self, what),
+
+            Command::Run { script } => {
+                let text = fs::read_to_string(&script)
+                    .with_context(|| format!("reading {}", script.display()))?;
+                for (n, line) in text.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    println!("> {line}");
+                    let words = shell_words::split(line)
+                        .with_context(|| format!("{}:{}", script.display(), n + 1))?;
+                    let parsed = ScriptLine::try_parse_from(words)?;
+                    if matches!(parsed.command, Command::Run { .. }) {
+                        bail!("{}:{}: scripts cannot run other scripts", script.display(), n + 1);
+                    }
+                    self.run(parsed.command)
+                        .with_context(|| format!("{}:{}", script.display(), n + 1))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// rent-exempt balance under the default rent parameters
+fn rent_exempt_minimum(space: usize) -> u64 {
+    solana_sdk::rent::Rent::default().minimum_balance(space)
+}
+
+fn parse_hex(text: &str) -> Result<Vec<u8>> {
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    if !text.len().is_multiple_of(2) {
+        bail!("hex data has an odd number of digits");
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|_| anyhow!("invalid hex data")))
+        .collect()
+}
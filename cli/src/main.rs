@@ -0,0 +1,60 @@
+//! `qm`: operate quantum-markets from the command line. Transactions go to
+//! a validator over RPC, or with `--in-process` to an SVM running inside
+//! the process with the program binary loaded; `qm run` replays a script
+//! of commands so a whole market lifecycle fits in one in-process session.
+
+mod backend;
+mod commands;
+mod show;
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+
+use crate::backend::Backend;
+use crate::commands::{Command, Session};
+
+#[derive(Parser)]
+#[command(name = "qm", version, about = "Operate quantum-markets")]
+struct Cli {
+    /// Keypair file that pays for and signs every transaction
+    #[arg(long, short, global = true, default_value_t = default_keypair())]
+    keypair: String,
+
+    /// RPC endpoint of the validator
+    #[arg(long, short, global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Use an in-process SVM loaded with this program binary instead of RPC.
+    /// A fresh keypair is used if the keypair file does not exist.
+    #[arg(long, global = true, value_name = "PROGRAM_SO")]
+    in_process: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+fn default_keypair() -> String {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    format!("{home}/.config/solana/id.json")
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair = read_keypair_file(&cli.keypair);
+    let (backend, payer) = match &cli.in_process {
+        Some(program) => {
+            let payer = keypair.unwrap_or_else(|_| Keypair::new());
+            (Backend::in_process(program, &payer.pubkey())?, payer)
+        }
+        None => {
+            let payer = keypair.map_err(|e| anyhow!("reading keypair {}: {e}", cli.keypair))?;
+            (Backend::rpc(&cli.url), payer)
+        }
+    };
+
+    Session { backend, payer }.run(cli.command)
+}
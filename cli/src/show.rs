@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use quantum_markets::constants::PRICE_SCALE;
+use quantum_markets_client::{decode, payload, MarketConfig, MarketStatus, Pool, ProposalConfig};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::commands::{Session, ShowCommand};
+
+pub fn run(session: &Session, what: ShowCommand) -> Result<()> {
+    match what {
+        ShowCommand::Global => {
+            let global = session.global()?;
+            println!("next market id    {}", global.next_market_id);
+            println!("next proposal id  {}", global.next_proposal_id);
+            println!("admin             {}", global.admin);
+            if global.has_pending_admin() {
+                println!("pending admin     {}", global.pending_admin);
+            }
+            println!("fee treasury      {}", global.fee_treasury);
+            println!("protocol fee      {} bps", global.protocol_fee_bps);
+            println!("max LP fee        {} bps", global.max_lp_fee_bps);
+            println!("max creator fee   {} bps", global.max_creator_fee_bps);
+            println!("paused            {}", global.paused);
+        }
+        ShowCommand::Market { id } => {
+            let market = session.market(id)?;
+            print_market(id, &market);
+            for entry in session.market_proposals(id)? {
+                println!(
+                    "  proposal          {}  YES {} / NO {} at {}",
+                    entry.id, price(entry.yes_price), price(entry.no_price), entry.updated_at,
+                );
+            }
+        }
+        ShowCommand::Proposal { id } => {
+            let proposal = session.proposal(id)?;
+            let market = session.market(proposal.market_id)?;
+            print_proposal(&proposal);
+            let now = session.backend.now()?;
+            for (name, address) in [("YES", proposal.yes_pool), ("NO", proposal.no_pool)] {
+                let pool = pool(session, &address)?;
+                print_pool(name, &pool, now, market.twap_window);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn pool(session: &Session, address: &Pubkey) -> Result<Pool> {
+    let account = session
+        .backend
+        .account(address)?
+        .ok_or_else(|| anyhow!("pool {address} not found"))?;
+    decode::pool(&account.data).map_err(|e| anyhow!("{e}"))
+}
+
+fn status_name(status: MarketStatus) -> &'static str {
+    match status {
+        MarketStatus::Open             => "open",
+        MarketStatus::ProposalAccepted => "proposal accepted",
+        MarketStatus::Timeout          => "timed out",
+        MarketStatus::ResolvedYes      => "resolved yes",
+        MarketStatus::ResolvedNo       => "resolved no",
+    }
+}
+
+/// PRICE_SCALE fixed point as a decimal
+fn price(value: u64) -> String {
+    format!("{}.{:06}", value / PRICE_SCALE, value % PRICE_SCALE)
+}
+
+fn print_market(id: u64, market: &MarketConfig) {
+    println!("market {id}: {}", market.title);
+    println!("  status            {}", status_name(market.status));
+    println!("  creator           {}", market.creator);
+    if market.has_oracle() {
+        let oracle = &market.oracle;
+        println!(
+            "  oracle            {:?} feed {} (max {}s old, {} bps confidence)",
+            oracle.kind, oracle.feed, oracle.max_staleness, oracle.max_confidence_bps,
+        );
+    } else {
+        println!("  resolver          {}", market.resolver);
+    }
+    println!("  reward mint       {}", market.market_token);
+    println!("  min deposit       {}", market.min_deposit);
+    println!("  strike price      {}", price(market.strike_price));
+    println!("  accept threshold  {}", price(market.accept_threshold));
+    println!("  twap window       {}s", market.twap_window);
+    println!("  execution delay   {}s", market.execution_delay);
+    println!(
+        "  fees              {} LP / {} creator / {} protocol bps",
+        market.lp_fee_bps, market.creator_fee_bps, market.protocol_fee_bps,
+    );
+    println!("  created at        {}", market.created_at);
+    println!("  deadline          {}", market.deadline);
+    println!("  proposals         {} ({} closed)", market.proposal_count, market.closed_proposals);
+    println!("  deposit records   {} open", market.open_deposits);
+    if market.status != MarketStatus::Open && market.status != MarketStatus::Timeout {
+        println!("  accepted          {} at {}", market.accepted_proposal, market.accepted_at);
+    }
+    if market.is_resolved() {
+        println!("  resolved at       {}", market.resolved_at);
+    }
+}
+
+fn print_proposal(proposal: &ProposalConfig) {
+    println!("proposal {} of market {}", proposal.id, proposal.market_id);
+    println!("  creator           {}", proposal.creator);
+    println!("  created at        {}", proposal.created_at);
+    println!("  vUSD mint         {}", proposal.vusd_mint);
+    println!("  YES mint          {}", proposal.yes_mint);
+    println!("  NO mint           {}", proposal.no_mint);
+    println!("  auth version      {}", proposal.auth_version);
+    println!("  uncollected fees  {} creator / {} protocol vUSD", proposal.creator_fees, proposal.protocol_fees);
+    if proposal.executed_at != 0 {
+        println!("  executed at       {}", proposal.executed_at);
+    }
+    match payload::decode(proposal) {
+        Ok(payload) => {
+            for line in payload::describe(&payload) {
+                println!("  action            {line}");
+            }
+        }
+        Err(e) => {
+            let data: String = proposal.data().iter().map(|b| format!("{b:02x}")).collect();
+            println!("  data              {data} (not a payload: {e})");
+        }
+    }
+}
+
+fn print_pool(name: &str, pool: &Pool, now: i64, window: i64) {
+    println!("  {name} pool");
+    println!("    reserves        {} token / {} vUSD", pool.reserve_token, pool.reserve_vusd);
+    match pool.spot_price() {
+        Ok(spot) => println!("    spot price      {}", price(spot)),
+        Err(e) => println!("    spot price      unavailable ({e})"),
+    }
+    match pool.twap(now, window) {
+        Ok(twap) => println!("    twap ({window}s)     {}", price(twap)),
+        Err(e) => println!("    twap ({window}s)     unavailable ({e})"),
+    }
+}
@@ -0,0 +1,67 @@
+use std::mem::size_of;
+use anchor_lang::error::ErrorCode;
+use anchor_lang::{AccountDeserialize, Result, ZeroCopy};
+use quantum_markets::state::config::MarketConfig;
+use quantum_markets::state::deposit::{ClaimRecord, DepositRecord};
+use quantum_markets::state::global::GlobalState;
+use quantum_markets::state::pool::Pool;
+use quantum_markets::state::proposal::ProposalConfig;
+use quantum_markets::state::registry::{MarketProposals, ProposalEntry};
+
+/// decode raw account data, checking the Anchor discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// same for zero-copy accounts, whose body is copied out as is
+pub fn decode_zero_copy<T: ZeroCopy>(data: &[u8]) -> Result<T> {
+    let disc = T::DISCRIMINATOR;
+    if data.len() < disc.len() {
+        return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+    if &data[..disc.len()] != disc {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    let body = data
+        .get(disc.len()..disc.len() + size_of::<T>())
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+    Ok(bytemuck::pod_read_unaligned(body))
+}
+
+pub fn global(data: &[u8]) -> Result<GlobalState> {
+    decode(data)
+}
+
+pub fn market(data: &[u8]) -> Result<MarketConfig> {
+    decode(data)
+}
+
+pub fn proposal(data: &[u8]) -> Result<ProposalConfig> {
+    decode_zero_copy(data)
+}
+
+/// the registry header and the entries in use, in creation order
+pub fn market_proposals(data: &[u8]) -> Result<(MarketProposals, Vec<ProposalEntry>)> {
+    let header: MarketProposals = decode_zero_copy(data)?;
+    let entries: Vec<ProposalEntry> = data[MarketProposals::HEADER.min(data.len())..]
+        .chunks_exact(ProposalEntry::SIZE)
+        .take(header.len as usize)
+        .map(bytemuck::pod_read_unaligned)
+        .collect();
+    if entries.len() != header.len as usize {
+        return Err(ErrorCode::AccountDidNotDeserialize.into());
+    }
+    Ok((header, entries))
+}
+
+pub fn pool(data: &[u8]) -> Result<Pool> {
+    decode(data)
+}
+
+pub fn deposit_record(data: &[u8]) -> Result<DepositRecord> {
+    decode(data)
+}
+
+pub fn claim_record(data: &[u8]) -> Result<ClaimRecord> {
+    decode(data)
+}
@@ -0,0 +1,96 @@
+//! Decoding of the events the program logs with `emit!`. Only `Program data:`
+//! lines written while this program is the innermost running program are
+//! considered, so CPIs into other programs can't inject events.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use quantum_markets::events::*;
+use quantum_markets::ID;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+macro_rules! events {
+    ($($name:ident),* $(,)?) => {
+        /// every event the program emits
+        pub enum QuantumEvent {
+            $($name($name),)*
+        }
+
+        impl QuantumEvent {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(QuantumEvent::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+
+        /// decode one event: 8-byte discriminator, then the Borsh body
+        pub fn decode(data: &[u8]) -> Option<QuantumEvent> {
+            let (discriminator, mut body) = data.split_at_checked(8)?;
+            $(
+                if discriminator == $name::DISCRIMINATOR {
+                    return $name::deserialize(&mut body).ok().map(QuantumEvent::$name);
+                }
+            )*
+            None
+        }
+    };
+}
+
+events!(
+    GlobalInitialized,
+    MarketCreated,
+    Deposited,
+    ProposalCreated,
+    VusdClaimed,
+    YesNoMinted,
+    YesNoRedeemed,
+    Swapped,
+    MarketExpired,
+    ProposalAccepted,
+    MarketResolved,
+    WinningRedeemed,
+    DepositWithdrawn,
+    UnclaimedWithdrawn,
+    ProposalAuthMigrated,
+    GlobalMigrated,
+    MarketMigrated,
+    GlobalConfigUpdated,
+    FeesCollected,
+    DepositRecordClosed,
+    ClaimRecordClosed,
+    ProposalClosed,
+    MarketClosed,
+    ProposalExecuted,
+    TreasuryFunded,
+    OracleSettled,
+);
+
+/// events of one transaction, in emission order
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<QuantumEvent> {
+    let program = ID.to_string();
+    let mut stack: Vec<bool> = Vec::new(); // is each running program ours
+    let mut events = Vec::new();
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&true) {
+                if let Some(event) = STANDARD.decode(data).ok().and_then(|bytes| decode(&bytes)) {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let id = words.next().unwrap_or_default();
+            match words.next() {
+                Some("invoke") => stack.push(id == program),
+                Some("success") | Some("failed:") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
@@ -0,0 +1,752 @@
+//! One builder per instruction in the program. Market and proposal ids are
+//! the ones their PDAs are seeded with; the builders derive every other
+//! address from them.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::{associated_token, token};
+use quantum_markets::state::oracle::OracleConfig;
+use quantum_markets::state::payload::ProposalPayload;
+use quantum_markets::state::pool::SwapDirection;
+use quantum_markets::state::proposal::PoolSide;
+use quantum_markets::{accounts, instruction, ID};
+
+use crate::pda::{self, ProposalKeys};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// arguments of `create_market`
+#[derive(Clone, Debug)]
+pub struct CreateMarketParams {
+    pub min_deposit: u64,
+    pub strike_price: u64,
+    pub deadline: i64,
+    pub accept_threshold: u64,
+    pub twap_window: i64,
+    pub title: String,
+    pub lp_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub execution_delay: i64,
+    pub oracle: OracleConfig,
+}
+
+pub fn initialize_global(payer: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeGlobal {
+            global: pda::global(),
+            payer: *payer,
+            program_data: pda::program_data(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGlobal {},
+    )
+}
+
+fn update_global(admin: &Pubkey) -> accounts::UpdateGlobal {
+    accounts::UpdateGlobal { admin: *admin, global: pda::global() }
+}
+
+/// `Pubkey::default()` as `new_admin` cancels a pending transfer
+pub fn transfer_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(update_global(admin), instruction::TransferAdmin { new_admin: *new_admin })
+}
+
+pub fn accept_admin(pending_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdmin { pending_admin: *pending_admin, global: pda::global() },
+        instruction::AcceptAdmin {},
+    )
+}
+
+pub fn set_fees(admin: &Pubkey, protocol_fee_bps: u16, max_lp_fee_bps: u16, max_creator_fee_bps: u16) -> Instruction {
+    build(
+        update_global(admin),
+        instruction::SetFees { protocol_fee_bps, max_lp_fee_bps, max_creator_fee_bps },
+    )
+}
+
+pub fn set_fee_treasury(admin: &Pubkey, fee_treasury: &Pubkey) -> Instruction {
+    build(update_global(admin), instruction::SetFeeTreasury { fee_treasury: *fee_treasury })
+}
+
+pub fn set_paused(admin: &Pubkey, paused: bool) -> Instruction {
+    build(update_global(admin), instruction::SetPaused { paused })
+}
+
+/// `market_id` must be the global counter's next market id
+pub fn create_market(
+    payer: &Pubkey,
+    reward_mint: &Pubkey,
+    resolver: &Pubkey,
+    market_id: u64,
+    params: &CreateMarketParams,
+) -> Instruction {
+    build(
+        accounts::CreateMarket {
+            payer: *payer,
+            reward_mint: *reward_mint,
+            resolver: *resolver,
+            market: pda::market(market_id),
+            registry: pda::market_proposals(&pda::market(market_id)),
+            global: pda::global(),
+            system_program: system_program::ID,
+        },
+        instruction::CreateMarket {
+            min_deposit: params.min_deposit,
+            strike_price: params.strike_price,
+            deadline: params.deadline,
+            accept_threshold: params.accept_threshold,
+            twap_window: params.twap_window,
+            title: params.title.clone(),
+            lp_fee_bps: params.lp_fee_bps,
+            creator_fee_bps: params.creator_fee_bps,
+            execution_delay: params.execution_delay,
+            oracle: params.oracle,
+        },
+    )
+}
+
+pub fn deposit_to_market(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let market = pda::market(market_id);
+    build(
+        accounts::DepositToMarket {
+            payer: *payer,
+            global: pda::global(),
+            reward_mint: *reward_mint,
+            user_token: pda::ata(payer, reward_mint),
+            market_vault: pda::ata(&market, reward_mint),
+            market,
+            deposit_record: pda::deposit(&market, payer),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::DepositToMarket { amount },
+    )
+}
+
+/// `proposal_id` must be the global counter's next proposal id
+pub fn create_proposal(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    proposal_id: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let market = pda::market(market_id);
+    let p = ProposalKeys::new(proposal_id);
+    build(
+        accounts::CreateProposal {
+            payer: *payer,
+            market,
+            registry: pda::market_proposals(&market),
+            user_deposit: pda::deposit(&market, payer),
+            reward_mint: *reward_mint,
+            global: pda::global(),
+            vusd_mint: p.vusd_mint,
+            yes_mint: p.yes_mint,
+            no_mint: p.no_mint,
+            vusd_vault: p.vusd_vault,
+            yes_vault: p.yes_vault,
+            no_vault: p.no_vault,
+            yes_pool: p.yes_pool,
+            no_pool: p.no_pool,
+            user_yes: pda::ata(payer, &p.yes_mint),
+            user_no: pda::ata(payer, &p.no_mint),
+            proposal_auth: p.proposal_auth,
+            proposal: p.proposal,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateProposal { data },
+    )
+}
+
+pub fn claim_for_proposal(payer: &Pubkey, market_id: u64, proposal_id: u64) -> Instruction {
+    let market = pda::market(market_id);
+    let p = ProposalKeys::new(proposal_id);
+    build(
+        accounts::ClaimForProposal {
+            payer: *payer,
+            global: pda::global(),
+            proposal: p.proposal,
+            market,
+            deposit_record: pda::deposit(&market, payer),
+            claim_record: pda::claim(&p.proposal, payer),
+            vusd_mint: p.vusd_mint,
+            user_vusd: pda::ata(payer, &p.vusd_mint),
+            proposal_auth: p.proposal_auth,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimForProposal {},
+    )
+}
+
+pub fn mint_yes_no(payer: &Pubkey, market_id: u64, proposal_id: u64, amount: u64) -> Instruction {
+    let p = ProposalKeys::new(proposal_id);
+    build(
+        accounts::MintYesNo {
+            payer: *payer,
+            global: pda::global(),
+            proposal: p.proposal,
+            market: pda::market(market_id),
+            vusd_mint: p.vusd_mint,
+            proposal_auth: p.proposal_auth,
+            user_vusd: pda::ata(payer, &p.vusd_mint),
+            vault_vusd: p.vusd_vault,
+            yes_mint: p.yes_mint,
+            no_mint: p.no_mint,
+            user_yes: pda::ata(payer, &p.yes_mint),
+            user_no: pda::ata(payer, &p.no_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::MintYesNo { amount },
+    )
+}
+
+pub fn redeem_yes_no(payer: &Pubkey, market_id: u64, proposal_id: u64, amount: u64) -> Instruction {
+    let p = ProposalKeys::new(proposal_id);
+    build(
+        accounts::RedeemYesNo {
+            payer: *payer,
+            global: pda::global(),
+            proposal: p.proposal,
+            market: pda::market(market_id),
+            proposal_auth: p.proposal_auth,
+            yes_mint: p.yes_mint,
+            no_mint: p.no_mint,
+            vusd_mint: p.vusd_mint,
+            user_yes: pda::ata(payer, &p.yes_mint),
+            user_no: pda::ata(payer, &p.no_mint),
+            user_vusd: pda::ata(payer, &p.vusd_mint),
+            vault_vusd: p.vusd_vault,
+            token_program: token::ID,
+        },
+        instruction::RedeemYesNo { amount },
+    )
+}
+
+fn swap_accounts(payer: &Pubkey, market_id: u64, proposal_id: u64, side: PoolSide) -> accounts::Swap {
+    let p = ProposalKeys::new(proposal_id);
+    let (pool, token_mint, token_vault) = match side {
+        PoolSide::Yes => (p.yes_pool, p.yes_mint, p.yes_vault),
+        PoolSide::No  => (p.no_pool, p.no_mint, p.no_vault),
+    };
+    let market = pda::market(market_id);
+    accounts::Swap {
+        payer: *payer,
+        global: pda::global(),
+        proposal: p.proposal,
+        market,
+        registry: pda::market_proposals(&market),
+        pool,
+        proposal_auth: p.proposal_auth,
+        token_mint,
+        vusd_mint: p.vusd_mint,
+        token_vault,
+        vusd_vault: p.vusd_vault,
+        user_token: pda::ata(payer, &token_mint),
+        user_vusd: pda::ata(payer, &p.vusd_mint),
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+}
+
+pub fn swap_exact_in(
+    payer: &Pubkey,
+    market_id: u64,
+    proposal_id: u64,
+    side: PoolSide,
+    direction: SwapDirection,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    build(
+        swap_accounts(payer, market_id, proposal_id, side),
+        instruction::SwapExactIn { direction, amount_in, min_amount_out },
+    )
+}
+
+pub fn swap_exact_out(
+    payer: &Pubkey,
+    market_id: u64,
+    proposal_id: u64,
+    side: PoolSide,
+    direction: SwapDirection,
+    amount_out: u64,
+    max_amount_in: u64,
+) -> Instruction {
+    build(
+        swap_accounts(payer, market_id, proposal_id, side),
+        instruction::SwapExactOut { direction, amount_out, max_amount_in },
+    )
+}
+
+/// read-only; the TWAP comes back as little-endian u64 return data
+pub fn yes_twap(proposal_id: u64, window: i64) -> Instruction {
+    let p = ProposalKeys::new(proposal_id);
+    build(
+        accounts::ReadTwap { proposal: p.proposal, yes_pool: p.yes_pool },
+        instruction::YesTwap { window },
+    )
+}
+
+pub fn expire_market(market_id: u64) -> Instruction {
+    build(
+        accounts::ExpireMarket { global: pda::global(), market: pda::market(market_id) },
+        instruction::ExpireMarket {},
+    )
+}
+
+/// `other_proposal_ids` must list every other proposal of the market
+pub fn graduate_proposal(market_id: u64, proposal_id: u64, other_proposal_ids: &[u64]) -> Instruction {
+    let p = ProposalKeys::new(proposal_id);
+    let mut ix = build(
+        accounts::GraduateProposal {
+            global: pda::global(),
+            market: pda::market(market_id),
+            proposal: p.proposal,
+            yes_pool: p.yes_pool,
+        },
+        instruction::GraduateProposal {},
+    );
+    ix.accounts.extend(
+        other_proposal_ids
+            .iter()
+            .map(|id| AccountMeta::new_readonly(ProposalKeys::new(*id).yes_pool, false)),
+    );
+    ix
+}
+
+/// from the payer's associated account of `mint`
+pub fn fund_treasury(payer: &Pubkey, market_id: u64, mint: &Pubkey, amount: u64) -> Instruction {
+    let market = pda::market(market_id);
+    let treasury = pda::treasury(&market);
+    build(
+        accounts::FundTreasury {
+            payer: *payer,
+            global: pda::global(),
+            market,
+            treasury,
+            mint: *mint,
+            payer_token: pda::ata(payer, mint),
+            treasury_token: pda::ata(&treasury, mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::FundTreasury { amount },
+    )
+}
+
+/// `payload` is the accepted proposal's; its instructions' programs and
+/// accounts are appended in the order the program checks them
+pub fn execute_proposal(market_id: u64, proposal_id: u64, payload: &ProposalPayload) -> Instruction {
+    let market = pda::market(market_id);
+    let treasury = pda::treasury(&market);
+    let mut ix = build(
+        accounts::ExecuteProposal {
+            global: pda::global(),
+            market,
+            proposal: pda::proposal(proposal_id),
+            treasury,
+        },
+        instruction::ExecuteProposal {},
+    );
+    // only a transfer with extra multisig signers could fail to build
+    let cpis = payload.instructions(&treasury).expect("treasury signs alone");
+    for cpi in cpis {
+        ix.accounts.push(AccountMeta::new_readonly(cpi.program_id, false));
+        // the treasury signs inside the program, not the transaction
+        ix.accounts.extend(cpi.accounts.into_iter().map(|meta| AccountMeta { is_signer: false, ..meta }));
+    }
+    ix
+}
+
+pub fn resolve_market(resolver: &Pubkey, market_id: u64, outcome_yes: bool) -> Instruction {
+    build(
+        accounts::ResolveMarket {
+            resolver: *resolver,
+            global: pda::global(),
+            market: pda::market(market_id),
+        },
+        instruction::ResolveMarket { outcome_yes },
+    )
+}
+
+/// `price_feed` is the feed in the market's oracle config
+pub fn resolve_with_oracle(market_id: u64, price_feed: &Pubkey) -> Instruction {
+    build(
+        accounts::ResolveWithOracle {
+            global: pda::global(),
+            market: pda::market(market_id),
+            price_feed: *price_feed,
+        },
+        instruction::ResolveWithOracle {},
+    )
+}
+
+/// `winning` is the side the market resolved to
+pub fn redeem_winning(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    proposal_id: u64,
+    winning: PoolSide,
+    amount: u64,
+) -> Instruction {
+    let market = pda::market(market_id);
+    let p = ProposalKeys::new(proposal_id);
+    let winning_mint = match winning {
+        PoolSide::Yes => p.yes_mint,
+        PoolSide::No  => p.no_mint,
+    };
+    build(
+        accounts::RedeemWinning {
+            payer: *payer,
+            global: pda::global(),
+            market,
+            proposal: p.proposal,
+            proposal_auth: p.proposal_auth,
+            winning_mint,
+            vusd_mint: p.vusd_mint,
+            vusd_vault: p.vusd_vault,
+            user_winning: pda::ata(payer, &winning_mint),
+            reward_mint: *reward_mint,
+            market_vault: pda::ata(&market, reward_mint),
+            user_reward: pda::ata(payer, reward_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RedeemWinning { amount },
+    )
+}
+
+fn collect_fees(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    proposal_id: u64,
+    recipient: &Pubkey,
+) -> accounts::CollectFees {
+    let market = pda::market(market_id);
+    let p = ProposalKeys::new(proposal_id);
+    accounts::CollectFees {
+        payer: *payer,
+        global: pda::global(),
+        market,
+        proposal: p.proposal,
+        proposal_auth: p.proposal_auth,
+        vusd_mint: p.vusd_mint,
+        vusd_vault: p.vusd_vault,
+        reward_mint: *reward_mint,
+        market_vault: pda::ata(&market, reward_mint),
+        recipient: *recipient,
+        recipient_reward: pda::ata(recipient, reward_mint),
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+}
+
+/// `creator` is the market's creator; `payer` funds their token account
+pub fn collect_creator_fees(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    accepted_proposal_id: u64,
+    creator: &Pubkey,
+) -> Instruction {
+    build(
+        collect_fees(payer, market_id, reward_mint, accepted_proposal_id, creator),
+        instruction::CollectCreatorFees {},
+    )
+}
+
+/// `fee_treasury` is the global fee treasury; `payer` funds its token account
+pub fn collect_protocol_fees(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    accepted_proposal_id: u64,
+    fee_treasury: &Pubkey,
+) -> Instruction {
+    build(
+        collect_fees(payer, market_id, reward_mint, accepted_proposal_id, fee_treasury),
+        instruction::CollectProtocolFees {},
+    )
+}
+
+/// `accepted_proposal_id` is required unless the market timed out
+pub fn withdraw_deposit(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    accepted_proposal_id: Option<u64>,
+) -> Instruction {
+    let market = pda::market(market_id);
+    let accepted = accepted_proposal_id.map(pda::proposal);
+    build(
+        accounts::WithdrawDeposit {
+            payer: *payer,
+            global: pda::global(),
+            market,
+            deposit_record: pda::deposit(&market, payer),
+            accepted_proposal: accepted,
+            accepted_claim: accepted.map(|proposal| pda::claim(&proposal, payer)),
+            reward_mint: *reward_mint,
+            market_vault: pda::ata(&market, reward_mint),
+            user_token: pda::ata(payer, reward_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawDeposit {},
+    )
+}
+
+pub fn withdraw_unclaimed(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let market = pda::market(market_id);
+    build(
+        accounts::WithdrawUnclaimed {
+            payer: *payer,
+            global: pda::global(),
+            market,
+            deposit_record: pda::deposit(&market, payer),
+            reward_mint: *reward_mint,
+            market_vault: pda::ata(&market, reward_mint),
+            user_token: pda::ata(payer, reward_mint),
+            token_program: token::ID,
+        },
+        instruction::WithdrawUnclaimed { amount },
+    )
+}
+
+/// `accepted_proposal_id` is required unless the market timed out;
+/// `rent_payer` is the one stored in the record
+pub fn close_deposit_record(
+    user: &Pubkey,
+    market_id: u64,
+    accepted_proposal_id: Option<u64>,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    let market = pda::market(market_id);
+    let accepted = accepted_proposal_id.map(pda::proposal);
+    build(
+        accounts::CloseDepositRecord {
+            global: pda::global(),
+            market,
+            user: *user,
+            deposit_record: pda::deposit(&market, user),
+            accepted_proposal: accepted,
+            accepted_claim: accepted.map(|proposal| pda::claim(&proposal, user)),
+            rent_payer: *rent_payer,
+        },
+        instruction::CloseDepositRecord {},
+    )
+}
+
+pub fn close_claim_record(user: &Pubkey, market_id: u64, proposal_id: u64, rent_payer: &Pubkey) -> Instruction {
+    let market = pda::market(market_id);
+    let proposal = pda::proposal(proposal_id);
+    build(
+        accounts::CloseClaimRecord {
+            global: pda::global(),
+            market,
+            proposal,
+            user: *user,
+            claim_record: pda::claim(&proposal, user),
+            deposit_record: pda::deposit(&market, user),
+            rent_payer: *rent_payer,
+        },
+        instruction::CloseClaimRecord {},
+    )
+}
+
+/// `creator` gets the stake back to their `reward_mint` ATA, unless the
+/// proposal was accepted
+pub fn close_proposal(
+    market_id: u64,
+    proposal_id: u64,
+    reward_mint: &Pubkey,
+    creator: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    let p = ProposalKeys::new(proposal_id);
+    let market = pda::market(market_id);
+    build(
+        accounts::CloseProposal {
+            global: pda::global(),
+            market,
+            proposal: p.proposal,
+            proposal_auth: p.proposal_auth,
+            vusd_mint: p.vusd_mint,
+            yes_mint: p.yes_mint,
+            no_mint: p.no_mint,
+            vusd_vault: p.vusd_vault,
+            yes_vault: p.yes_vault,
+            no_vault: p.no_vault,
+            yes_pool: p.yes_pool,
+            no_pool: p.no_pool,
+            market_vault: pda::ata(&market, reward_mint),
+            creator_reward: pda::ata(creator, reward_mint),
+            rent_payer: *rent_payer,
+            token_program: token::ID,
+        },
+        instruction::CloseProposal {},
+    )
+}
+
+/// `fee_treasury` is the global fee treasury, which receives what is left
+pub fn close_market(
+    payer: &Pubkey,
+    market_id: u64,
+    reward_mint: &Pubkey,
+    fee_treasury: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    let market = pda::market(market_id);
+    build(
+        accounts::CloseMarket {
+            payer: *payer,
+            global: pda::global(),
+            market,
+            registry: pda::market_proposals(&market),
+            reward_mint: *reward_mint,
+            market_vault: pda::ata(&market, reward_mint),
+            fee_treasury: *fee_treasury,
+            treasury_reward: pda::ata(fee_treasury, reward_mint),
+            rent_payer: *rent_payer,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CloseMarket {},
+    )
+}
+
+/// `claimants` are all users holding a claim record of the proposal
+pub fn migrate_proposal_auth(payer: &Pubkey, market_id: u64, proposal_id: u64, claimants: &[Pubkey]) -> Instruction {
+    let p = ProposalKeys::new(proposal_id);
+    let legacy = pda::legacy_proposal_auth();
+    let market = pda::market(market_id);
+    let mut ix = build(
+        accounts::MigrateProposalAuth {
+            payer: *payer,
+            global: pda::global(),
+            market,
+            registry: pda::market_proposals(&market),
+            proposal: p.proposal,
+            legacy_auth: legacy,
+            proposal_auth: p.proposal_auth,
+            vusd_mint: p.vusd_mint,
+            yes_mint: p.yes_mint,
+            no_mint: p.no_mint,
+            legacy_vusd_vault: pda::ata(&legacy, &p.vusd_mint),
+            legacy_yes_vault: pda::ata(&legacy, &p.yes_mint),
+            legacy_no_vault: pda::ata(&legacy, &p.no_mint),
+            vusd_vault: p.vusd_vault,
+            yes_vault: p.yes_vault,
+            no_vault: p.no_vault,
+            yes_pool: p.yes_pool,
+            no_pool: p.no_pool,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateProposalAuth { proposal_id },
+    );
+    for user in claimants {
+        ix.accounts.push(AccountMeta::new_readonly(*user, false));
+        ix.accounts.push(AccountMeta::new_readonly(pda::claim(&p.proposal, user), false));
+    }
+    ix
+}
+
+pub fn migrate_global(payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateGlobal {
+            payer: *payer,
+            program_data: pda::program_data(),
+            global: pda::global(),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGlobal {},
+    )
+}
+
+/// `admin` sets the terms a legacy market was created without
+pub fn migrate_market(
+    admin: &Pubkey,
+    market_id: u64,
+    deadline: i64,
+    accept_threshold: u64,
+    twap_window: i64,
+) -> Instruction {
+    let market = pda::market(market_id);
+    build(
+        accounts::MigrateMarket {
+            admin: *admin,
+            global: pda::global(),
+            market,
+            registry: pda::market_proposals(&market),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateMarket { market_id, deadline, accept_threshold, twap_window },
+    )
+}
+
+pub fn migrate_deposit_record(payer: &Pubkey, market_id: u64, user: &Pubkey) -> Instruction {
+    let market = pda::market(market_id);
+    build(
+        accounts::MigrateDepositRecord {
+            payer: *payer,
+            global: pda::global(),
+            market,
+            user: *user,
+            deposit_record: pda::deposit(&market, user),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateDepositRecord {},
+    )
+}
+
+pub fn migrate_claim_record(payer: &Pubkey, proposal_id: u64, user: &Pubkey) -> Instruction {
+    let proposal = pda::proposal(proposal_id);
+    build(
+        accounts::MigrateClaimRecord {
+            payer: *payer,
+            global: pda::global(),
+            proposal,
+            user: *user,
+            claim_record: pda::claim(&proposal, user),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateClaimRecord {},
+    )
+}
@@ -0,0 +1,24 @@
+//! Off-chain helpers for the quantum-markets program: PDA derivation using
+//! the program's own seed constants, one instruction builder per program
+//! instruction, and decoders for the program's accounts, events and
+//! proposal payloads. With the `svm` feature, loading the program into an
+//! in-process LiteSVM.
+
+pub mod decode;
+pub mod events;
+pub mod instructions;
+pub mod payload;
+pub mod pda;
+#[cfg(feature = "svm")]
+pub mod svm;
+
+pub use quantum_markets::ID as PROGRAM_ID;
+pub use quantum_markets::state::config::{MarketConfig, MarketStatus};
+pub use quantum_markets::state::deposit::{ClaimRecord, DepositRecord};
+pub use quantum_markets::state::fees::{FeeKind, FeeRates, Fees};
+pub use quantum_markets::state::global::GlobalState;
+pub use quantum_markets::state::oracle::{OracleConfig, OracleKind};
+pub use quantum_markets::state::payload::{MarketParameter, PayloadAccount, PayloadInstruction, ProposalPayload};
+pub use quantum_markets::state::pool::{Pool, SwapDirection};
+pub use quantum_markets::state::proposal::{PoolSide, ProposalConfig};
+pub use quantum_markets::state::registry::{MarketProposals, ProposalEntry};
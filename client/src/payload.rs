@@ -0,0 +1,51 @@
+//! Decoding proposal payloads into something a UI can show.
+
+use anchor_lang::Result;
+use quantum_markets::state::payload::{MarketParameter, ProposalPayload};
+use quantum_markets::state::proposal::ProposalConfig;
+
+/// the payload stored in a proposal
+pub fn decode(proposal: &ProposalConfig) -> Result<ProposalPayload> {
+    proposal.payload()
+}
+
+pub fn parameter_name(parameter: MarketParameter) -> &'static str {
+    match parameter {
+        MarketParameter::MinDeposit      => "min deposit",
+        MarketParameter::AcceptThreshold => "accept threshold",
+        MarketParameter::TwapWindow      => "twap window",
+        MarketParameter::LpFeeBps        => "LP fee bps",
+        MarketParameter::CreatorFeeBps   => "creator fee bps",
+    }
+}
+
+/// one line per action the proposal would take
+pub fn describe(payload: &ProposalPayload) -> Vec<String> {
+    match payload {
+        ProposalPayload::Text { uri, content_hash } => {
+            vec![format!("text {uri} (content hash {})", hex(content_hash))]
+        }
+        ProposalPayload::Transfer { mint, destination, amount } => {
+            vec![format!("transfer {amount} of mint {mint} from the treasury to {destination}")]
+        }
+        ProposalPayload::ParameterChange { parameter, value } => {
+            vec![format!("set {} to {value}", parameter_name(*parameter))]
+        }
+        ProposalPayload::Instructions(instructions) => instructions
+            .iter()
+            .map(|ix| {
+                let writable = ix.accounts.iter().filter(|a| a.is_writable).count();
+                format!(
+                    "call {} with {} accounts ({writable} writable) and data {}",
+                    ix.program_id,
+                    ix.accounts.len(),
+                    hex(&ix.data),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
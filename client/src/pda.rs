@@ -0,0 +1,127 @@
+use anchor_lang::prelude::Pubkey;
+#[allow(deprecated)] // anchor-lang only re-exports the loader id through this path
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::associated_token::get_associated_token_address;
+use quantum_markets::constants::*;
+use quantum_markets::state::proposal::ProposalConfig;
+use quantum_markets::ID;
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ID).0
+}
+
+pub fn global() -> Pubkey {
+    find(&[GLOBAL_SEED])
+}
+
+/// the program's program data account, holding its upgrade authority
+pub fn program_data() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn market(market_id: u64) -> Pubkey {
+    find(&[MARKET_SEED, &market_id.to_le_bytes()])
+}
+
+/// the market's `MarketProposals` registry
+pub fn market_proposals(market: &Pubkey) -> Pubkey {
+    find(&[PROPOSALS_SEED, market.as_ref()])
+}
+
+/// signs the accepted proposal's CPIs; owns the tokens proposals can move
+pub fn treasury(market: &Pubkey) -> Pubkey {
+    find(&[TREASURY_SEED, market.as_ref()])
+}
+
+pub fn deposit(market: &Pubkey, user: &Pubkey) -> Pubkey {
+    find(&[DEPOSIT_SEED, market.as_ref(), user.as_ref()])
+}
+
+pub fn claim(proposal: &Pubkey, user: &Pubkey) -> Pubkey {
+    find(&[CLAIM_SEED, proposal.as_ref(), user.as_ref()])
+}
+
+pub fn proposal(proposal_id: u64) -> Pubkey {
+    find(&[PROPOSAL_SEED, &proposal_id.to_le_bytes()])
+}
+
+pub fn vusd_mint(proposal_id: u64) -> Pubkey {
+    find(&[VUSD_SEED, &proposal_id.to_le_bytes()])
+}
+
+pub fn yes_mint(proposal_id: u64) -> Pubkey {
+    find(&[YES_MINT_SEED, &proposal_id.to_le_bytes()])
+}
+
+pub fn no_mint(proposal_id: u64) -> Pubkey {
+    find(&[NO_MINT_SEED, &proposal_id.to_le_bytes()])
+}
+
+/// per-proposal mint and vault authority
+pub fn proposal_auth(proposal: &Pubkey) -> Pubkey {
+    find(&[PROPOSAL_AUTH_SEED, proposal.as_ref()])
+}
+
+/// shared authority of proposals created before per-proposal authorities
+pub fn legacy_proposal_auth() -> Pubkey {
+    find(&[PROPOSAL_AUTH_SEED])
+}
+
+pub fn pool(token_vault: &Pubkey) -> Pubkey {
+    find(&[POOL_SEED, token_vault.as_ref()])
+}
+
+/// ATA of `owner` for `mint`; vaults are ATAs of the market or proposal authority
+pub fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, mint)
+}
+
+/// every address that hangs off one proposal id. Pools are seeded from the
+/// vaults they were created with, so for proposals moved off the legacy
+/// authority use [`ProposalKeys::from_config`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProposalKeys {
+    pub id:            u64,
+    pub proposal:      Pubkey,
+    pub proposal_auth: Pubkey,
+    pub vusd_mint:     Pubkey,
+    pub yes_mint:      Pubkey,
+    pub no_mint:       Pubkey,
+    pub vusd_vault:    Pubkey,
+    pub yes_vault:     Pubkey,
+    pub no_vault:      Pubkey,
+    pub yes_pool:      Pubkey,
+    pub no_pool:       Pubkey,
+}
+
+impl ProposalKeys {
+    pub fn new(proposal_id: u64) -> Self {
+        let proposal = proposal(proposal_id);
+        let auth = proposal_auth(&proposal);
+        let (vusd_mint, yes_mint, no_mint) =
+            (vusd_mint(proposal_id), yes_mint(proposal_id), no_mint(proposal_id));
+        let (yes_vault, no_vault) = (ata(&auth, &yes_mint), ata(&auth, &no_mint));
+        Self {
+            id: proposal_id,
+            proposal,
+            proposal_auth: auth,
+            vusd_mint,
+            yes_mint,
+            no_mint,
+            vusd_vault: ata(&auth, &vusd_mint),
+            yes_vault,
+            no_vault,
+            yes_pool: pool(&yes_vault),
+            no_pool: pool(&no_vault),
+        }
+    }
+
+    /// same, but with the pool addresses recorded on-chain
+    pub fn from_config(config: &ProposalConfig) -> Self {
+        Self {
+            yes_pool: config.yes_pool,
+            no_pool: config.no_pool,
+            ..Self::new(config.id)
+        }
+    }
+}
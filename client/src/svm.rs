@@ -0,0 +1,38 @@
+//! Loading the program into an in-process LiteSVM, shared by the
+//! integration tests and the CLI's `--in-process` mode.
+
+use anchor_lang::prelude::ProgramData;
+use anchor_lang::Owner;
+use litesvm::error::LiteSVMError;
+use litesvm::LiteSVM;
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{pda, PROGRAM_ID};
+
+/// install the program behind the upgradeable loader, as `solana program
+/// deploy` would, with `authority` as its upgrade authority
+pub fn deploy_upgradeable(svm: &mut LiteSVM, elf: &[u8], authority: &Pubkey) -> Result<(), LiteSVMError> {
+    let loader = ProgramData::owner();
+    let metadata = UpgradeableLoaderState::size_of_programdata_metadata();
+    let len = metadata + elf.len();
+    let mut program_data = Account::new_data_with_space(
+        svm.minimum_balance_for_rent_exemption(len),
+        &UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address: Some(*authority) },
+        len,
+        &loader,
+    )
+    .expect("program data metadata fits");
+    program_data.data[metadata..].copy_from_slice(elf);
+    svm.set_account(pda::program_data(), program_data)?;
+
+    let mut program = Account::new_data(
+        svm.minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_program()),
+        &UpgradeableLoaderState::Program { programdata_address: pda::program_data() },
+        &loader,
+    )
+    .expect("program account serializes");
+    program.executable = true;
+    svm.set_account(PROGRAM_ID, program)
+}
@@ -0,0 +1,315 @@
+//! How each event changes the tables.
+
+use anyhow::Result;
+use quantum_markets::constants::PRICE_SCALE;
+use quantum_markets_client::events::QuantumEvent;
+use quantum_markets_client::{FeeKind, MarketParameter, OracleKind, PoolSide, ProposalPayload, SwapDirection};
+use rusqlite::{params, OptionalExtension, Transaction};
+
+use crate::TxLogs;
+
+pub(crate) fn side(side: PoolSide) -> &'static str {
+    match side {
+        PoolSide::Yes => "yes",
+        PoolSide::No => "no",
+    }
+}
+
+fn direction(direction: SwapDirection) -> &'static str {
+    match direction {
+        SwapDirection::Buy => "buy",
+        SwapDirection::Sell => "sell",
+    }
+}
+
+fn spot_price(reserve_token: u64, reserve_vusd: u64) -> i64 {
+    if reserve_token == 0 {
+        return 0;
+    }
+    (reserve_vusd as u128 * PRICE_SCALE as u128 / reserve_token as u128) as i64
+}
+
+fn record_price(db: &Transaction, proposal_id: u64, side: &str, token: u64, vusd: u64, at: i64) -> Result<()> {
+    db.execute(
+        "INSERT INTO prices (proposal_id, side, price, reserve_token, reserve_vusd, timestamp)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![proposal_id as i64, side, spot_price(token, vusd), token as i64, vusd as i64, at],
+    )?;
+    Ok(())
+}
+
+/// set a user's deposit record to `amount`, adding to the running totals
+fn set_deposit(db: &Transaction, market_id: u64, user: &str, amount: u64, deposited: u64, withdrawn: u64) -> Result<()> {
+    db.execute(
+        "INSERT INTO deposits (market_id, user, amount, deposited, withdrawn) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (market_id, user) DO UPDATE SET
+             amount = excluded.amount,
+             deposited = deposited + excluded.deposited,
+             withdrawn = withdrawn + excluded.withdrawn",
+        params![market_id as i64, user, amount as i64, deposited as i64, withdrawn as i64],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn event(db: &Transaction, tx: &TxLogs, event: &QuantumEvent) -> Result<()> {
+    match event {
+        QuantumEvent::MarketCreated(e) => {
+            db.execute(
+                "INSERT OR REPLACE INTO markets (id, address, creator, reward_mint, resolver, title, min_deposit,
+                     strike_price, accept_threshold, twap_window, deadline, created_at,
+                     lp_fee_bps, creator_fee_bps, protocol_fee_bps, execution_delay, oracle_feed, status)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, 'open')",
+                params![
+                    e.market_id as i64,
+                    e.market.to_string(),
+                    e.creator.to_string(),
+                    e.reward_mint.to_string(),
+                    e.resolver.to_string(),
+                    e.title,
+                    e.min_deposit as i64,
+                    e.strike_price as i64,
+                    e.accept_threshold as i64,
+                    e.twap_window,
+                    e.deadline,
+                    e.created_at,
+                    e.lp_fee_bps,
+                    e.creator_fee_bps,
+                    e.protocol_fee_bps,
+                    e.execution_delay,
+                    (e.oracle_kind != OracleKind::None).then(|| e.oracle_feed.to_string()),
+                ],
+            )?;
+        }
+        QuantumEvent::Deposited(e) => {
+            set_deposit(db, e.market_id, &e.user.to_string(), e.total, e.amount, 0)?;
+        }
+        QuantumEvent::ProposalCreated(e) => {
+            let price = spot_price(e.pool_tokens, e.pool_vusd);
+            db.execute(
+                "INSERT OR REPLACE INTO proposals (id, market_id, address, creator, vusd_mint, yes_mint, no_mint,
+                     yes_pool, no_pool, locked, data, created_at,
+                     yes_reserve_token, yes_reserve_vusd, yes_price, no_reserve_token, no_reserve_vusd, no_price)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?13, ?14, ?15)",
+                params![
+                    e.proposal_id as i64,
+                    e.market_id as i64,
+                    e.proposal.to_string(),
+                    e.creator.to_string(),
+                    e.vusd_mint.to_string(),
+                    e.yes_mint.to_string(),
+                    e.no_mint.to_string(),
+                    e.yes_pool.to_string(),
+                    e.no_pool.to_string(),
+                    e.locked as i64,
+                    e.data,
+                    e.created_at,
+                    e.pool_tokens as i64,
+                    e.pool_vusd as i64,
+                    price,
+                ],
+            )?;
+            // the min deposit comes out of the creator's record
+            db.execute(
+                "UPDATE deposits SET amount = amount - ?3 WHERE market_id = ?1 AND user = ?2",
+                params![e.market_id as i64, e.creator.to_string(), e.locked as i64],
+            )?;
+            for s in ["yes", "no"] {
+                record_price(db, e.proposal_id, s, e.pool_tokens, e.pool_vusd, e.created_at)?;
+            }
+        }
+        QuantumEvent::VusdClaimed(e) => {
+            db.execute(
+                "INSERT INTO claims (proposal_id, user, amount) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (proposal_id, user) DO UPDATE SET amount = amount + excluded.amount",
+                params![e.proposal_id as i64, e.user.to_string(), e.amount as i64],
+            )?;
+        }
+        QuantumEvent::YesNoMinted(e) => {
+            db.execute(
+                "INSERT INTO conversions (signature, proposal_id, user, kind, amount) VALUES (?1, ?2, ?3, 'mint', ?4)",
+                params![tx.signature, e.proposal_id as i64, e.user.to_string(), e.amount as i64],
+            )?;
+            db.execute(
+                "UPDATE proposals SET minted = minted + ?2 WHERE id = ?1",
+                params![e.proposal_id as i64, e.amount as i64],
+            )?;
+        }
+        QuantumEvent::YesNoRedeemed(e) => {
+            db.execute(
+                "INSERT INTO conversions (signature, proposal_id, user, kind, amount) VALUES (?1, ?2, ?3, 'redeem', ?4)",
+                params![tx.signature, e.proposal_id as i64, e.user.to_string(), e.amount as i64],
+            )?;
+            db.execute(
+                "UPDATE proposals SET redeemed = redeemed + ?2 WHERE id = ?1",
+                params![e.proposal_id as i64, e.amount as i64],
+            )?;
+        }
+        QuantumEvent::Swapped(e) => {
+            let s = side(e.side);
+            db.execute(
+                "INSERT INTO swaps (signature, proposal_id, user, side, direction, amount_in, amount_out,
+                     lp_fee, protocol_fee, creator_fee, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    tx.signature,
+                    e.proposal_id as i64,
+                    e.user.to_string(),
+                    s,
+                    direction(e.direction),
+                    e.amount_in as i64,
+                    e.amount_out as i64,
+                    e.lp_fee as i64,
+                    e.protocol_fee as i64,
+                    e.creator_fee as i64,
+                    e.timestamp,
+                ],
+            )?;
+            // column names can't be bound, so pick the statement by side
+            let update = match e.side {
+                PoolSide::Yes => {
+                    "UPDATE proposals SET yes_reserve_token = ?2, yes_reserve_vusd = ?3, yes_price = ?4 WHERE id = ?1"
+                }
+                PoolSide::No => {
+                    "UPDATE proposals SET no_reserve_token = ?2, no_reserve_vusd = ?3, no_price = ?4 WHERE id = ?1"
+                }
+            };
+            db.execute(
+                update,
+                params![e.proposal_id as i64, e.reserve_token as i64, e.reserve_vusd as i64, e.price as i64],
+            )?;
+            record_price(db, e.proposal_id, s, e.reserve_token, e.reserve_vusd, e.timestamp)?;
+        }
+        QuantumEvent::MarketExpired(e) => {
+            db.execute("UPDATE markets SET status = 'timeout' WHERE id = ?1", [e.market_id as i64])?;
+        }
+        QuantumEvent::ProposalAccepted(e) => {
+            db.execute(
+                "UPDATE markets SET status = 'accepted', accepted_proposal = ?2, accepted_twap = ?3, accepted_at = ?4
+                 WHERE id = ?1",
+                params![e.market_id as i64, e.proposal_id as i64, e.twap as i64, e.timestamp],
+            )?;
+        }
+        QuantumEvent::ProposalExecuted(e) => {
+            db.execute(
+                "UPDATE proposals SET executed_at = ?2 WHERE id = ?1",
+                params![e.proposal_id as i64, e.executed_at],
+            )?;
+            // the event doesn't repeat the payload; a parameter change is
+            // read back from the data logged at creation
+            let data: Option<Vec<u8>> = db
+                .query_row("SELECT data FROM proposals WHERE id = ?1", [e.proposal_id as i64], |r| r.get(0))
+                .optional()?;
+            if let Some(ProposalPayload::ParameterChange { parameter, value }) =
+                data.and_then(|data| ProposalPayload::decode(&data).ok())
+            {
+                let column = match parameter {
+                    MarketParameter::MinDeposit => "min_deposit",
+                    MarketParameter::AcceptThreshold => "accept_threshold",
+                    MarketParameter::TwapWindow => "twap_window",
+                    MarketParameter::LpFeeBps => "lp_fee_bps",
+                    MarketParameter::CreatorFeeBps => "creator_fee_bps",
+                };
+                db.execute(
+                    &format!("UPDATE markets SET {column} = ?2 WHERE id = ?1"),
+                    params![e.market_id as i64, value as i64],
+                )?;
+            }
+        }
+        QuantumEvent::TreasuryFunded(e) => {
+            db.execute(
+                "INSERT INTO treasury_fundings (signature, market_id, funder, mint, amount, balance)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    tx.signature,
+                    e.market_id as i64,
+                    e.funder.to_string(),
+                    e.mint.to_string(),
+                    e.amount as i64,
+                    e.balance as i64,
+                ],
+            )?;
+        }
+        QuantumEvent::OracleSettled(e) => {
+            db.execute(
+                "UPDATE markets SET settlement_price = ?2 WHERE id = ?1",
+                params![e.market_id as i64, e.price],
+            )?;
+        }
+        QuantumEvent::MarketResolved(e) => {
+            let status = if e.outcome_yes { "resolved_yes" } else { "resolved_no" };
+            db.execute(
+                "UPDATE markets SET status = ?2, resolved_at = ?3 WHERE id = ?1",
+                params![e.market_id as i64, status, e.timestamp],
+            )?;
+        }
+        QuantumEvent::WinningRedeemed(e) => {
+            db.execute(
+                "INSERT INTO redemptions (signature, market_id, proposal_id, user, side, amount, payout)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    tx.signature,
+                    e.market_id as i64,
+                    e.proposal_id as i64,
+                    e.user.to_string(),
+                    side(e.side),
+                    e.amount as i64,
+                    e.payout as i64,
+                ],
+            )?;
+        }
+        QuantumEvent::FeesCollected(e) => {
+            let kind = match e.kind {
+                FeeKind::Creator => "creator",
+                FeeKind::Protocol => "protocol",
+            };
+            db.execute(
+                "INSERT INTO fee_collections (signature, market_id, proposal_id, kind, recipient, amount)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    tx.signature,
+                    e.market_id as i64,
+                    e.proposal_id as i64,
+                    kind,
+                    e.recipient.to_string(),
+                    e.amount as i64,
+                ],
+            )?;
+        }
+        QuantumEvent::DepositWithdrawn(e) => {
+            set_deposit(db, e.market_id, &e.user.to_string(), e.remaining, 0, e.amount)?;
+        }
+        QuantumEvent::UnclaimedWithdrawn(e) => {
+            set_deposit(db, e.market_id, &e.user.to_string(), e.remaining, 0, e.amount)?;
+        }
+        // follows the ProposalCreated that indexes a legacy proposal; the
+        // original create_proposal already took the stake out of the
+        // creator's deposit record
+        QuantumEvent::ProposalAuthMigrated(e) => {
+            let proposal: Option<(i64, String, i64)> = db
+                .query_row(
+                    "SELECT market_id, creator, locked FROM proposals WHERE id = ?1",
+                    [e.proposal_id as i64],
+                    |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+                )
+                .optional()?;
+            if let Some((market_id, creator, locked)) = proposal {
+                db.execute(
+                    "UPDATE deposits SET amount = amount + ?3 WHERE market_id = ?1 AND user = ?2",
+                    params![market_id, creator, locked],
+                )?;
+            }
+        }
+        // nothing indexed depends on these; migrate_market also emits the
+        // MarketCreated that indexes a legacy market
+        QuantumEvent::GlobalInitialized(_)
+        | QuantumEvent::GlobalMigrated(_)
+        | QuantumEvent::MarketMigrated(_)
+        | QuantumEvent::GlobalConfigUpdated(_) => {}
+        // closing accounts only refunds rent; the history stays indexed
+        QuantumEvent::DepositRecordClosed(_)
+        | QuantumEvent::ClaimRecordClosed(_)
+        | QuantumEvent::ProposalClosed(_)
+        | QuantumEvent::MarketClosed(_) => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Off-chain indexer for quantum-markets. It decodes the events the program
+//! logs, from a dump file or a validator's RPC, and folds them into an
+//! SQLite database of markets, proposals, deposits, claims, YES/NO
+//! conversions and per-proposal price history.
+
+mod apply;
+pub mod query;
+mod schema;
+pub mod source;
+
+use std::path::Path;
+
+use anyhow::Result;
+use quantum_markets_client::events::parse_logs;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub use source::{RpcSource, TxLogs};
+
+/// cursor holding the newest signature read over RPC
+const RPC_CURSOR: &str = "rpc:last_signature";
+
+pub struct Indexer {
+    conn: Connection,
+}
+
+impl Indexer {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(schema::SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// fold one transaction's events into the database; returns the number
+    /// of events applied, 0 for transactions already seen
+    pub fn ingest(&mut self, tx: &TxLogs) -> Result<usize> {
+        let db = self.conn.transaction()?;
+        let seen: Option<String> = db
+            .query_row("SELECT signature FROM transactions WHERE signature = ?1", [&tx.signature], |r| r.get(0))
+            .optional()?;
+        if seen.is_some() {
+            return Ok(0);
+        }
+        db.execute(
+            "INSERT INTO transactions (signature, slot, block_time) VALUES (?1, ?2, ?3)",
+            params![tx.signature, tx.slot as i64, tx.block_time],
+        )?;
+
+        let events = parse_logs(&tx.logs);
+        for event in &events {
+            apply::event(&db, tx, event)?;
+        }
+        db.commit()?;
+        Ok(events.len())
+    }
+
+    /// fetch and ingest every program transaction since the last sync;
+    /// returns the number of events applied
+    pub fn sync(&mut self, rpc: &RpcSource) -> Result<usize> {
+        let until = self.cursor(RPC_CURSOR)?;
+        let mut applied = 0;
+        for signature in rpc.signatures_since(until.as_deref())? {
+            applied += self.ingest(&rpc.transaction(&signature)?)?;
+            self.set_cursor(RPC_CURSOR, &signature)?;
+        }
+        Ok(applied)
+    }
+
+    /// value stored under `key` by [`Indexer::set_cursor`]
+    pub fn cursor(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM cursors WHERE key = ?1", [key], |r| r.get(0))
+            .optional()?)
+    }
+
+    /// remember how far a source has been read
+    pub fn set_cursor(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO cursors (key, value) VALUES (?1, ?2)
+             ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+            [key, value],
+        )?;
+        Ok(())
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+}
@@ -0,0 +1,122 @@
+//! `qm-indexer`: keep an SQLite database of quantum-markets state up to
+//! date from a log dump or a validator, and query it.
+
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use quantum_markets::constants::PRICE_SCALE;
+use quantum_markets_client::PoolSide;
+use quantum_markets_indexer::source::read_dump;
+use quantum_markets_indexer::{Indexer, RpcSource};
+
+#[derive(Parser)]
+#[command(name = "qm-indexer", version, about = "Index quantum-markets events into SQLite")]
+struct Cli {
+    /// SQLite database, created if missing
+    #[arg(long, global = true, default_value = "quantum-markets.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Ingest a JSON-lines dump of {signature, slot, block_time, logs}
+    IngestFile { dump: PathBuf },
+    /// Ingest the program's transactions from a validator
+    Sync {
+        #[arg(long, short, default_value = "http://127.0.0.1:8899")]
+        url: String,
+        /// Keep polling, every this many seconds
+        #[arg(long, value_name = "SECONDS")]
+        follow: Option<u64>,
+    },
+    /// List markets
+    Markets,
+    /// Show a market with its proposals ranked by YES price
+    Market { id: u64 },
+    /// Show a proposal's price history
+    History {
+        proposal: u64,
+        #[arg(long, value_enum, default_value = "yes")]
+        side: Side,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Side {
+    Yes,
+    No,
+}
+
+fn price(value: u64) -> String {
+    format!("{}.{:06}", value / PRICE_SCALE, value % PRICE_SCALE)
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut indexer = Indexer::open(&cli.db)?;
+
+    match cli.command {
+        Command::IngestFile { dump } => {
+            let txs = read_dump(&dump)?;
+            let mut events = 0;
+            for tx in &txs {
+                events += indexer.ingest(tx)?;
+            }
+            println!("{} transactions, {events} events", txs.len());
+        }
+        Command::Sync { url, follow } => {
+            let rpc = RpcSource::new(url);
+            loop {
+                let events = indexer.sync(&rpc)?;
+                println!("{events} events");
+                let Some(seconds) = follow else { break };
+                sleep(Duration::from_secs(seconds));
+            }
+        }
+        Command::Markets => {
+            for m in indexer.markets()? {
+                println!("{:>4}  {:<12} deposits {:>12}  {}", m.id, m.status, m.total_deposits, m.title);
+            }
+        }
+        Command::Market { id } => {
+            let m = indexer.market(id)?.ok_or_else(|| anyhow!("market {id} not indexed"))?;
+            println!("market            {} ({})", m.id, m.address);
+            println!("title             {}", m.title);
+            println!("status            {}", m.status);
+            println!("deadline          {}", m.deadline);
+            println!("deposits          {}", m.total_deposits);
+            if let (Some(proposal), Some(twap)) = (m.accepted_proposal, m.accepted_twap) {
+                println!("accepted          {proposal} at TWAP {}", price(twap));
+            }
+            if let Some(feed) = &m.oracle_feed {
+                println!("oracle            {feed}");
+            }
+            if let Some(settlement) = m.settlement_price {
+                let sign = if settlement < 0 { "-" } else { "" };
+                println!("settled at        {sign}{}", price(settlement.unsigned_abs()));
+            }
+            for t in indexer.treasury_funding(id)? {
+                println!("treasury          {} funded {} (balance {})", t.mint, t.funded, t.balance);
+            }
+            for p in indexer.proposals_by_yes_price(id)? {
+                println!("  proposal {:>4}   YES {}  NO {}  by {}", p.id, price(p.yes_price), price(p.no_price), p.creator);
+            }
+        }
+        Command::History { proposal, side } => {
+            let side = match side {
+                Side::Yes => PoolSide::Yes,
+                Side::No => PoolSide::No,
+            };
+            for point in indexer.price_history(proposal, side)? {
+                println!("{:>12}  {}", point.timestamp, price(point.price));
+            }
+        }
+    }
+    Ok(())
+}
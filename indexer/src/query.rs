@@ -0,0 +1,209 @@
+//! Read side of the database.
+
+use anyhow::Result;
+use rusqlite::{params, OptionalExtension, Row};
+use quantum_markets_client::PoolSide;
+use serde::Serialize;
+
+use crate::apply::side as side_name;
+use crate::Indexer;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Market {
+    pub id:                u64,
+    pub address:           String,
+    pub creator:           String,
+    pub reward_mint:       String,
+    pub resolver:          String,
+    pub title:             String,
+    pub min_deposit:       u64,
+    pub accept_threshold:  u64,
+    pub deadline:          i64,
+    pub created_at:        i64,
+    pub status:            String,
+    pub accepted_proposal: Option<u64>,
+    pub accepted_twap:     Option<u64>,
+    pub total_deposits:    u64,       // sum of the deposit records
+    pub oracle_feed:       Option<String>,
+    pub settlement_price:  Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Proposal {
+    pub id:          u64,
+    pub market_id:   u64,
+    pub address:     String,
+    pub creator:     String,
+    pub created_at:  i64,
+    pub yes_price:   u64,              // spot vUSD per token, PRICE_SCALE decimals
+    pub no_price:    u64,
+    pub claimed:     u64,              // vUSD claimed against the proposal
+    pub minted:      u64,
+    pub redeemed:    u64,
+    pub executed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Deposit {
+    pub user:      String,
+    pub amount:    u64,
+    pub deposited: u64,
+    pub withdrawn: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Claim {
+    pub user:   String,
+    pub amount: u64,
+}
+
+/// what a market treasury was funded with, per mint; payouts by executed
+/// proposals aren't logged, so `balance` is as of the last funding
+#[derive(Debug, Clone, Serialize)]
+pub struct TreasuryFunding {
+    pub mint:    String,
+    pub funded:  u64,
+    pub balance: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PricePoint {
+    pub timestamp:     i64,
+    pub price:         u64,
+    pub reserve_token: u64,
+    pub reserve_vusd:  u64,
+}
+
+const MARKET: &str = "
+    SELECT m.id, m.address, m.creator, m.reward_mint, m.resolver, m.title, m.min_deposit, m.accept_threshold,
+           m.deadline, m.created_at, m.status, m.accepted_proposal, m.accepted_twap,
+           (SELECT COALESCE(SUM(amount), 0) FROM deposits d WHERE d.market_id = m.id),
+           m.oracle_feed, m.settlement_price
+    FROM markets m";
+
+const PROPOSAL: &str = "
+    SELECT p.id, p.market_id, p.address, p.creator, p.created_at, p.yes_price, p.no_price,
+           (SELECT COALESCE(SUM(amount), 0) FROM claims c WHERE c.proposal_id = p.id),
+           p.minted, p.redeemed, p.executed_at
+    FROM proposals p";
+
+fn market(r: &Row) -> rusqlite::Result<Market> {
+    Ok(Market {
+        id:                r.get::<_, i64>(0)? as u64,
+        address:           r.get(1)?,
+        creator:           r.get(2)?,
+        reward_mint:       r.get(3)?,
+        resolver:          r.get(4)?,
+        title:             r.get(5)?,
+        min_deposit:       r.get::<_, i64>(6)? as u64,
+        accept_threshold:  r.get::<_, i64>(7)? as u64,
+        deadline:          r.get(8)?,
+        created_at:        r.get(9)?,
+        status:            r.get(10)?,
+        accepted_proposal: r.get::<_, Option<i64>>(11)?.map(|id| id as u64),
+        accepted_twap:     r.get::<_, Option<i64>>(12)?.map(|twap| twap as u64),
+        total_deposits:    r.get::<_, i64>(13)? as u64,
+        oracle_feed:       r.get(14)?,
+        settlement_price:  r.get(15)?,
+    })
+}
+
+fn proposal(r: &Row) -> rusqlite::Result<Proposal> {
+    Ok(Proposal {
+        id:          r.get::<_, i64>(0)? as u64,
+        market_id:   r.get::<_, i64>(1)? as u64,
+        address:     r.get(2)?,
+        creator:     r.get(3)?,
+        created_at:  r.get(4)?,
+        yes_price:   r.get::<_, i64>(5)? as u64,
+        no_price:    r.get::<_, i64>(6)? as u64,
+        claimed:     r.get::<_, i64>(7)? as u64,
+        minted:      r.get::<_, i64>(8)? as u64,
+        redeemed:    r.get::<_, i64>(9)? as u64,
+        executed_at: r.get(10)?,
+    })
+}
+
+impl Indexer {
+    pub fn markets(&self) -> Result<Vec<Market>> {
+        let mut stmt = self.conn.prepare(&format!("{MARKET} ORDER BY m.id"))?;
+        let rows = stmt.query_map([], market)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn market(&self, id: u64) -> Result<Option<Market>> {
+        Ok(self.conn.query_row(&format!("{MARKET} WHERE m.id = ?1"), [id as i64], market).optional()?)
+    }
+
+    pub fn proposal(&self, id: u64) -> Result<Option<Proposal>> {
+        Ok(self.conn.query_row(&format!("{PROPOSAL} WHERE p.id = ?1"), [id as i64], proposal).optional()?)
+    }
+
+    /// proposals of a market, highest current YES price first
+    pub fn proposals_by_yes_price(&self, market_id: u64) -> Result<Vec<Proposal>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("{PROPOSAL} WHERE p.market_id = ?1 ORDER BY p.yes_price DESC, p.id"))?;
+        let rows = stmt.query_map([market_id as i64], proposal)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn deposits(&self, market_id: u64) -> Result<Vec<Deposit>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT user, amount, deposited, withdrawn FROM deposits WHERE market_id = ?1 ORDER BY user",
+        )?;
+        let rows = stmt.query_map([market_id as i64], |r| {
+            Ok(Deposit {
+                user:      r.get(0)?,
+                amount:    r.get::<_, i64>(1)? as u64,
+                deposited: r.get::<_, i64>(2)? as u64,
+                withdrawn: r.get::<_, i64>(3)? as u64,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn claims(&self, proposal_id: u64) -> Result<Vec<Claim>> {
+        let mut stmt =
+            self.conn.prepare("SELECT user, amount FROM claims WHERE proposal_id = ?1 ORDER BY user")?;
+        let rows = stmt.query_map([proposal_id as i64], |r| {
+            Ok(Claim { user: r.get(0)?, amount: r.get::<_, i64>(1)? as u64 })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn treasury_funding(&self, market_id: u64) -> Result<Vec<TreasuryFunding>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT mint, SUM(amount),
+                    (SELECT balance FROM treasury_fundings l
+                     WHERE l.market_id = f.market_id AND l.mint = f.mint ORDER BY l.id DESC LIMIT 1)
+             FROM treasury_fundings f WHERE market_id = ?1 GROUP BY mint ORDER BY mint",
+        )?;
+        let rows = stmt.query_map([market_id as i64], |r| {
+            Ok(TreasuryFunding {
+                mint:    r.get(0)?,
+                funded:  r.get::<_, i64>(1)? as u64,
+                balance: r.get::<_, i64>(2)? as u64,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// spot prices of one pool of a proposal, oldest first, starting at the
+    /// opening price
+    pub fn price_history(&self, proposal_id: u64, side: PoolSide) -> Result<Vec<PricePoint>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp, price, reserve_token, reserve_vusd FROM prices
+             WHERE proposal_id = ?1 AND side = ?2 ORDER BY timestamp, id",
+        )?;
+        let rows = stmt.query_map(params![proposal_id as i64, side_name(side)], |r| {
+            Ok(PricePoint {
+                timestamp:     r.get(0)?,
+                price:         r.get::<_, i64>(1)? as u64,
+                reserve_token: r.get::<_, i64>(2)? as u64,
+                reserve_vusd:  r.get::<_, i64>(3)? as u64,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
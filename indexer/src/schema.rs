@@ -0,0 +1,148 @@
+//! Tables mirror the program's accounts where an event carries the data;
+//! amounts are stored as INTEGER (i64), prices in PRICE_SCALE decimals.
+
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature   TEXT PRIMARY KEY,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS cursors (
+    key     TEXT PRIMARY KEY,
+    value   TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS markets (
+    id                  INTEGER PRIMARY KEY,
+    address             TEXT NOT NULL,
+    creator             TEXT NOT NULL,
+    reward_mint         TEXT NOT NULL,
+    resolver            TEXT NOT NULL,
+    title               TEXT NOT NULL,
+    min_deposit         INTEGER NOT NULL,
+    strike_price        INTEGER NOT NULL,
+    accept_threshold    INTEGER NOT NULL,
+    twap_window         INTEGER NOT NULL,
+    deadline            INTEGER NOT NULL,
+    created_at          INTEGER NOT NULL,
+    lp_fee_bps          INTEGER NOT NULL DEFAULT 0,
+    creator_fee_bps     INTEGER NOT NULL DEFAULT 0,
+    protocol_fee_bps    INTEGER NOT NULL DEFAULT 0,
+    execution_delay     INTEGER NOT NULL DEFAULT 0,
+    oracle_feed         TEXT,               -- set when resolved from a price feed
+    status              TEXT NOT NULL,      -- open | accepted | timeout | resolved_yes | resolved_no
+    accepted_proposal   INTEGER,
+    accepted_twap       INTEGER,
+    accepted_at         INTEGER,
+    resolved_at         INTEGER,
+    settlement_price    INTEGER             -- oracle price it resolved at
+);
+
+CREATE TABLE IF NOT EXISTS proposals (
+    id                  INTEGER PRIMARY KEY,
+    market_id           INTEGER NOT NULL,
+    address             TEXT NOT NULL,
+    creator             TEXT NOT NULL,
+    vusd_mint           TEXT NOT NULL,
+    yes_mint            TEXT NOT NULL,
+    no_mint             TEXT NOT NULL,
+    yes_pool            TEXT NOT NULL,
+    no_pool             TEXT NOT NULL,
+    locked              INTEGER NOT NULL,
+    data                BLOB NOT NULL,
+    created_at          INTEGER NOT NULL,
+    yes_reserve_token   INTEGER NOT NULL,
+    yes_reserve_vusd    INTEGER NOT NULL,
+    yes_price           INTEGER NOT NULL,
+    no_reserve_token    INTEGER NOT NULL,
+    no_reserve_vusd     INTEGER NOT NULL,
+    no_price            INTEGER NOT NULL,
+    minted              INTEGER NOT NULL DEFAULT 0,     -- YES/NO pairs minted for vUSD
+    redeemed            INTEGER NOT NULL DEFAULT 0,     -- pairs redeemed back
+    executed_at         INTEGER
+);
+CREATE INDEX IF NOT EXISTS proposals_market ON proposals (market_id);
+
+CREATE TABLE IF NOT EXISTS deposits (
+    market_id   INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    amount      INTEGER NOT NULL,       -- the user's deposit record
+    deposited   INTEGER NOT NULL DEFAULT 0,
+    withdrawn   INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (market_id, user)
+);
+
+CREATE TABLE IF NOT EXISTS claims (
+    proposal_id INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    amount      INTEGER NOT NULL,
+    PRIMARY KEY (proposal_id, user)
+);
+
+CREATE TABLE IF NOT EXISTS conversions (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature   TEXT NOT NULL,
+    proposal_id INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    kind        TEXT NOT NULL,          -- mint | redeem
+    amount      INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS swaps (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature   TEXT NOT NULL,
+    proposal_id INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    side        TEXT NOT NULL,          -- yes | no
+    direction   TEXT NOT NULL,          -- buy | sell
+    amount_in   INTEGER NOT NULL,
+    amount_out  INTEGER NOT NULL,
+    lp_fee      INTEGER NOT NULL DEFAULT 0,     -- vUSD, part of the vUSD leg
+    protocol_fee INTEGER NOT NULL DEFAULT 0,
+    creator_fee INTEGER NOT NULL DEFAULT 0,
+    timestamp   INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS prices (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    proposal_id     INTEGER NOT NULL,
+    side            TEXT NOT NULL,
+    price           INTEGER NOT NULL,
+    reserve_token   INTEGER NOT NULL,
+    reserve_vusd    INTEGER NOT NULL,
+    timestamp       INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS prices_proposal ON prices (proposal_id, side, timestamp);
+
+CREATE TABLE IF NOT EXISTS redemptions (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature   TEXT NOT NULL,
+    market_id   INTEGER NOT NULL,
+    proposal_id INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    side        TEXT NOT NULL,
+    amount      INTEGER NOT NULL,
+    payout      INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS fee_collections (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature   TEXT NOT NULL,
+    market_id   INTEGER NOT NULL,
+    proposal_id INTEGER NOT NULL,
+    kind        TEXT NOT NULL,          -- creator | protocol
+    recipient   TEXT NOT NULL,
+    amount      INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS treasury_fundings (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature   TEXT NOT NULL,
+    market_id   INTEGER NOT NULL,
+    funder      TEXT NOT NULL,
+    mint        TEXT NOT NULL,
+    amount      INTEGER NOT NULL,
+    balance     INTEGER NOT NULL        -- treasury account of `mint` afterwards
+);
+";
@@ -0,0 +1,117 @@
+//! Where transaction logs come from: a JSON-lines dump, or a validator's
+//! JSON-RPC (`getSignaturesForAddress` + `getTransaction`).
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use quantum_markets_client::PROGRAM_ID;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// one confirmed transaction's log messages; a dump file holds one of these
+/// per line, in execution order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxLogs {
+    pub signature:  String,
+    pub slot:       u64,
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    pub logs:       Vec<String>,
+}
+
+/// read a dump file; blank lines are skipped
+pub fn read_dump(path: impl AsRef<Path>) -> Result<Vec<TxLogs>> {
+    let path = path.as_ref();
+    let file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut txs = Vec::new();
+    for (n, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tx = serde_json::from_str(&line).with_context(|| format!("{}:{}", path.display(), n + 1))?;
+        txs.push(tx);
+    }
+    Ok(txs)
+}
+
+const PAGE: usize = 1_000; // getSignaturesForAddress limit
+
+pub struct RpcSource {
+    url: String,
+}
+
+impl RpcSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = ureq::post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("{method} to {}", self.url))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            bail!("{method}: {error}");
+        }
+        Ok(response["result"].clone())
+    }
+
+    /// successful program transactions newer than `until` (all of them if
+    /// `None`), oldest first
+    pub fn signatures_since(&self, until: Option<&str>) -> Result<Vec<String>> {
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let mut config = json!({ "limit": PAGE, "commitment": "confirmed" });
+            if let Some(before) = &before {
+                config["before"] = json!(before);
+            }
+            if let Some(until) = until {
+                config["until"] = json!(until);
+            }
+            let page = self.call("getSignaturesForAddress", json!([PROGRAM_ID.to_string(), config]))?;
+            let page = page.as_array().ok_or_else(|| anyhow!("getSignaturesForAddress: expected an array"))?;
+            for entry in page {
+                let signature = entry["signature"].as_str().ok_or_else(|| anyhow!("entry without signature"))?;
+                before = Some(signature.to_string());
+                // failed transactions emit nothing that stuck
+                if entry["err"].is_null() {
+                    signatures.push(signature.to_string());
+                }
+            }
+            if page.len() < PAGE {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    pub fn transaction(&self, signature: &str) -> Result<TxLogs> {
+        let config = json!({
+            "encoding": "json",
+            "commitment": "confirmed",
+            "maxSupportedTransactionVersion": 0,
+        });
+        let tx = self.call("getTransaction", json!([signature, config]))?;
+        if tx.is_null() {
+            bail!("transaction {signature} not found");
+        }
+        let logs = tx["meta"]["logMessages"]
+            .as_array()
+            .ok_or_else(|| anyhow!("transaction {signature} has no logMessages"))?
+            .iter()
+            .filter_map(|line| line.as_str().map(str::to_string))
+            .collect();
+        Ok(TxLogs {
+            signature:  signature.to_string(),
+            slot:       tx["slot"].as_u64().unwrap_or_default(),
+            block_time: tx["blockTime"].as_i64(),
+            logs,
+        })
+    }
+}
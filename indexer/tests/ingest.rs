@@ -0,0 +1,205 @@
+//! Synthetic transaction logs folded into an in-memory database.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use quantum_markets::events::*;
+use quantum_markets_client::{MarketParameter, OracleKind, PoolSide, ProposalPayload, SwapDirection, PROGRAM_ID};
+use quantum_markets_indexer::{Indexer, TxLogs};
+
+const START: i64 = 1_700_000_000;
+
+fn tx(n: u64, events: &[&dyn Fn() -> Vec<u8>]) -> TxLogs {
+    let mut logs = vec![format!("Program {PROGRAM_ID} invoke [1]")];
+    logs.extend(events.iter().map(|data| format!("Program data: {}", STANDARD.encode(data()))));
+    logs.push(format!("Program {PROGRAM_ID} success"));
+    TxLogs { signature: format!("sig{n}"), slot: n, block_time: Some(START + n as i64), logs }
+}
+
+fn market_created(creator: Pubkey) -> MarketCreated {
+    MarketCreated {
+        market_id:        0,
+        market:           Pubkey::new_unique(),
+        creator,
+        reward_mint:      Pubkey::new_unique(),
+        resolver:         creator,
+        min_deposit:      600,
+        strike_price:     0,
+        accept_threshold: 500_000,
+        twap_window:      3_600,
+        deadline:         START + 86_400,
+        created_at:       START,
+        title:            "market".to_string(),
+        lp_fee_bps:       0,
+        creator_fee_bps:  0,
+        protocol_fee_bps: 0,
+        execution_delay:  0,
+        oracle_kind:      OracleKind::None,
+        oracle_feed:      Pubkey::default(),
+    }
+}
+
+fn proposal(id: u64, creator: Pubkey) -> ProposalCreated {
+    ProposalCreated {
+        proposal_id: id,
+        market_id:   0,
+        proposal:    Pubkey::new_unique(),
+        creator,
+        vusd_mint:   Pubkey::new_unique(),
+        yes_mint:    Pubkey::new_unique(),
+        no_mint:     Pubkey::new_unique(),
+        yes_pool:    Pubkey::new_unique(),
+        no_pool:     Pubkey::new_unique(),
+        locked:      600,
+        pool_tokens: 200,
+        pool_vusd:   100,
+        created_at:  START,
+        data:        vec![id as u8],
+    }
+}
+
+fn buy_yes(proposal_id: u64, user: Pubkey, reserve_token: u64, reserve_vusd: u64, timestamp: i64) -> Swapped {
+    Swapped {
+        proposal_id,
+        market_id: 0,
+        user,
+        side: PoolSide::Yes,
+        direction: SwapDirection::Buy,
+        amount_in: reserve_vusd - 100,
+        amount_out: 200 - reserve_token,
+        lp_fee: 0,
+        protocol_fee: 0,
+        creator_fee: 0,
+        reserve_token,
+        reserve_vusd,
+        price: reserve_vusd * 1_000_000 / reserve_token,
+        timestamp,
+    }
+}
+
+#[test]
+fn rebuilds_market_state() {
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let created = market_created(alice);
+
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let txs = [
+        tx(1, &[&|| created.data()]),
+        tx(2, &[
+            &|| Deposited { market_id: 0, user: alice, amount: 1_000, total: 1_000 }.data(),
+            &|| Deposited { market_id: 0, user: bob, amount: 1_000, total: 1_000 }.data(),
+        ]),
+        tx(3, &[&|| proposal(1, alice).data()]),
+        tx(4, &[&|| proposal(2, bob).data()]),
+        tx(5, &[&|| VusdClaimed { proposal_id: 1, market_id: 0, user: alice, amount: 400 }.data()]),
+        tx(6, &[&|| YesNoMinted { proposal_id: 1, market_id: 0, user: alice, amount: 10 }.data()]),
+        tx(7, &[&|| buy_yes(2, bob, 134, 150, START + 5).data()]),
+        tx(8, &[&|| buy_yes(1, alice, 160, 125, START + 9).data()]),
+        tx(9, &[&|| ProposalAccepted { market_id: 0, proposal_id: 2, twap: 900_000, timestamp: START + 9 }.data()]),
+    ];
+    for tx in &txs {
+        indexer.ingest(tx).unwrap();
+    }
+    // replays are ignored
+    assert_eq!(indexer.ingest(&txs[7]).unwrap(), 0);
+
+    let market = indexer.market(0).unwrap().unwrap();
+    assert_eq!(market.status, "accepted");
+    assert_eq!((market.accepted_proposal, market.accepted_twap), (Some(2), Some(900_000)));
+    assert_eq!(market.total_deposits, 800);
+
+    let deposits = indexer.deposits(0).unwrap();
+    assert!(deposits.iter().all(|d| d.amount == 400 && d.deposited == 1_000));
+
+    let ranked: Vec<_> = indexer.proposals_by_yes_price(0).unwrap().iter().map(|p| (p.id, p.yes_price)).collect();
+    assert_eq!(ranked, [(2, 1_119_402), (1, 781_250)]);
+    let first = indexer.proposal(1).unwrap().unwrap();
+    assert_eq!((first.claimed, first.minted, first.no_price), (400, 10, 500_000));
+
+    let history: Vec<_> = indexer.price_history(1, PoolSide::Yes).unwrap().iter().map(|p| p.price).collect();
+    assert_eq!(history, [500_000, 781_250]);
+    assert_eq!(indexer.price_history(1, PoolSide::No).unwrap().len(), 1);
+}
+
+#[test]
+fn indexes_migrated_legacy_proposals() {
+    let alice = Pubkey::new_unique();
+    let created = proposal(1, alice);
+    let migrated = ProposalAuthMigrated {
+        proposal_id:   1,
+        proposal_auth: Pubkey::new_unique(),
+        vusd_vault:    Pubkey::new_unique(),
+        yes_vault:     Pubkey::new_unique(),
+        no_vault:      Pubkey::new_unique(),
+    };
+
+    // as migrate_market, migrate_deposit_record and migrate_proposal_auth
+    // log them: the legacy record already excludes the stake
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    indexer.ingest(&tx(1, &[&|| market_created(alice).data()])).unwrap();
+    indexer.ingest(&tx(2, &[&|| Deposited { market_id: 0, user: alice, amount: 400, total: 400 }.data()])).unwrap();
+    indexer.ingest(&tx(3, &[&|| created.data(), &|| migrated.data()])).unwrap();
+
+    let row = indexer.proposal(1).unwrap().unwrap();
+    assert_eq!((row.market_id, row.creator, row.yes_price), (0, alice.to_string(), 500_000));
+    assert_eq!(indexer.deposits(0).unwrap()[0].amount, 400);
+}
+
+#[test]
+fn applies_executed_parameter_changes() {
+    let alice = Pubkey::new_unique();
+    let created = market_created(alice);
+    let change = ProposalCreated {
+        data: ProposalPayload::ParameterChange { parameter: MarketParameter::MinDeposit, value: 900 }.encode(),
+        ..proposal(1, alice)
+    };
+    let executed = ProposalExecuted {
+        market_id:    0,
+        proposal_id:  1,
+        treasury:     Pubkey::new_unique(),
+        instructions: 0,
+        executed_at:  START + 20,
+    };
+
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    indexer.ingest(&tx(1, &[&|| created.data()])).unwrap();
+    indexer.ingest(&tx(2, &[&|| change.data()])).unwrap();
+    assert_eq!(indexer.proposal(1).unwrap().unwrap().executed_at, None);
+    indexer.ingest(&tx(3, &[&|| executed.data()])).unwrap();
+
+    assert_eq!(indexer.market(0).unwrap().unwrap().min_deposit, 900);
+    assert_eq!(indexer.proposal(1).unwrap().unwrap().executed_at, Some(START + 20));
+}
+
+#[test]
+fn sums_treasury_fundings_per_mint() {
+    let (alice, bob, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let funded = |funder, amount, balance| TreasuryFunded { market_id: 0, funder, mint, amount, balance };
+
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    indexer.ingest(&tx(1, &[&|| market_created(alice).data()])).unwrap();
+    indexer.ingest(&tx(2, &[&|| funded(alice, 300, 300).data()])).unwrap();
+    indexer.ingest(&tx(3, &[&|| funded(bob, 200, 450).data()])).unwrap();
+
+    let treasury = indexer.treasury_funding(0).unwrap();
+    assert_eq!(treasury.len(), 1);
+    assert_eq!((treasury[0].mint.as_str(), treasury[0].funded, treasury[0].balance), (mint.to_string().as_str(), 500, 450));
+}
+
+#[test]
+fn ignores_data_logged_by_other_programs() {
+    let other = Pubkey::new_unique();
+    let deposited = Deposited { market_id: 0, user: Pubkey::new_unique(), amount: 5, total: 5 }.data();
+    let logs = vec![
+        format!("Program {PROGRAM_ID} invoke [1]"),
+        format!("Program {other} invoke [2]"),
+        format!("Program data: {}", STANDARD.encode(&deposited)),
+        format!("Program {other} success"),
+        format!("Program {PROGRAM_ID} success"),
+    ];
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let applied = indexer.ingest(&TxLogs { signature: "sig".into(), slot: 1, block_time: None, logs }).unwrap();
+    assert_eq!(applied, 0);
+    assert!(indexer.deposits(0).unwrap().is_empty());
+}
@@ -4,4 +4,30 @@ pub const U8_L: usize = 1;
 pub const DISCRIMINATOR: usize = 8;
 pub const STR_MAX_LEN: usize = 100;
 pub const STRING_PREFIX: usize = 4;
-pub const MAX_DATA: usize = 256;
\ No newline at end of file
+pub const MAX_DATA: usize = 256;
+pub const I64_L: usize = 8;
+pub const U16_L: usize = 2;
+pub const U32_L: usize = 4;
+pub const U128_L: usize = 16;
+pub const PRICE_SCALE: u64 = 1_000_000;   // prices are vUSD per token, 6 decimals
+pub const OBSERVATIONS: usize = 32;       // TWAP ring buffer length
+pub const PROPOSAL_AUTH_V1: u8 = 1;       // authority seeded by the proposal key
+pub const PAYLOAD_VERSION: u8 = 1;        // leading byte of an encoded ProposalPayload
+pub const MAX_FEE_BPS: u16 = 1_000;       // no single fee rate may exceed 10%
+pub const BPS: u64 = 10_000;              // basis points in one
+pub const MIN_DEPOSIT_FLOOR: u64 = 6;     // smallest min_deposit seeding each pool with D/6 vUSD
+pub const REGISTRY_GROWTH: u32 = 8;       // proposal slots added when a registry is full
+
+// PDA seeds, shared with off-chain clients
+pub const GLOBAL_SEED: &[u8] = b"global";
+pub const MARKET_SEED: &[u8] = b"market";
+pub const DEPOSIT_SEED: &[u8] = b"deposit";
+pub const CLAIM_SEED: &[u8] = b"claim";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const VUSD_SEED: &[u8] = b"vusd";
+pub const YES_MINT_SEED: &[u8] = b"yes_mint";
+pub const NO_MINT_SEED: &[u8] = b"no_mint";
+pub const PROPOSAL_AUTH_SEED: &[u8] = b"proposal_auth";
+pub const POOL_SEED: &[u8] = b"pool";
+pub const PROPOSALS_SEED: &[u8] = b"proposals";
+pub const TREASURY_SEED: &[u8] = b"treasury";
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use super::update_global::config_updated;
+
+/// Second half of the admin handover started by `transfer_admin`.
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = global.pending_admin == pending_admin.key() @ QuantumError::NotPendingAdmin,
+    )]
+    pub global: Account<'info, GlobalState>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        self.global.admin = self.pending_admin.key();
+        self.global.pending_admin = Pubkey::default();
+        emit!(config_updated(&self.global));
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount, MintTo, mint_to};
+use crate::state::global::GlobalState;
 use crate::state::proposal::ProposalConfig;
 use crate::state::deposit::{DepositRecord, ClaimRecord};
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::VusdClaimed;
 
 #[derive(Accounts)]
 pub struct ClaimForProposal<'info> {
@@ -9,20 +13,31 @@ pub struct ClaimForProposal<'info> {
     pub payer: Signer<'info>,
 
     #[account(
-        seeds = [b"proposal", &proposal.id.to_le_bytes()],
-        bump,
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
     )]
-    pub proposal: Account<'info, ProposalConfig>,
+    pub global: Account<'info, GlobalState>,
 
     #[account(
-        seeds = [b"market", &proposal.market_id.to_le_bytes()],
-        bump,
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
     )]
     pub market: Account<'info, crate::state::config::MarketConfig>,
 
     /// User’s cumulative deposit in that market
     #[account(
-        seeds = [b"deposit", market.key().as_ref(), payer.key().as_ref()],
+        mut,
+        seeds = [DEPOSIT_SEED, market.key().as_ref(), payer.key().as_ref()],
         bump,
     )]
     pub deposit_record: Account<'info, DepositRecord>,
@@ -31,14 +46,14 @@ pub struct ClaimForProposal<'info> {
     #[account(
         init_if_needed,
         payer = payer,
-        seeds = [b"claim", proposal.key().as_ref(), payer.key().as_ref()],
+        seeds = [CLAIM_SEED, proposal.key().as_ref(), payer.key().as_ref()],
         bump,
         space = ClaimRecord::SIZE
     )]
     pub claim_record: Account<'info, ClaimRecord>,
 
     /// vUSD mint of this proposal
-    #[account(mut, address = proposal.vusd_mint)]
+    #[account(mut, address = proposal.load()?.vusd_mint @ QuantumError::InvalidProposalMint)]
     pub vusd_mint: Account<'info, Mint>,
 
     /// User’s vUSD ATA
@@ -52,7 +67,7 @@ pub struct ClaimForProposal<'info> {
 
     /// Authority PDA allowed to mint vUSD
     /// CHECK: signer via seeds
-    #[account(seeds = [b"proposal_auth"], bump)]
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
     pub proposal_auth: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, anchor_spl::token::Token>,
@@ -62,13 +77,14 @@ pub struct ClaimForProposal<'info> {
 
 impl<'info> ClaimForProposal<'info> {
     pub fn handler(&mut self, bumps: ClaimForProposalBumps) -> Result<()> {
+        // stakes only come out of the unclaimed deposit, so the deposit
+        // never drops below a claim; if it did there would be nothing new
         let total = self.deposit_record.amount;
         let claimed = self.claim_record.claimed;
-        let claimable = total
-            .checked_sub(claimed)
-            .ok_or(crate::errors::QuantumError::Overflow)?;
-        require!(claimable > 0, crate::errors::QuantumError::NothingToClaim);
+        let claimable = total.saturating_sub(claimed);
+        require!(claimable > 0, QuantumError::NothingToClaim);
 
+        let proposal_key = self.proposal.key();
         mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
@@ -77,12 +93,28 @@ impl<'info> ClaimForProposal<'info> {
                     to:        self.user_vusd.to_account_info(),
                     authority: self.proposal_auth.to_account_info(),
                 },
-                &[&[b"proposal_auth", &[bumps.proposal_auth]]],
+                &[&[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]]],
             ),
             claimable,
         )?;
 
+        if self.claim_record.rent_payer == Pubkey::default() {
+            self.claim_record.rent_payer = self.payer.key();
+            let mut proposal = self.proposal.load_mut()?;
+            proposal.open_claims = proposal.open_claims
+                .checked_add(1)
+                .ok_or(QuantumError::Overflow)?;
+        }
         self.claim_record.claimed = total; // now fully claimed
+        self.deposit_record.max_claimed = self.deposit_record.max_claimed.max(total);
+
+        let proposal = self.proposal.load()?;
+        emit!(VusdClaimed {
+            proposal_id: proposal.id,
+            market_id:   proposal.market_id,
+            user:        self.payer.key(),
+            amount:      claimable,
+        });
         Ok(())
     }
 }
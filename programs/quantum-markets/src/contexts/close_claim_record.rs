@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::state::proposal::ProposalConfig;
+use crate::state::deposit::ClaimRecord;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::ClaimRecordClosed;
+
+/// Permissionless crank: closes a claim record of a final market and refunds
+/// its rent. A claim into the accepted proposal still decides what the user
+/// may withdraw, so it outlives the user's deposit record.
+#[derive(Accounts)]
+pub struct CloseClaimRecord<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    /// CHECK: only seeds the records
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [CLAIM_SEED, proposal.key().as_ref(), user.key().as_ref()],
+        bump,
+        has_one = rent_payer @ QuantumError::InvalidRentPayer,
+        close = rent_payer,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// User's deposit record in the market; must be closed for a claim into
+    /// the accepted proposal
+    /// CHECK: address via seeds, emptiness checked in the handler
+    #[account(seeds = [DEPOSIT_SEED, market.key().as_ref(), user.key().as_ref()], bump)]
+    pub deposit_record: UncheckedAccount<'info>,
+
+    /// CHECK: receives the rent; must be the record's rent payer
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseClaimRecord<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        require!(self.market.is_final(), QuantumError::MarketNotFinal);
+        let mut proposal = self.proposal.load_mut()?;
+        if self.market.is_resolved() && proposal.id == self.market.accepted_proposal {
+            require!(self.deposit_record.data_is_empty(), QuantumError::DependentAccountsOpen);
+        }
+
+        proposal.open_claims = proposal.open_claims
+            .checked_sub(1)
+            .ok_or(QuantumError::Underflow)?;
+
+        emit!(ClaimRecordClosed {
+            market_id:   self.market.id,
+            proposal_id: proposal.id,
+            user:        self.user.key(),
+            rent_payer:  self.rent_payer.key(),
+        });
+        Ok(())
+    }
+}
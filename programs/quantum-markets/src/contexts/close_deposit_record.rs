@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::state::proposal::ProposalConfig;
+use crate::state::deposit::DepositRecord;
+use crate::contexts::withdraw_deposit::withdrawable;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::DepositRecordClosed;
+
+/// Permissionless crank: once the market is final, a deposit record with
+/// nothing left to withdraw is closed and its rent refunded.
+#[derive(Accounts)]
+pub struct CloseDepositRecord<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    /// CHECK: only seeds the record
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump,
+        has_one = rent_payer @ QuantumError::InvalidRentPayer,
+        close = rent_payer,
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Accepted proposal, required unless the market timed out
+    pub accepted_proposal: Option<AccountLoader<'info, ProposalConfig>>,
+
+    /// User's claim PDA for the accepted proposal; may be uninitialized
+    /// CHECK: address and owner verified in the handler
+    pub accepted_claim: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: receives the rent; must be the record's rent payer
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseDepositRecord<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        require!(self.market.is_final(), QuantumError::MarketNotFinal);
+        let left = withdrawable(
+            &self.market,
+            &self.deposit_record,
+            self.accepted_proposal.as_ref(),
+            self.accepted_claim.as_ref(),
+            &self.user.key(),
+        )?;
+        require!(left == 0, QuantumError::BalanceNotZero);
+
+        self.market.open_deposits = self.market.open_deposits
+            .checked_sub(1)
+            .ok_or(QuantumError::Underflow)?;
+
+        emit!(DepositRecordClosed {
+            market_id:  self.market.id,
+            user:       self.user.key(),
+            rent_payer: self.rent_payer.key(),
+        });
+        Ok(())
+    }
+}
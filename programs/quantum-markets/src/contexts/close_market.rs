@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{CloseAccount, close_account, Transfer, transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::state::registry::MarketProposals;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::MarketClosed;
+
+/// Permissionless crank: the last step once every proposal and deposit
+/// record of a final market is closed. Deposits and the stakes of proposals
+/// that were not accepted have been paid back by then, so the reward tokens
+/// still in the vault are the accepted proposal's stake and claims beyond
+/// what its winning tokens redeemed, plus rounding; they go to the fee
+/// treasury. The vault, the
+/// proposal registry and the market are closed and their rent refunded to
+/// the market's rent payer.
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(mut)] pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+        has_one = rent_payer @ QuantumError::InvalidRentPayer,
+        close = rent_payer,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSALS_SEED, market.key().as_ref()],
+        bump = registry.load()?.bump,
+        close = rent_payer,
+    )]
+    pub registry: AccountLoader<'info, MarketProposals>,
+
+    #[account(address = market.market_token @ QuantumError::InvalidRewardMint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    /// created if nobody ever deposited, so there is one to close
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = market,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: address checked against the global fee treasury
+    #[account(address = global.fee_treasury @ QuantumError::InvalidFeeRecipient)]
+    pub fee_treasury: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = fee_treasury
+    )]
+    pub treasury_reward: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: receives the rent; must be the market's rent payer
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseMarket<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        require!(self.market.is_final(), QuantumError::MarketNotFinal);
+        require!(
+            self.market.closed_proposals == self.market.proposal_count
+                && self.market.open_deposits == 0,
+            QuantumError::DependentAccountsOpen
+        );
+
+        let id_bytes = self.market.id.to_le_bytes();
+        let market_seeds: &[&[u8]] = &[MARKET_SEED, &id_bytes, &[self.market.bump]];
+        let swept = self.market_vault.amount;
+        if swept > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from:      self.market_vault.to_account_info(),
+                        to:        self.treasury_reward.to_account_info(),
+                        authority: self.market.to_account_info(),
+                    },
+                    &[market_seeds],
+                ),
+                swept,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account:     self.market_vault.to_account_info(),
+                destination: self.rent_payer.to_account_info(),
+                authority:   self.market.to_account_info(),
+            },
+            &[market_seeds],
+        ))?;
+
+        emit!(MarketClosed {
+            market_id:  self.market.id,
+            rent_payer: self.rent_payer.key(),
+            swept,
+        });
+        Ok(())
+    }
+}
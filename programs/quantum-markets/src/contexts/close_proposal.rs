@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Burn, burn, CloseAccount, close_account, Transfer, transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::state::proposal::ProposalConfig;
+use crate::state::pool::Pool;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::ProposalClosed;
+
+/// Permissionless crank: once the market is final and every claim record of
+/// the proposal is closed (and the accepted one has been executed), burns
+/// what is left in its vaults and closes the vaults, pools and config,
+/// refunding the rent to its creator. The creator's stake goes back to them
+/// unless the proposal was accepted, whose stake backs its winning tokens.
+/// The mints stay, as the token program can't close them.
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+        has_one = rent_payer @ QuantumError::InvalidRentPayer,
+        close = rent_payer,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    /// CHECK: signer via seeds
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
+    pub proposal_auth: UncheckedAccount<'info>,
+
+    // mints
+    #[account(mut, address = proposal.load()?.vusd_mint @ QuantumError::InvalidProposalMint)]
+    pub vusd_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = proposal.load()?.yes_mint @ QuantumError::InvalidProposalMint)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = proposal.load()?.no_mint @ QuantumError::InvalidProposalMint)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    // vaults (owned by proposal_auth)
+    #[account(
+        mut,
+        address = get_associated_token_address(&proposal_auth.key(), &vusd_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub vusd_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = get_associated_token_address(&proposal_auth.key(), &yes_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = get_associated_token_address(&proposal_auth.key(), &no_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = proposal.load()?.yes_pool @ QuantumError::InvalidPool, close = rent_payer)]
+    pub yes_pool: Box<Account<'info, Pool>>,
+    #[account(mut, address = proposal.load()?.no_pool @ QuantumError::InvalidPool, close = rent_payer)]
+    pub no_pool: Box<Account<'info, Pool>>,
+
+    /// holds the proposal's stake
+    #[account(
+        mut,
+        address = get_associated_token_address(&market.key(), &market.market_token) @ QuantumError::InvalidVault,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    /// creator's reward token ATA, receiving the stake back
+    #[account(
+        mut,
+        address = get_associated_token_address(&proposal.load()?.creator, &market.market_token)
+            @ QuantumError::InvalidTokenAccount,
+    )]
+    pub creator_reward: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: receives the rent; must be the proposal's rent payer
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CloseProposal<'info> {
+    pub fn handler(&mut self, bumps: CloseProposalBumps) -> Result<()> {
+        require!(self.market.is_final(), QuantumError::MarketNotFinal);
+        let proposal = self.proposal.load()?;
+        require!(proposal.open_claims == 0, QuantumError::DependentAccountsOpen);
+        let accepted = self.market.is_resolved() && proposal.id == self.market.accepted_proposal;
+        if accepted {
+            // withdrawals read the accepted proposal, and its vUSD backs
+            // winning tokens and fees until they are paid out
+            require!(self.market.open_deposits == 0, QuantumError::DependentAccountsOpen);
+            let (winning_mint, winning_vault) = match self.market.status {
+                MarketStatus::ResolvedYes => (&self.yes_mint, &self.yes_vault),
+                _                         => (&self.no_mint, &self.no_vault),
+            };
+            require!(winning_mint.supply == winning_vault.amount, QuantumError::BalanceNotZero);
+            require!(proposal.uncollected_fees() == 0, QuantumError::BalanceNotZero);
+            // execute_proposal reads it too; a payload that can't be decoded
+            // never executes
+            let has_action = proposal.payload().is_ok_and(|payload| payload.has_action());
+            require!(proposal.executed_at != 0 || !has_action, QuantumError::ProposalNotExecuted);
+        }
+
+        // a proposal that was not accepted locked its stake for nothing
+        let refunded = if accepted { 0 } else { proposal.stake };
+        if refunded > 0 {
+            let id_bytes = self.market.id.to_le_bytes();
+            let market_seeds: &[&[u8]] = &[MARKET_SEED, &id_bytes, &[self.market.bump]];
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from:      self.market_vault.to_account_info(),
+                        to:        self.creator_reward.to_account_info(),
+                        authority: self.market.to_account_info(),
+                    },
+                    &[market_seeds],
+                ),
+                refunded,
+            )?;
+        }
+
+        // whatever is left backs nothing any more
+        let proposal_key = self.proposal.key();
+        let auth_seeds: &[&[u8]] = &[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]];
+        for (mint, vault) in [
+            (&self.vusd_mint, &self.vusd_vault),
+            (&self.yes_mint, &self.yes_vault),
+            (&self.no_mint, &self.no_vault),
+        ] {
+            if vault.amount > 0 {
+                burn(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        Burn {
+                            mint:      mint.to_account_info(),
+                            from:      vault.to_account_info(),
+                            authority: self.proposal_auth.to_account_info(),
+                        },
+                        &[auth_seeds],
+                    ),
+                    vault.amount,
+                )?;
+            }
+            close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account:     vault.to_account_info(),
+                    destination: self.rent_payer.to_account_info(),
+                    authority:   self.proposal_auth.to_account_info(),
+                },
+                &[auth_seeds],
+            ))?;
+        }
+
+        self.market.closed_proposals = self.market.closed_proposals
+            .checked_add(1)
+            .ok_or(QuantumError::Overflow)?;
+
+        emit!(ProposalClosed {
+            market_id:   self.market.id,
+            proposal_id: proposal.id,
+            rent_payer:  self.rent_payer.key(),
+            refunded,
+        });
+        Ok(())
+    }
+}
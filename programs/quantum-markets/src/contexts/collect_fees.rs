@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Burn, burn, Transfer, transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::state::proposal::ProposalConfig;
+use crate::state::fees::FeeKind;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::FeesCollected;
+
+/// Pays the swap fees set aside in the accepted proposal's vUSD vault out of
+/// the market's deposits, 1:1 in the reward token. Anyone may crank it; the
+/// payout only goes to the market creator or the protocol fee treasury.
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(mut)] pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    /// the accepted proposal of this market
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.id == market.accepted_proposal @ QuantumError::ProposalNotAccepted,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    /// CHECK: signer via seeds
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
+    pub proposal_auth: UncheckedAccount<'info>,
+
+    #[account(mut, address = proposal.load()?.vusd_mint @ QuantumError::InvalidProposalMint)]
+    pub vusd_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        address = get_associated_token_address(&proposal_auth.key(), &vusd_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub vusd_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = market.market_token @ QuantumError::InvalidRewardMint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        address = get_associated_token_address(&market.key(), &reward_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the market creator or the fee treasury, checked by the handler
+    pub recipient: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_reward: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CollectFees<'info> {
+    pub fn collect(&mut self, bumps: CollectFeesBumps, kind: FeeKind) -> Result<()> {
+        require!(self.market.is_resolved(), QuantumError::MarketNotResolved);
+        let mut proposal = self.proposal.load_mut()?;
+        let (recipient, accrued) = match kind {
+            FeeKind::Creator  => (self.market.creator, &mut proposal.creator_fees),
+            FeeKind::Protocol => (self.global.fee_treasury, &mut proposal.protocol_fees),
+        };
+        require_keys_eq!(self.recipient.key(), recipient, QuantumError::InvalidFeeRecipient);
+        let amount = std::mem::take(accrued);
+        require!(amount > 0, QuantumError::NothingToClaim);
+        let proposal_id = proposal.id;
+        drop(proposal);
+
+        // retire the vUSD the fees were set aside in
+        let proposal_key = self.proposal.key();
+        let auth_seeds: &[&[u8]] = &[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]];
+        burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint:      self.vusd_mint.to_account_info(),
+                    from:      self.vusd_vault.to_account_info(),
+                    authority: self.proposal_auth.to_account_info(),
+                },
+                &[auth_seeds],
+            ),
+            amount,
+        )?;
+
+        // market PDA releases the reward token
+        let id_bytes = self.market.id.to_le_bytes();
+        let market_seeds: &[&[u8]] = &[MARKET_SEED, &id_bytes, &[self.market.bump]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from:      self.market_vault.to_account_info(),
+                    to:        self.recipient_reward.to_account_info(),
+                    authority: self.market.to_account_info(),
+                },
+                &[market_seeds],
+            ),
+            amount,
+        )?;
+
+        emit!(FeesCollected {
+            market_id:   self.market.id,
+            proposal_id,
+            kind,
+            recipient,
+            amount,
+        });
+        Ok(())
+    }
+}
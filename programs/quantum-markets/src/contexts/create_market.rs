@@ -2,7 +2,11 @@ use anchor_lang::{prelude::*};
 use crate::errors::QuantumError;
 use crate::state::config::{MarketStatus, MarketConfig};
 use crate::state::global::GlobalState;
+use crate::state::registry::MarketProposals;
+use crate::state::oracle::OracleConfig;
 use anchor_spl::token::Mint;
+use crate::constants::*;
+use crate::events::MarketCreated;
 
 #[derive(Accounts)]
 pub struct CreateMarket<'info> {
@@ -15,44 +19,104 @@ pub struct CreateMarket<'info> {
     pub resolver: UncheckedAccount<'info>,
     #[account(
         init,
-        seeds = [b"market".as_ref(), &global.next_id.to_le_bytes()],
+        seeds = [MARKET_SEED, &global.next_market_id.to_le_bytes()],
         bump,
         payer = payer,
         space = 8 + MarketConfig::SIZE,
     )]
     pub market: Account<'info, MarketConfig>,
+    #[account(
+        init,
+        seeds = [PROPOSALS_SEED, market.key().as_ref()],
+        bump,
+        payer = payer,
+        space = MarketProposals::space(REGISTRY_GROWTH),
+    )]
+    pub registry: AccountLoader<'info, MarketProposals>,
     #[account(
       mut,
-      seeds = [b"global"],
-      bump,
+      seeds = [GLOBAL_SEED],
+      bump = global.bump,
+      constraint = !global.paused @ QuantumError::ProtocolPaused,
     )]
     pub global: Account<'info, GlobalState>,
     pub system_program: Program<'info, System>
 }
 
 impl<'info> CreateMarket<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn handler(
         &mut self,
         bumps: CreateMarketBumps,
         min_deposit: u64,
         strike_price: u64,
+        deadline: i64,
+        accept_threshold: u64,
+        twap_window: i64,
         title: String,
+        lp_fee_bps: u16,
+        creator_fee_bps: u16,
+        execution_delay: i64,
+        oracle: OracleConfig,
     ) -> Result<()> {
-        let market_id = self.global.next_id;
-        self.global.next_id = market_id
+        let market_id = self.global.next_market_id;
+        self.global.next_market_id = market_id
             .checked_add(1)
             .ok_or(QuantumError::Overflow)?;
         let bump = bumps.market;
         let now = Clock::get()?.unix_timestamp;
+        require!(deadline > now, QuantumError::InvalidDeadline);
+        require!(twap_window > 0, QuantumError::InvalidWindow);
+        require!(min_deposit >= MIN_DEPOSIT_FLOOR, QuantumError::MinDepositTooSmall);
+        require!(lp_fee_bps <= self.global.max_lp_fee_bps, QuantumError::FeeTooHigh);
+        require!(creator_fee_bps <= self.global.max_creator_fee_bps, QuantumError::FeeTooHigh);
+        require!(execution_delay >= 0, QuantumError::InvalidExecutionDelay);
+        oracle.validate()?;
+        self.market.id = market_id;
         self.market.created_at = now;
+        self.market.deadline = deadline;
         self.market.min_deposit = min_deposit;
         self.market.strike_price = strike_price;
+        self.market.accept_threshold = accept_threshold;
+        self.market.twap_window = twap_window;
         self.market.creator = self.payer.key();
         self.market.market_token = self.reward_mint.key();
         self.market.resolver = self.resolver.key();
         self.market.status = MarketStatus::Open;
         self.market.title = title;
         self.market.bump = bump;
+        self.market.lp_fee_bps = lp_fee_bps;
+        self.market.creator_fee_bps = creator_fee_bps;
+        self.market.protocol_fee_bps = self.global.protocol_fee_bps;
+        self.market.rent_payer = self.payer.key();
+        self.market.execution_delay = execution_delay;
+        self.market.oracle = oracle;
+
+        let mut registry = self.registry.load_init()?;
+        registry.market_id = market_id;
+        registry.capacity = REGISTRY_GROWTH;
+        registry.bump = bumps.registry;
+
+        emit!(MarketCreated {
+            market_id,
+            market: self.market.key(),
+            creator: self.market.creator,
+            reward_mint: self.market.market_token,
+            resolver: self.market.resolver,
+            min_deposit,
+            strike_price,
+            accept_threshold,
+            twap_window,
+            deadline,
+            created_at: now,
+            title: self.market.title.clone(),
+            lp_fee_bps,
+            creator_fee_bps,
+            protocol_fee_bps: self.market.protocol_fee_bps,
+            execution_delay,
+            oracle_kind: oracle.kind,
+            oracle_feed: oracle.feed,
+        });
         Ok(())
     }
 }
\ No newline at end of file
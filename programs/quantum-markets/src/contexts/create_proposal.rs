@@ -2,9 +2,14 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount, Token, MintTo, mint_to};
 use crate::state::config::MarketConfig;
 use crate::state::global::GlobalState;
-use crate::state::proposal::ProposalConfig;
+use crate::state::proposal::{ProposalConfig, PoolSide};
 use crate::state::deposit::DepositRecord;
+use crate::state::pool::Pool;
+use crate::state::registry::{MarketProposals, ProposalEntry};
+use crate::state::payload::ProposalPayload;
 use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::ProposalCreated;
 
 #[derive(Accounts)]
 pub struct CreateProposal<'info> {
@@ -15,27 +20,37 @@ pub struct CreateProposal<'info> {
     /// market we’re proposing inside
     #[account(
         mut,
-        seeds = [b"market", &market.id.to_le_bytes()],
-        bump
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
     )]
     pub market: Account<'info, MarketConfig>,
 
+    /// the market's proposal list, grown by the payer when full
+    #[account(
+        mut,
+        seeds = [PROPOSALS_SEED, market.key().as_ref()],
+        bump = registry.load()?.bump,
+    )]
+    pub registry: AccountLoader<'info, MarketProposals>,
+
     /// User’s deposit tracker (ATA of reward token)
     #[account(
         mut,
-        seeds = [b"deposit", market.key().as_ref(), payer.key().as_ref()],
+        seeds = [DEPOSIT_SEED, market.key().as_ref(), payer.key().as_ref()],
         bump,
     )]
     pub user_deposit: Account<'info, DepositRecord>,
 
     /// reward token mint (same as market.market_token)
+    #[account(address = market.market_token @ QuantumError::InvalidRewardMint)]
     pub reward_mint: Account<'info, Mint>,
 
     // ============== global counter ==============
     #[account(
       mut,
-      seeds = [b"global"],
-      bump,
+      seeds = [GLOBAL_SEED],
+      bump = global.bump,
+      constraint = !global.paused @ QuantumError::ProtocolPaused,
     )]
     pub global: Account<'info, GlobalState>,
 
@@ -43,7 +58,7 @@ pub struct CreateProposal<'info> {
     #[account(
         init,
         payer = payer,
-        seeds = [b"vusd".as_ref(), &global.next_id.to_le_bytes()],
+        seeds = [VUSD_SEED, &global.next_proposal_id.to_le_bytes()],
         bump,
         mint::decimals = 6,
         mint::authority = proposal_auth
@@ -53,7 +68,7 @@ pub struct CreateProposal<'info> {
     #[account(
         init,
         payer = payer,
-        seeds = [b"yes_mint".as_ref(), &global.next_id.to_le_bytes()],
+        seeds = [YES_MINT_SEED, &global.next_proposal_id.to_le_bytes()],
         bump,
         mint::decimals = 0,
         mint::authority = proposal_auth
@@ -63,7 +78,7 @@ pub struct CreateProposal<'info> {
     #[account(
         init,
         payer = payer,
-        seeds = [b"no_mint".as_ref(), &global.next_id.to_le_bytes()],
+        seeds = [NO_MINT_SEED, &global.next_proposal_id.to_le_bytes()],
         bump,
         mint::decimals = 0,
         mint::authority = proposal_auth
@@ -95,6 +110,25 @@ pub struct CreateProposal<'info> {
     )]
     pub no_vault: Box<Account<'info, TokenAccount>>,
 
+    // ============== YES/vUSD and NO/vUSD pools ==============
+    #[account(
+        init,
+        payer = payer,
+        seeds = [POOL_SEED, yes_vault.key().as_ref()],
+        bump,
+        space = 8 + Pool::SIZE
+    )]
+    pub yes_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [POOL_SEED, no_vault.key().as_ref()],
+        bump,
+        space = 8 + Pool::SIZE
+    )]
+    pub no_pool: Box<Account<'info, Pool>>,
+
     // ============== user ATAs to receive inventory ==============
     #[account(
         init_if_needed,
@@ -112,10 +146,10 @@ pub struct CreateProposal<'info> {
     )]
     pub user_no: Box<Account<'info, TokenAccount>>,
 
-    /// PDA that will sign `mint_to` CPIs
+    /// PDA that will sign `mint_to` CPIs, one per proposal
     /// CHECK: only used as mint_authority
     #[account(
-        seeds = [b"proposal_auth"],
+        seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()],
         bump
     )]
     pub proposal_auth: UncheckedAccount<'info>,
@@ -124,11 +158,11 @@ pub struct CreateProposal<'info> {
     #[account(
         init,
         payer = payer,
-        seeds = [b"proposal", &global.next_id.to_le_bytes()],
+        seeds = [PROPOSAL_SEED, &global.next_proposal_id.to_le_bytes()],
         bump,
         space = 8 + ProposalConfig::SIZE
     )]
-    pub proposal: Account<'info, ProposalConfig>,
+    pub proposal: AccountLoader<'info, ProposalConfig>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
@@ -139,12 +173,20 @@ impl<'info> CreateProposal<'info> {
     pub fn handler(
         &mut self,
         bumps: CreateProposalBumps,     // { proposal_auth, proposal, vusd_mint, yes_mint, … }
-        data: Vec<u8>,                 // opaque proposal blob
+        data: Vec<u8>,                 // encoded ProposalPayload
     ) -> Result<()> {
-        // 1) ensure caller has enough un-claimed deposit
+        // 1) market must still take proposals, and caller must have enough un-claimed deposit
+        let now = Clock::get()?.unix_timestamp;
+        require!(self.market.accepts_proposals(now), QuantumError::MarketClosed);
+        require!(data.len() <= MAX_DATA, QuantumError::ProposalDataTooLong);
+        let (treasury, _) =
+            Pubkey::find_program_address(&[TREASURY_SEED, self.market.key().as_ref()], &crate::ID);
+        ProposalPayload::decode(&data)?.validate(&self.global, &treasury)?;
         let min_d = self.market.min_deposit;
         let claimable = self.user_deposit.amount;     // simplistic: all tokens in this ATA
-        require!(claimable >= min_d, QuantumError::MinDeposit);
+        // the stake may only come out of deposit no proposal minted vUSD
+        // against, or a refund of it would take from that vUSD's backing
+        require!(min_d <= self.user_deposit.unclaimed(), QuantumError::MinDeposit);
 
         // burn (lock) the minDeposit from user_deposit
         self.user_deposit.amount = claimable
@@ -152,12 +194,16 @@ impl<'info> CreateProposal<'info> {
             .ok_or(QuantumError::Underflow)?;
 
         // 2) split D (min_d) exactly like Solidity logic
-        let burn_total = min_d * 2 / 3;          // D * 2/3  as YES+NO
+        let burn_total = min_d.checked_mul(2).ok_or(QuantumError::Overflow)? / 3;   // D * 2/3  as YES+NO
         let token_per_pool = burn_total / 2;     // D/3
         let vusd_to_mint = min_d - burn_total;   // D/3
-        let vusd_per_pool = vusd_to_mint / 2;    // D/6   (we just mint D/3 total)
+        let vusd_per_pool = vusd_to_mint / 2;    // D/6   per pool
+        // every YES/NO pair is backed by one vUSD, as one from mint_yes_no
+        // is: D/3 pairs in the pools and D/3 to the caller
+        let collateral = token_per_pool * 2;     // D*2/3
 
-        // 3) mint vUSD into vault
+        // 3) mint the pools' vUSD and the pairs' collateral into the vault
+        let proposal_key = self.proposal.key();
         mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
@@ -166,9 +212,9 @@ impl<'info> CreateProposal<'info> {
                     to: self.vusd_vault.to_account_info(),
                     authority: self.proposal_auth.to_account_info(),
                 },
-                &[&[b"proposal_auth", &[bumps.proposal_auth]]],
+                &[&[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]]],
             ),
-            vusd_to_mint,
+            vusd_to_mint + collateral,
         )?;
 
 
@@ -182,7 +228,7 @@ impl<'info> CreateProposal<'info> {
                         to: to.to_account_info(),
                         authority: self.proposal_auth.to_account_info(),
                     },
-                    &[&[b"proposal_auth", &[bumps.proposal_auth]]],
+                    &[&[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]]],
                 ),
                 amount,
             )
@@ -196,7 +242,7 @@ impl<'info> CreateProposal<'info> {
                         to: to.to_account_info(),
                         authority: self.proposal_auth.to_account_info(),
                     },
-                    &[&[b"proposal_auth", &[bumps.proposal_auth]]],
+                    &[&[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]]],
                 ),
                 amount,
             )
@@ -207,25 +253,84 @@ impl<'info> CreateProposal<'info> {
         mint_yes(&self.user_yes, token_per_pool)?;
         mint_no(&self.user_no, token_per_pool)?;
 
-        // 5) record ProposalConfig
-        let id = self.global.next_id;
-        let now = Clock::get()?.unix_timestamp;
-        self.proposal.set_inner(ProposalConfig {
-            id,
-            market_id: self.market.id,
-            created_at: now,
-            creator: self.payer.key(),
-            vusd_mint: self.vusd_mint.key(),
-            yes_mint:  self.yes_mint.key(),
-            no_mint:   self.no_mint.key(),
-            yes_pool:  Pubkey::default(), // will be filled after pool bootstrap
-            no_pool:   Pubkey::default(),
+        // 5) seed YES/vUSD and NO/vUSD pools from the vaults
+        let id = self.global.next_proposal_id;
+        for (pool, side, mint, vault, bump) in [
+            (&mut self.yes_pool, PoolSide::Yes, &self.yes_mint, &self.yes_vault, bumps.yes_pool),
+            (&mut self.no_pool,  PoolSide::No,  &self.no_mint,  &self.no_vault,  bumps.no_pool),
+        ] {
+            pool.init(
+                id,
+                self.market.id,
+                side,
+                mint.key(),
+                vault.key(),
+                self.vusd_vault.key(),
+                token_per_pool,
+                vusd_per_pool,
+                now,
+                bump,
+            );
+        }
+
+        // 6) list it in the market's registry, priced at the seeded reserves
+        let registry_index = MarketProposals::push(
+            &self.registry,
+            ProposalEntry {
+                id,
+                yes_price:  self.yes_pool.spot_price()?,
+                no_price:   self.no_pool.spot_price()?,
+                updated_at: now,
+                vusd_mint:  self.vusd_mint.key(),
+                yes_mint:   self.yes_mint.key(),
+                no_mint:    self.no_mint.key(),
+            },
+            &self.payer,
+            &self.system_program,
+        )?;
+
+        // 7) record ProposalConfig
+        let mut proposal = self.proposal.load_init()?;
+        proposal.id = id;
+        proposal.market_id = self.market.id;
+        proposal.created_at = now;
+        proposal.creator = self.payer.key();
+        proposal.stake = min_d;
+        proposal.vusd_mint = self.vusd_mint.key();
+        proposal.yes_mint = self.yes_mint.key();
+        proposal.no_mint = self.no_mint.key();
+        proposal.yes_pool = self.yes_pool.key();
+        proposal.no_pool = self.no_pool.key();
+        proposal.data[..data.len()].copy_from_slice(&data);
+        proposal.data_len = data.len() as u16;
+        proposal.bump = bumps.proposal;
+        proposal.auth_version = PROPOSAL_AUTH_V1;
+        proposal.rent_payer = self.payer.key();
+        proposal.registry_index = registry_index;
+
+        emit!(ProposalCreated {
+            proposal_id: id,
+            market_id:   self.market.id,
+            proposal:    self.proposal.key(),
+            creator:     self.payer.key(),
+            vusd_mint:   self.vusd_mint.key(),
+            yes_mint:    self.yes_mint.key(),
+            no_mint:     self.no_mint.key(),
+            yes_pool:    self.yes_pool.key(),
+            no_pool:     self.no_pool.key(),
+            locked:      min_d,
+            pool_tokens: token_per_pool,
+            pool_vusd:   vusd_per_pool,
+            created_at:  now,
             data,
-            bump: bumps.proposal,
         });
 
-        // 6) bump the global counter
-        self.global.next_id = id
+        self.market.proposal_count = self.market.proposal_count
+            .checked_add(1)
+            .ok_or(QuantumError::Overflow)?;
+
+        // 8) bump the global counter
+        self.global.next_proposal_id = id
             .checked_add(1)
             .ok_or(QuantumError::Overflow)?;
 
@@ -1,19 +1,34 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, Mint, transfer};
 use anchor_spl::associated_token::AssociatedToken;
+use crate::state::global::GlobalState;
 use crate::state::config::MarketConfig;
 use crate::state::deposit::DepositRecord;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::Deposited;
 
 #[derive(Accounts)]
 pub struct DepositToMarket<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    #[account(address = market.market_token)]
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Account<'info, GlobalState>,
+
+    #[account(address = market.market_token @ QuantumError::InvalidRewardMint)]
     pub reward_mint: Account<'info, Mint>,
 
     /// User’s ATA holding the market token
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = user_token.mint == reward_mint.key() @ QuantumError::InvalidTokenAccount,
+        constraint = user_token.owner == payer.key() @ QuantumError::InvalidTokenAccount,
+    )]
     pub user_token: Account<'info, TokenAccount>,
 
     /// Program vault that holds all deposits for this market
@@ -27,8 +42,8 @@ pub struct DepositToMarket<'info> {
 
     #[account(
         mut,
-        seeds = [b"market", &market.id.to_le_bytes()],
-        bump,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
     )]
     pub market: Account<'info, MarketConfig>,
 
@@ -36,7 +51,7 @@ pub struct DepositToMarket<'info> {
     #[account(
         init_if_needed,
         payer = payer,
-        seeds = [b"deposit", market.key().as_ref(), payer.key().as_ref()],
+        seeds = [DEPOSIT_SEED, market.key().as_ref(), payer.key().as_ref()],
         bump,
         space = DepositRecord::SIZE
     )]
@@ -49,10 +64,11 @@ pub struct DepositToMarket<'info> {
 
 impl<'info> DepositToMarket<'info> {
     pub fn handler(&mut self, amount: u64) -> Result<()> {
-        // reject if market closed
+        // reject if market closed or past its deadline
+        let now = Clock::get()?.unix_timestamp;
         require!(
-            matches!(self.market.status, crate::state::config::MarketStatus::Open | crate::state::config::MarketStatus::ProposalAccepted),
-            crate::errors::QuantumError::MarketClosed
+            self.market.is_active(now),
+            QuantumError::MarketClosed
         );
 
         // token transfer
@@ -66,13 +82,27 @@ impl<'info> DepositToMarket<'info> {
         );
         transfer(cpi, amount)?;
 
+        // a new record is refunded to whoever paid for it
+        if self.deposit_record.rent_payer == Pubkey::default() {
+            self.deposit_record.rent_payer = self.payer.key();
+            self.market.open_deposits = self.market.open_deposits
+                .checked_add(1)
+                .ok_or(QuantumError::Overflow)?;
+        }
+
         // bump deposit total
         self.deposit_record.amount = self
             .deposit_record
             .amount
             .checked_add(amount)
-            .ok_or(crate::errors::QuantumError::Overflow)?;
+            .ok_or(QuantumError::Overflow)?;
 
+        emit!(Deposited {
+            market_id: self.market.id,
+            user:      self.payer.key(),
+            amount,
+            total:     self.deposit_record.amount,
+        });
         Ok(())
     }
 }
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::state::proposal::ProposalConfig;
+use crate::state::payload::ProposalPayload;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::ProposalExecuted;
+
+/// Permissionless crank: carries out the accepted proposal's payload once
+/// the market's execution delay has passed since acceptance. Transfers and
+/// instruction lists run as CPIs signed by the market treasury; parameter
+/// changes are applied to the market. Each proposal executes once.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    /// the accepted proposal of this market
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.id == market.accepted_proposal @ QuantumError::ProposalNotAccepted,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    /// CHECK: signer via seeds; holds the assets proposals can move
+    #[account(seeds = [TREASURY_SEED, market.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    pub fn handler(&mut self, bumps: ExecuteProposalBumps, remaining: &[AccountInfo]) -> Result<()> {
+        require!(self.market.was_accepted(), QuantumError::MarketNotAccepted);
+        let now = Clock::get()?.unix_timestamp;
+        let unlocks_at = self.market.accepted_at
+            .checked_add(self.market.execution_delay)
+            .ok_or(QuantumError::Overflow)?;
+        require!(now >= unlocks_at, QuantumError::TimelockActive);
+
+        // mark it first; the proposal may be among the CPI accounts, which
+        // must not be borrowed while they run
+        let (proposal_id, payload) = {
+            let mut proposal = self.proposal.load_mut()?;
+            require!(proposal.executed_at == 0, QuantumError::ProposalAlreadyExecuted);
+            proposal.executed_at = now;
+            (proposal.id, proposal.payload()?)
+        };
+        // the global fee maximums may have dropped since creation
+        payload.validate(&self.global, &self.treasury.key())?;
+
+        if let ProposalPayload::ParameterChange { parameter, value } = payload {
+            self.market.set_parameter(parameter, value);
+        }
+
+        let instructions = payload.instructions(&self.treasury.key())?;
+        let market_key = self.market.key();
+        let treasury_seeds: &[&[u8]] = &[TREASURY_SEED, market_key.as_ref(), &[bumps.treasury]];
+        let mut accounts = remaining.iter();
+        for ix in &instructions {
+            // the program, then one account per meta with the same key and
+            // at least the writability it asks for
+            let program = accounts.next().ok_or(QuantumError::ExecutionAccountsMismatch)?;
+            require_keys_eq!(program.key(), ix.program_id, QuantumError::ExecutionAccountsMismatch);
+            let mut infos = Vec::with_capacity(ix.accounts.len() + 1);
+            for meta in &ix.accounts {
+                let info = accounts.next().ok_or(QuantumError::ExecutionAccountsMismatch)?;
+                require!(
+                    info.key() == meta.pubkey && (info.is_writable || !meta.is_writable),
+                    QuantumError::ExecutionAccountsMismatch
+                );
+                infos.push(info.clone());
+            }
+            infos.push(program.clone());
+            invoke_signed(ix, &infos, &[treasury_seeds])?;
+        }
+        require!(accounts.next().is_none(), QuantumError::ExecutionAccountsMismatch);
+
+        emit!(ProposalExecuted {
+            market_id:    self.market.id,
+            proposal_id,
+            treasury:     self.treasury.key(),
+            instructions: instructions.len() as u32,
+            executed_at:  now,
+        });
+        Ok(())
+    }
+}
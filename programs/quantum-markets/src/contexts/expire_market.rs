@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::MarketExpired;
+
+/// Permissionless crank: anyone may time out a market once its deadline passes.
+#[derive(Accounts)]
+pub struct ExpireMarket<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, MarketConfig>,
+}
+
+impl<'info> ExpireMarket<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        // only Open markets time out; an accepted proposal keeps the market alive
+        require!(self.market.status == MarketStatus::Open, QuantumError::MarketNotOpen);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= self.market.deadline, QuantumError::DeadlineNotReached);
+
+        self.market.status = MarketStatus::Timeout;
+        emit!(MarketExpired { market_id: self.market.id, timestamp: now });
+        Ok(())
+    }
+}
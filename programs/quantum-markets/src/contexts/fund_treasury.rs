@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, Mint, transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::TreasuryFunded;
+
+/// Anyone can pay any SPL mint into the market treasury, held in the
+/// associated token accounts of the treasury PDA. Nothing but
+/// `execute_proposal` signs for the treasury, so the tokens only leave
+/// through an accepted proposal; funding stops once the market times out
+/// or resolves.
+#[derive(Accounts)]
+pub struct FundTreasury<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, MarketConfig>,
+
+    /// CHECK: signer via seeds in execute_proposal; only owns token accounts
+    #[account(seeds = [TREASURY_SEED, market.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = payer_token.mint == mint.key() @ QuantumError::InvalidTokenAccount,
+        constraint = payer_token.owner == payer.key() @ QuantumError::InvalidTokenAccount,
+    )]
+    pub payer_token: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint      = mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_token: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundTreasury<'info> {
+    pub fn handler(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, QuantumError::ZeroAmount);
+        // open before its deadline, or accepted and not yet resolved
+        let now = Clock::get()?.unix_timestamp;
+        require!(self.market.is_active(now), QuantumError::MarketClosed);
+
+        transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from:      self.payer_token.to_account_info(),
+                    to:        self.treasury_token.to_account_info(),
+                    authority: self.payer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        self.treasury_token.reload()?;
+
+        emit!(TreasuryFunded {
+            market_id: self.market.id,
+            funder:    self.payer.key(),
+            mint:      self.mint.key(),
+            amount,
+            balance:   self.treasury_token.amount,
+        });
+        Ok(())
+    }
+}
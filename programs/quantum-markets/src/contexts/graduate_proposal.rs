@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::state::proposal::{ProposalConfig, PoolSide};
+use crate::state::pool::Pool;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::ProposalAccepted;
+
+/// Accept the proposal whose YES TWAP is above the market threshold and
+/// strictly higher than every other proposal's, mirroring the Solidity
+/// reference. Permissionless: the prices decide, not the caller.
+#[derive(Accounts)]
+pub struct GraduateProposal<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, MarketConfig>,
+
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    #[account(address = proposal.load()?.yes_pool @ QuantumError::InvalidPool)]
+    pub yes_pool: Box<Account<'info, Pool>>,
+}
+
+impl<'info> GraduateProposal<'info> {
+    pub fn handler(&mut self, other_pools: &[AccountInfo]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = self.proposal.load()?;
+        require!(self.market.status == MarketStatus::Open, QuantumError::MarketNotOpen);
+        require!(now < self.market.deadline, QuantumError::MarketClosed);
+
+        // a full window of history, so a fresh proposal can't win on a spike
+        let window = self.market.twap_window;
+        require!(
+            now.saturating_sub(proposal.created_at) >= window,
+            QuantumError::ProposalTooYoung
+        );
+        let twap = self.yes_pool.twap(now, window)?;
+        require!(twap > self.market.accept_threshold, QuantumError::BelowAcceptThreshold);
+
+        // every other proposal of the market, each exactly once
+        require!(
+            other_pools.len() as u64 + 1 == self.market.proposal_count,
+            QuantumError::IncompleteProposalSet
+        );
+        let mut seen: Vec<u64> = Vec::with_capacity(other_pools.len());
+        for info in other_pools {
+            require_keys_eq!(*info.owner, crate::ID, QuantumError::InvalidPool);
+            let pool = Pool::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(
+                pool.market_id == self.market.id && pool.side == PoolSide::Yes,
+                QuantumError::InvalidPool
+            );
+            require!(
+                pool.proposal_id != proposal.id && !seen.contains(&pool.proposal_id),
+                QuantumError::IncompleteProposalSet
+            );
+            seen.push(pool.proposal_id);
+
+            require!(pool.twap(now, window)? < twap, QuantumError::NotHighestProposal);
+        }
+
+        self.market.accepted_proposal = proposal.id;
+        self.market.accepted_at = now;
+        self.market.status = MarketStatus::ProposalAccepted;
+
+        emit!(ProposalAccepted {
+            market_id:   self.market.id,
+            proposal_id: proposal.id,
+            twap,
+            timestamp:   now,
+        });
+        Ok(())
+    }
+}
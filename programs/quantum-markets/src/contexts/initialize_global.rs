@@ -1,24 +1,55 @@
 use crate::state::global::GlobalState;
 use anchor_lang::{prelude::*};
+#[allow(deprecated)] // anchor-lang only re-exports the loader id through this path
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use crate::constants::*;
+use crate::errors::QuantumError;
+use crate::events::GlobalInitialized;
 
 #[derive(Accounts)]
 pub struct InitializeGlobal<'info> {
   #[account(
     init,
-    seeds = [b"global"],
+    seeds = [GLOBAL_SEED],
     bump,
     payer = payer,
-    space = 8 + 8,      // discriminator + u64
+    space = 8 + GlobalState::SIZE,
   )]
   pub global: Account<'info, GlobalState>,
 
+  /// becomes the protocol admin
   #[account(mut)] pub payer: Signer<'info>,
+
+  /// this program's program data; only its upgrade authority may initialize
+  #[account(
+    seeds = [crate::ID.as_ref()],
+    bump,
+    seeds::program = bpf_loader_upgradeable::ID,
+    constraint = program_data.upgrade_authority_address == Some(payer.key()) @ QuantumError::NotUpgradeAuthority,
+  )]
+  pub program_data: Account<'info, ProgramData>,
+
   pub system_program: Program<'info, System>,
 }
 
 impl<'info> InitializeGlobal<'info> {
-  pub fn handler(&mut self) -> Result<()> {
-    self.global.next_id = 0;
+  pub fn handler(&mut self, bumps: InitializeGlobalBumps) -> Result<()> {
+    self.global.set_inner(GlobalState {
+      next_market_id:      0,
+      next_proposal_id:    0,
+      admin:               self.payer.key(),
+      pending_admin:       Pubkey::default(),
+      fee_treasury:        self.payer.key(),
+      protocol_fee_bps:    0,
+      max_lp_fee_bps:      0,
+      max_creator_fee_bps: 0,
+      paused:              false,
+      bump:                bumps.global,
+    });
+    emit!(GlobalInitialized {
+      global: self.global.key(),
+      payer:  self.payer.key(),
+    });
     Ok(())
   }
 }
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::global::GlobalState;
+use crate::state::proposal::ProposalConfig;
+use crate::state::deposit::ClaimRecord;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::VusdClaimed;
+
+/// Permissionless crank: rewrites a claim record written in the original
+/// layout, which held only the claimed amount. Its proposal must be
+/// migrated first, which counted it as an open claim.
+#[derive(Accounts)]
+pub struct MigrateClaimRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    /// CHECK: only seeds the record
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: legacy layout that no longer deserializes as ClaimRecord;
+    /// length and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [CLAIM_SEED, proposal.key().as_ref(), user.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub claim_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateClaimRecord<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let record = self.claim_record.to_account_info();
+        require!(record.data_len() == ClaimRecord::LEGACY_LEN, QuantumError::RecordAlreadyMigrated);
+        let claimed = {
+            let data = record.try_borrow_data()?;
+            require!(
+                data[..DISCRIMINATOR] == *ClaimRecord::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            u64::deserialize(&mut &data[DISCRIMINATOR..])?
+        };
+
+        // 1) top up rent for the larger account, then grow it
+        let shortfall = Rent::get()?.minimum_balance(ClaimRecord::SIZE).saturating_sub(record.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer { from: self.payer.to_account_info(), to: record.clone() },
+                ),
+                shortfall,
+            )?;
+        }
+        record.resize(ClaimRecord::SIZE)?;
+
+        // 2) rewrite it; the user paid for the original record
+        let migrated = ClaimRecord {
+            claimed,
+            rent_payer: self.user.key(),
+        };
+        {
+            let mut data = record.try_borrow_mut_data()?;
+            data.fill(0);
+            migrated.try_serialize(&mut &mut data[..])?;
+        }
+
+        // legacy claims predate the event log; index them from here on
+        let proposal = self.proposal.load()?;
+        emit!(VusdClaimed {
+            proposal_id: proposal.id,
+            market_id:   proposal.market_id,
+            user:        self.user.key(),
+            amount:      claimed,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::state::deposit::DepositRecord;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::Deposited;
+
+/// Permissionless crank: rewrites a deposit record written in the original
+/// layout, which held only the amount. Claims against it were not tracked,
+/// so the whole deposit counts as claimed: it backs vUSD until the market
+/// settles and is withdrawn then. Its market must be migrated first.
+#[derive(Accounts)]
+pub struct MigrateDepositRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    /// CHECK: only seeds the record
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: legacy layout that no longer deserializes as DepositRecord;
+    /// length and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [DEPOSIT_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub deposit_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateDepositRecord<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let record = self.deposit_record.to_account_info();
+        require!(record.data_len() == DepositRecord::LEGACY_LEN, QuantumError::RecordAlreadyMigrated);
+        let amount = {
+            let data = record.try_borrow_data()?;
+            require!(
+                data[..DISCRIMINATOR] == *DepositRecord::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            u64::deserialize(&mut &data[DISCRIMINATOR..])?
+        };
+
+        // 1) top up rent for the larger account, then grow it
+        let shortfall = Rent::get()?.minimum_balance(DepositRecord::SIZE).saturating_sub(record.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer { from: self.payer.to_account_info(), to: record.clone() },
+                ),
+                shortfall,
+            )?;
+        }
+        record.resize(DepositRecord::SIZE)?;
+
+        // 2) rewrite it; the user paid for the original record
+        let migrated = DepositRecord {
+            amount,
+            max_claimed: amount,
+            rent_payer:  self.user.key(),
+        };
+        {
+            let mut data = record.try_borrow_mut_data()?;
+            data.fill(0);
+            migrated.try_serialize(&mut &mut data[..])?;
+        }
+        self.market.open_deposits = self.market.open_deposits
+            .checked_add(1)
+            .ok_or(QuantumError::Overflow)?;
+
+        // legacy deposits predate the event log; index them from here on
+        emit!(Deposited {
+            market_id: self.market.id,
+            user:      self.user.key(),
+            amount,
+            total:     amount,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+#[allow(deprecated)] // anchor-lang only re-exports the loader id through this path
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::global::GlobalState;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::GlobalMigrated;
+
+/// Brings an older global account to the current layout. A legacy shared
+/// `next_id` is split into `next_market_id` and `next_proposal_id`, both
+/// starting from the old counter so neither can land on a PDA it already
+/// used. The upgrade authority signs and becomes the protocol admin.
+#[derive(Accounts)]
+pub struct MigrateGlobal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @ QuantumError::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: legacy layout that no longer deserializes as GlobalState;
+    /// length and discriminator are checked in the handler
+    #[account(mut, seeds = [GLOBAL_SEED], bump, owner = crate::ID)]
+    pub global: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateGlobal<'info> {
+    pub fn handler(&mut self, bumps: MigrateGlobalBumps) -> Result<()> {
+        let global = self.global.to_account_info();
+        let len = global.data_len();
+        require!(
+            len == GlobalState::LEGACY_LEN || len == GlobalState::COUNTERS_LEN,
+            QuantumError::GlobalAlreadyMigrated
+        );
+
+        let (next_market_id, next_proposal_id) = {
+            let data = global.try_borrow_data()?;
+            require!(
+                data[..DISCRIMINATOR] == *GlobalState::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            let counter = |at: usize| u64::from_le_bytes(data[at..at + U64_L].try_into().unwrap());
+            let next_market_id = counter(DISCRIMINATOR);
+            if len == GlobalState::LEGACY_LEN {
+                (next_market_id, next_market_id)
+            } else {
+                (next_market_id, counter(DISCRIMINATOR + U64_L))
+            }
+        };
+
+        // 1) top up rent for the larger account, then grow it
+        let len = DISCRIMINATOR + GlobalState::SIZE;
+        let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(global.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer { from: self.payer.to_account_info(), to: global.clone() },
+                ),
+                shortfall,
+            )?;
+        }
+        global.resize(len)?;
+
+        // 2) rewrite the body: counters carried over, a fresh unpaused
+        //    configuration owned by the upgrade authority
+        let migrated = GlobalState {
+            next_market_id,
+            next_proposal_id,
+            admin:               self.payer.key(),
+            pending_admin:       Pubkey::default(),
+            fee_treasury:        self.payer.key(),
+            protocol_fee_bps:    0,
+            max_lp_fee_bps:      0,
+            max_creator_fee_bps: 0,
+            paused:              false,
+            bump:                bumps.global,
+        };
+        let mut data = global.try_borrow_mut_data()?;
+        migrated.try_serialize(&mut &mut data[..])?;
+
+        emit!(GlobalMigrated {
+            global: global.key(),
+            admin:  self.payer.key(),
+            next_market_id,
+            next_proposal_id,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::global::GlobalState;
+use crate::state::config::{LegacyMarketConfig, MarketConfig};
+use crate::state::registry::MarketProposals;
+use crate::state::oracle::OracleConfig;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::{MarketCreated, MarketMigrated};
+
+/// Rewrites a market created in the original layout into the current one.
+/// The id its address was derived from is stored, the admin sets the
+/// terms the old layout lacked (deadline, acceptance threshold and TWAP
+/// window), fees start at zero apart from the protocol rate, and the
+/// market gets its empty proposal registry. Its legacy proposals join the
+/// registry as `migrate_proposal_auth` moves them over.
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct MigrateMarket<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        has_one = admin @ QuantumError::NotAdmin,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Account<'info, GlobalState>,
+
+    /// CHECK: legacy layout that no longer deserializes as MarketConfig;
+    /// length and discriminator are checked in the handler
+    #[account(mut, seeds = [MARKET_SEED, &market_id.to_le_bytes()], bump, owner = crate::ID)]
+    pub market: UncheckedAccount<'info>,
+
+    /// only missing for legacy markets; if needed so that a migrated market
+    /// fails with MarketAlreadyMigrated
+    #[account(
+        init_if_needed,
+        seeds = [PROPOSALS_SEED, market.key().as_ref()],
+        bump,
+        payer = admin,
+        space = MarketProposals::space(REGISTRY_GROWTH),
+    )]
+    pub registry: AccountLoader<'info, MarketProposals>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateMarket<'info> {
+    pub fn handler(
+        &mut self,
+        bumps: MigrateMarketBumps,
+        market_id: u64,
+        deadline: i64,
+        accept_threshold: u64,
+        twap_window: i64,
+    ) -> Result<()> {
+        let market = self.market.to_account_info();
+        require!(market.data_len() == MarketConfig::LEGACY_LEN, QuantumError::MarketAlreadyMigrated);
+        let legacy = {
+            let data = market.try_borrow_data()?;
+            require!(
+                data[..DISCRIMINATOR] == *MarketConfig::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            LegacyMarketConfig::deserialize(&mut &data[DISCRIMINATOR..])?
+        };
+        let now = Clock::get()?.unix_timestamp;
+        require!(deadline > now, QuantumError::InvalidDeadline);
+        require!(twap_window > 0, QuantumError::InvalidWindow);
+
+        // 1) top up rent for the larger account, then grow it
+        let len = DISCRIMINATOR + MarketConfig::SIZE;
+        let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(market.lamports());
+        if shortfall > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer { from: self.admin.to_account_info(), to: market.clone() },
+                ),
+                shortfall,
+            )?;
+        }
+        market.resize(len)?;
+
+        // 2) rewrite the body: the old fields carried over, the new terms
+        //    from the admin, and the creator refunded on close as the payer
+        let migrated = MarketConfig {
+            id:                market_id,
+            created_at:        legacy.created_at,
+            deadline,
+            min_deposit:       legacy.min_deposit,
+            strike_price:      legacy.strike_price,
+            accept_threshold,
+            twap_window,
+            proposal_count:    0,
+            accepted_proposal: 0,
+            accepted_at:       0,
+            resolved_at:       0,
+            creator:           legacy.creator,
+            market_token:      legacy.market_token,
+            resolver:          legacy.resolver,
+            status:            legacy.status,
+            title:             legacy.title,
+            bump:              bumps.market,
+            lp_fee_bps:        0,
+            creator_fee_bps:   0,
+            protocol_fee_bps:  self.global.protocol_fee_bps,
+            rent_payer:        legacy.creator,
+            open_deposits:     0,
+            closed_proposals:  0,
+            execution_delay:   0,
+            oracle:            OracleConfig::default(),
+            legacy_stake:      legacy.min_deposit,
+        };
+        {
+            let mut data = market.try_borrow_mut_data()?;
+            data.fill(0);
+            migrated.try_serialize(&mut &mut data[..])?;
+        }
+
+        let mut registry = self.registry.load_init()?;
+        registry.market_id = market_id;
+        registry.capacity = REGISTRY_GROWTH;
+        registry.bump = bumps.registry;
+
+        emit!(MarketMigrated {
+            market_id,
+            market: market.key(),
+        });
+        // legacy markets predate the event log; index them from here on
+        emit!(MarketCreated {
+            market_id,
+            market: market.key(),
+            creator: migrated.creator,
+            reward_mint: migrated.market_token,
+            resolver: migrated.resolver,
+            min_deposit: migrated.min_deposit,
+            strike_price: migrated.strike_price,
+            accept_threshold,
+            twap_window,
+            deadline,
+            created_at: migrated.created_at,
+            title: migrated.title,
+            lp_fee_bps: 0,
+            creator_fee_bps: 0,
+            protocol_fee_bps: migrated.protocol_fee_bps,
+            execution_delay: 0,
+            oracle_kind: migrated.oracle.kind,
+            oracle_feed: migrated.oracle.feed,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,373 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    Mint, TokenAccount, Token, Transfer, transfer, SetAuthority, set_authority,
+    CloseAccount, close_account, MintTo, mint_to,
+};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::system_program::{transfer as transfer_lamports, Transfer as TransferLamports};
+use bytemuck::Zeroable;
+use crate::state::global::GlobalState;
+use crate::state::config::MarketConfig;
+use crate::state::proposal::{LegacyProposalConfig, ProposalConfig, PoolSide};
+use crate::state::pool::Pool;
+use crate::state::deposit::ClaimRecord;
+use crate::state::registry::{MarketProposals, ProposalEntry};
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::{ProposalAuthMigrated, ProposalCreated};
+
+/// Moves a proposal created under the shared `proposal_auth` PDA onto its
+/// own authority: mint authorities are handed over, vault balances move to
+/// ATAs of the new authority and the old vaults are closed. Such proposals
+/// are still in the original borsh layout and predate the pools, so the
+/// pools are created over the vault inventory, the account is rewritten as
+/// a zero-copy ProposalConfig and listed in its market's registry. Its
+/// claim records are counted from the remaining accounts, which must be
+/// every one of them as (user, claim record) pairs.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct MigrateProposalAuth<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    /// must be migrated first
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSALS_SEED, market.key().as_ref()],
+        bump = registry.load()?.bump,
+    )]
+    pub registry: AccountLoader<'info, MarketProposals>,
+
+    /// CHECK: legacy layout that no longer deserializes as ProposalConfig;
+    /// length and discriminator are checked in the handler
+    #[account(mut, seeds = [PROPOSAL_SEED, &proposal_id.to_le_bytes()], bump, owner = crate::ID)]
+    pub proposal: UncheckedAccount<'info>,
+
+    /// CHECK: the old global authority, signs one last time
+    #[account(seeds = [PROPOSAL_AUTH_SEED], bump)]
+    pub legacy_auth: UncheckedAccount<'info>,
+
+    /// CHECK: the new per-proposal authority
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
+    pub proposal_auth: UncheckedAccount<'info>,
+
+    // ============== mints, checked against the proposal ==============
+    #[account(mut)]
+    pub vusd_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub yes_mint:  Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub no_mint:   Box<Account<'info, Mint>>,
+
+    // ============== vaults owned by legacy_auth ==============
+    #[account(mut, associated_token::mint = vusd_mint, associated_token::authority = legacy_auth)]
+    pub legacy_vusd_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, associated_token::mint = yes_mint, associated_token::authority = legacy_auth)]
+    pub legacy_yes_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, associated_token::mint = no_mint, associated_token::authority = legacy_auth)]
+    pub legacy_no_vault: Box<Account<'info, TokenAccount>>,
+
+    // ============== vaults owned by proposal_auth ==============
+    #[account(init, payer = payer, associated_token::mint = vusd_mint, associated_token::authority = proposal_auth)]
+    pub vusd_vault: Box<Account<'info, TokenAccount>>,
+    #[account(init, payer = payer, associated_token::mint = yes_mint, associated_token::authority = proposal_auth)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+    #[account(init, payer = payer, associated_token::mint = no_mint, associated_token::authority = proposal_auth)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    // ============== YES/vUSD and NO/vUSD pools over the new vaults ==============
+    #[account(
+        init,
+        payer = payer,
+        seeds = [POOL_SEED, yes_vault.key().as_ref()],
+        bump,
+        space = 8 + Pool::SIZE
+    )]
+    pub yes_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [POOL_SEED, no_vault.key().as_ref()],
+        bump,
+        space = 8 + Pool::SIZE
+    )]
+    pub no_pool: Box<Account<'info, Pool>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateProposalAuth<'info> {
+    pub fn handler(
+        &mut self,
+        bumps: MigrateProposalAuthBumps,
+        proposal_id: u64,
+        claims: &[AccountInfo],
+    ) -> Result<()> {
+        let info = self.proposal.to_account_info();
+        require!(info.data_len() == ProposalConfig::LEGACY_LEN, QuantumError::AlreadyMigrated);
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data[..DISCRIMINATOR] == *ProposalConfig::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            LegacyProposalConfig::deserialize(&mut &data[DISCRIMINATOR..])?
+        };
+        require!(
+            legacy.id == proposal_id && legacy.market_id == self.market.id,
+            QuantumError::ProposalMismatch
+        );
+        require!(
+            self.vusd_mint.key() == legacy.vusd_mint
+                && self.yes_mint.key() == legacy.yes_mint
+                && self.no_mint.key() == legacy.no_mint,
+            QuantumError::InvalidProposalMint
+        );
+        // the original create_proposal left the inventory in the vaults
+        // without pools
+        require!(
+            legacy.yes_pool == Pubkey::default() && legacy.no_pool == Pubkey::default(),
+            QuantumError::InvalidPool
+        );
+        require!(legacy.data.len() <= MAX_DATA, QuantumError::ProposalDataTooLong);
+
+        // the original create_proposal locked the min_deposit of the
+        // original market, which a ParameterChange may have moved since;
+        // the pools get the vaults' YES/NO inventory and the vUSD share it
+        // split off for them
+        let min_d = self.market.legacy_stake;
+        require!(min_d > 0, QuantumError::ProposalMismatch);
+        let burn_total = min_d.checked_mul(2).ok_or(QuantumError::Overflow)? / 3;
+        let vusd_per_pool = (min_d - burn_total) / 2;
+
+        // the original claim_for_proposal minted exactly what each claim
+        // record holds, and never nothing, so only the full set of records
+        // adds up to the vUSD minted beyond create_proposal's
+        let (open_claims, claimed) = legacy_claims(&self.proposal.key(), claims)?;
+        require!(
+            self.vusd_mint.supply.checked_sub(min_d - burn_total) == Some(claimed),
+            QuantumError::ClaimRecordsMismatch
+        );
+        let (yes_reserve, no_reserve) = (self.legacy_yes_vault.amount, self.legacy_no_vault.amount);
+
+        let legacy_seeds: &[&[u8]] = &[PROPOSAL_AUTH_SEED, &[bumps.legacy_auth]];
+
+        for (mint, old_vault, new_vault) in [
+            (&self.vusd_mint, &self.legacy_vusd_vault, &self.vusd_vault),
+            (&self.yes_mint,  &self.legacy_yes_vault,  &self.yes_vault),
+            (&self.no_mint,   &self.legacy_no_vault,   &self.no_vault),
+        ] {
+            // 1) hand the mint over
+            set_authority(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: self.legacy_auth.to_account_info(),
+                        account_or_mint:   mint.to_account_info(),
+                    },
+                    &[legacy_seeds],
+                ),
+                AuthorityType::MintTokens,
+                Some(self.proposal_auth.key()),
+            )?;
+
+            // 2) move the vault balance
+            if old_vault.amount > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        Transfer {
+                            from:      old_vault.to_account_info(),
+                            to:        new_vault.to_account_info(),
+                            authority: self.legacy_auth.to_account_info(),
+                        },
+                        &[legacy_seeds],
+                    ),
+                    old_vault.amount,
+                )?;
+            }
+
+            // 3) close the empty legacy vault, rent to the migrator
+            close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account:     old_vault.to_account_info(),
+                    destination: self.payer.to_account_info(),
+                    authority:   self.legacy_auth.to_account_info(),
+                },
+                &[legacy_seeds],
+            ))?;
+        }
+
+        // 4) the original create_proposal minted only the pools' vUSD; back
+        //    every YES/NO pair with one vUSD, as create_proposal does now
+        let backed = vusd_per_pool
+            .checked_mul(2)
+            .and_then(|reserves| reserves.checked_add(self.yes_mint.supply))
+            .ok_or(QuantumError::Overflow)?;
+        let shortfall = backed.saturating_sub(self.legacy_vusd_vault.amount);
+        if shortfall > 0 {
+            let proposal_key = self.proposal.key();
+            mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    MintTo {
+                        mint:      self.vusd_mint.to_account_info(),
+                        to:        self.vusd_vault.to_account_info(),
+                        authority: self.proposal_auth.to_account_info(),
+                    },
+                    &[&[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]]],
+                ),
+                shortfall,
+            )?;
+        }
+
+        // 5) seed YES/vUSD and NO/vUSD pools from the new vaults
+        let now = Clock::get()?.unix_timestamp;
+        for (pool, side, mint, vault, reserve, bump) in [
+            (&mut self.yes_pool, PoolSide::Yes, &self.yes_mint, &self.yes_vault, yes_reserve, bumps.yes_pool),
+            (&mut self.no_pool,  PoolSide::No,  &self.no_mint,  &self.no_vault,  no_reserve,  bumps.no_pool),
+        ] {
+            pool.init(
+                proposal_id,
+                legacy.market_id,
+                side,
+                mint.key(),
+                vault.key(),
+                self.vusd_vault.key(),
+                reserve,
+                vusd_per_pool,
+                now,
+                bump,
+            );
+        }
+
+        // 6) list it in the market's registry, priced at the pools
+        let registry_index = MarketProposals::push(
+            &self.registry,
+            ProposalEntry {
+                id:         proposal_id,
+                yes_price:  self.yes_pool.spot_price()?,
+                no_price:   self.no_pool.spot_price()?,
+                updated_at: now,
+                vusd_mint:  legacy.vusd_mint,
+                yes_mint:   legacy.yes_mint,
+                no_mint:    legacy.no_mint,
+            },
+            &self.payer,
+            &self.system_program,
+        )?;
+        self.market.proposal_count = self.market.proposal_count
+            .checked_add(1)
+            .ok_or(QuantumError::Overflow)?;
+
+        // 7) grow the account at the payer's expense and rewrite it in the
+        //    zero-copy layout; fees start empty
+        let len = DISCRIMINATOR + ProposalConfig::SIZE;
+        let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(info.lamports());
+        if shortfall > 0 {
+            transfer_lamports(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    TransferLamports { from: self.payer.to_account_info(), to: info.clone() },
+                ),
+                shortfall,
+            )?;
+        }
+        info.resize(len)?;
+
+        let mut proposal = ProposalConfig::zeroed();
+        proposal.id = proposal_id;
+        proposal.market_id = legacy.market_id;
+        proposal.created_at = legacy.created_at;
+        proposal.creator = legacy.creator;
+        proposal.stake = min_d;
+        proposal.open_claims = open_claims;
+        proposal.vusd_mint = legacy.vusd_mint;
+        proposal.yes_mint = legacy.yes_mint;
+        proposal.no_mint = legacy.no_mint;
+        proposal.yes_pool = self.yes_pool.key();
+        proposal.no_pool = self.no_pool.key();
+        proposal.rent_payer = legacy.creator;
+        proposal.data[..legacy.data.len()].copy_from_slice(&legacy.data);
+        proposal.data_len = legacy.data.len() as u16;
+        proposal.bump = legacy.bump;
+        proposal.auth_version = PROPOSAL_AUTH_V1;
+        proposal.registry_index = registry_index;
+        info.try_borrow_mut_data()?[DISCRIMINATOR..].copy_from_slice(bytemuck::bytes_of(&proposal));
+
+        // legacy proposals predate the event log; index them from here on
+        emit!(ProposalCreated {
+            proposal_id,
+            market_id:   legacy.market_id,
+            proposal:    info.key(),
+            creator:     legacy.creator,
+            vusd_mint:   legacy.vusd_mint,
+            yes_mint:    legacy.yes_mint,
+            no_mint:     legacy.no_mint,
+            yes_pool:    self.yes_pool.key(),
+            no_pool:     self.no_pool.key(),
+            locked:      min_d,
+            pool_tokens: yes_reserve,
+            pool_vusd:   vusd_per_pool,
+            created_at:  legacy.created_at,
+            data:        legacy.data,
+        });
+        emit!(ProposalAuthMigrated {
+            proposal_id,
+            proposal_auth: self.proposal_auth.key(),
+            vusd_vault:    self.vusd_vault.key(),
+            yes_vault:     self.yes_vault.key(),
+            no_vault:      self.no_vault.key(),
+        });
+        Ok(())
+    }
+}
+
+/// how many claim records `claims` holds, as (user, claim record) pairs of
+/// `proposal` in the original layout, and the vUSD they claimed
+fn legacy_claims(proposal: &Pubkey, claims: &[AccountInfo]) -> Result<(u64, u64)> {
+    let pairs = claims.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), QuantumError::InvalidClaimRecord);
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(claims.len() / 2);
+    let mut claimed: u64 = 0;
+    for pair in pairs {
+        let (user, record) = (&pair[0], &pair[1]);
+        let (expected, _) = Pubkey::find_program_address(
+            &[CLAIM_SEED, proposal.as_ref(), user.key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(*record.key, expected, QuantumError::InvalidClaimRecord);
+        require_keys_eq!(*record.owner, crate::ID, QuantumError::InvalidClaimRecord);
+        require!(!seen.contains(record.key), QuantumError::InvalidClaimRecord);
+        seen.push(*record.key);
+
+        let data = record.try_borrow_data()?;
+        require!(
+            data.len() == ClaimRecord::LEGACY_LEN
+                && data[..DISCRIMINATOR] == *ClaimRecord::DISCRIMINATOR,
+            QuantumError::InvalidClaimRecord
+        );
+        let amount = u64::deserialize(&mut &data[DISCRIMINATOR..])?;
+        claimed = claimed.checked_add(amount).ok_or(QuantumError::Overflow)?;
+    }
+    Ok((seen.len() as u64, claimed))
+}
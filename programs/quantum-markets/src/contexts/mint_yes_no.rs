@@ -1,36 +1,63 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Transfer, transfer, MintTo, mint_to, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::global::GlobalState;
 use crate::state::proposal::ProposalConfig;
+use crate::state::config::MarketConfig;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::YesNoMinted;
 
 #[derive(Accounts)]
 pub struct MintYesNo<'info> {
     #[account(mut)] pub payer: Signer<'info>,
 
     #[account(
-        seeds = [b"proposal", &proposal.id.to_le_bytes()],
-        bump
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
     )]
-    pub proposal: Account<'info, ProposalConfig>,
+    pub global: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, MarketConfig>,
 
     // vUSD mint and authority
-    #[account(mut, address = proposal.vusd_mint)] pub vusd_mint: Account<'info, Mint>,
+    #[account(mut, address = proposal.load()?.vusd_mint @ QuantumError::InvalidProposalMint)]
+    pub vusd_mint: Account<'info, Mint>,
     /// CHECK:
-    #[account(seeds = [b"proposal_auth"], bump)]
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
     pub proposal_auth: UncheckedAccount<'info>,
 
     // user gives vUSD
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = user_vusd.mint == vusd_mint.key() @ QuantumError::InvalidTokenAccount,
+        constraint = user_vusd.owner == payer.key() @ QuantumError::InvalidTokenAccount,
+    )]
     pub user_vusd: Account<'info, TokenAccount>,
     #[account(
         mut,
-        associated_token::mint = vusd_mint,
-        associated_token::authority = proposal_auth
+        address = get_associated_token_address(&proposal_auth.key(), &vusd_mint.key()) @ QuantumError::InvalidVault,
     )]
     pub vault_vusd: Account<'info, TokenAccount>,
 
     // YES mint, NO mint
-    #[account(mut, address = proposal.yes_mint)] pub yes_mint: Account<'info, Mint>,
-    #[account(mut, address = proposal.no_mint)]  pub no_mint:  Account<'info, Mint>,
+    #[account(mut, address = proposal.load()?.yes_mint @ QuantumError::InvalidProposalMint)]
+    pub yes_mint: Account<'info, Mint>,
+    #[account(mut, address = proposal.load()?.no_mint @ QuantumError::InvalidProposalMint)]
+    pub no_mint:  Account<'info, Mint>,
 
     // user ATAs to receive inventory
     #[account(init_if_needed, payer = payer, associated_token::mint = yes_mint, associated_token::authority = payer)]
@@ -45,6 +72,9 @@ pub struct MintYesNo<'info> {
 
 impl<'info> MintYesNo<'info> {
     pub fn handler(&mut self, bumps: MintYesNoBumps, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(self.market.is_active(now), QuantumError::MarketClosed);
+
         // move vUSD from user → vault
         transfer(
             CpiContext::new(
@@ -58,6 +88,7 @@ impl<'info> MintYesNo<'info> {
         )?;
 
         // mint YES
+        let proposal_key = self.proposal.key();
         for (mint, dest) in [
             (&self.yes_mint, &self.user_yes),
             (&self.no_mint,  &self.user_no),
@@ -70,11 +101,19 @@ impl<'info> MintYesNo<'info> {
                         to:        dest.to_account_info(),
                         authority: self.proposal_auth.to_account_info(),
                     },
-                    &[&[b"proposal_auth", &[bumps.proposal_auth]]],
+                    &[&[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]]],
                 ),
                 amount,
             )?;
         }
+
+        let proposal = self.proposal.load()?;
+        emit!(YesNoMinted {
+            proposal_id: proposal.id,
+            market_id:   proposal.market_id,
+            user:        self.payer.key(),
+            amount,
+        });
         Ok(())
     }
 }
@@ -5,10 +5,56 @@ pub mod deposit_to_market;
 pub mod mint_yes_no;
 pub mod redeem_yes_no;
 pub mod claim_for_proposal;
+pub mod swap;
+pub mod read_twap;
+pub mod expire_market;
+pub mod graduate_proposal;
+pub mod resolve_market;
+pub mod redeem_winning;
+pub mod withdraw_deposit;
+pub mod withdraw_unclaimed;
+pub mod migrate_proposal_auth;
+pub mod migrate_global;
+pub mod migrate_market;
+pub mod migrate_deposit_record;
+pub mod migrate_claim_record;
+pub mod update_global;
+pub mod accept_admin;
+pub mod collect_fees;
+pub mod close_deposit_record;
+pub mod close_claim_record;
+pub mod close_proposal;
+pub mod close_market;
+pub mod execute_proposal;
+pub mod fund_treasury;
+pub mod resolve_with_oracle;
 pub use create_market::*;
 pub use initialize_global::*;
 pub use create_proposal::*;
 pub use deposit_to_market::*;
 pub use mint_yes_no::*;
 pub use redeem_yes_no::*;
-pub use claim_for_proposal::*;
\ No newline at end of file
+pub use claim_for_proposal::*;
+pub use swap::*;
+pub use read_twap::*;
+pub use expire_market::*;
+pub use graduate_proposal::*;
+pub use resolve_market::*;
+pub use redeem_winning::*;
+pub use withdraw_deposit::*;
+pub use withdraw_unclaimed::*;
+pub use migrate_proposal_auth::*;
+pub use migrate_global::*;
+pub use migrate_market::*;
+pub use migrate_deposit_record::*;
+pub use migrate_claim_record::*;
+pub use update_global::*;
+pub use accept_admin::*;
+pub use collect_fees::*;
+pub use close_deposit_record::*;
+pub use close_claim_record::*;
+pub use close_proposal::*;
+pub use close_market::*;
+pub use execute_proposal::*;
+pub use fund_treasury::*;
+pub use resolve_with_oracle::*;
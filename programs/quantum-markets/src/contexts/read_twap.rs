@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::proposal::ProposalConfig;
+use crate::state::pool::Pool;
+use crate::errors::QuantumError;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ReadTwap<'info> {
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    #[account(address = proposal.load()?.yes_pool @ QuantumError::InvalidPool)]
+    pub yes_pool: Box<Account<'info, Pool>>,
+}
+
+impl<'info> ReadTwap<'info> {
+    /// YES price (vUSD per YES, PRICE_SCALE decimals) averaged over `window` seconds
+    pub fn handler(&self, window: i64) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        self.yes_pool.twap(now, window)
+    }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Burn, burn, Transfer, transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::state::proposal::{ProposalConfig, PoolSide};
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::WinningRedeemed;
+
+#[derive(Accounts)]
+pub struct RedeemWinning<'info> {
+    #[account(mut)] pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    /// the accepted proposal of this market
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.id == market.accepted_proposal @ QuantumError::ProposalNotAccepted,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    /// CHECK: signer via seeds
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
+    pub proposal_auth: UncheckedAccount<'info>,
+
+    // winning side (YES or NO) and the vUSD backing it
+    #[account(mut)] pub winning_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = proposal.load()?.vusd_mint @ QuantumError::InvalidProposalMint)]
+    pub vusd_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        address = get_associated_token_address(&proposal_auth.key(), &vusd_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub vusd_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_winning.mint == winning_mint.key() @ QuantumError::InvalidTokenAccount,
+        constraint = user_winning.owner == payer.key() @ QuantumError::InvalidTokenAccount,
+    )]
+    pub user_winning: Box<Account<'info, TokenAccount>>,
+
+    // reward token paid out of the market's deposits
+    #[account(address = market.market_token @ QuantumError::InvalidRewardMint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        address = get_associated_token_address(&market.key(), &reward_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = payer
+    )]
+    pub user_reward: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedeemWinning<'info> {
+    pub fn handler(&mut self, bumps: RedeemWinningBumps, amount: u64) -> Result<()> {
+        require!(amount > 0, QuantumError::ZeroAmount);
+        let proposal = self.proposal.load()?;
+        let (side, expected_mint) = match self.market.status {
+            MarketStatus::ResolvedYes => (PoolSide::Yes, proposal.yes_mint),
+            MarketStatus::ResolvedNo  => (PoolSide::No, proposal.no_mint),
+            _ => return err!(QuantumError::MarketNotResolved),
+        };
+        require_keys_eq!(self.winning_mint.key(), expected_mint, QuantumError::NotWinningToken);
+
+        // pay pro rata to the vUSD still backing the winning supply, never above 1:1;
+        // fees not yet collected sit in the same vault but back nothing
+        let backing = self.vusd_vault.amount.saturating_sub(proposal.uncollected_fees()) as u128;
+        let supply = self.winning_mint.supply as u128;
+        let payout = if backing >= supply {
+            amount
+        } else {
+            ((amount as u128)
+                .checked_mul(backing)
+                .ok_or(QuantumError::Overflow)?
+                / supply) as u64
+        };
+        require!(payout > 0, QuantumError::NothingToClaim);
+
+        // burn the winning tokens from the caller
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint:      self.winning_mint.to_account_info(),
+                    from:      self.user_winning.to_account_info(),
+                    authority: self.payer.to_account_info(),
+                }),
+            amount,
+        )?;
+
+        // retire the vUSD that backed them
+        let proposal_key = self.proposal.key();
+        let auth_seeds: &[&[u8]] = &[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]];
+        burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint:      self.vusd_mint.to_account_info(),
+                    from:      self.vusd_vault.to_account_info(),
+                    authority: self.proposal_auth.to_account_info(),
+                },
+                &[auth_seeds],
+            ),
+            payout,
+        )?;
+
+        // market PDA releases the reward token
+        let id_bytes = self.market.id.to_le_bytes();
+        let market_seeds: &[&[u8]] = &[MARKET_SEED, &id_bytes, &[self.market.bump]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from:      self.market_vault.to_account_info(),
+                    to:        self.user_reward.to_account_info(),
+                    authority: self.market.to_account_info(),
+                },
+                &[market_seeds],
+            ),
+            payout,
+        )?;
+
+        emit!(WinningRedeemed {
+            market_id:   self.market.id,
+            proposal_id: proposal.id,
+            user:        self.payer.key(),
+            side,
+            amount,
+            payout,
+        });
+        Ok(())
+    }
+}
@@ -1,33 +1,62 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Burn, burn, Transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::global::GlobalState;
 use crate::state::proposal::ProposalConfig;
+use crate::state::config::MarketConfig;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::YesNoRedeemed;
 
 #[derive(Accounts)]
 pub struct RedeemYesNo<'info> {
     #[account(mut)] pub payer: Signer<'info>,
 
-    #[account(seeds = [b"proposal", &proposal.id.to_le_bytes()], bump)]
-    pub proposal: Account<'info, ProposalConfig>,
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, MarketConfig>,
 
     /// CHECK:
-    #[account(seeds = [b"proposal_auth"], bump)]
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
     pub proposal_auth: UncheckedAccount<'info>,
 
     // mints
-    #[account(mut, address = proposal.yes_mint)] pub yes_mint: Account<'info, Mint>,
-    #[account(mut, address = proposal.no_mint)]  pub no_mint:  Account<'info, Mint>,
-    #[account(address = proposal.vusd_mint)] pub vusd_mint: Account<'info, Mint>,
+    #[account(mut, address = proposal.load()?.yes_mint @ QuantumError::InvalidProposalMint)]
+    pub yes_mint: Account<'info, Mint>,
+    #[account(mut, address = proposal.load()?.no_mint @ QuantumError::InvalidProposalMint)]
+    pub no_mint:  Account<'info, Mint>,
+    #[account(address = proposal.load()?.vusd_mint @ QuantumError::InvalidProposalMint)]
+    pub vusd_mint: Account<'info, Mint>,
 
-    // user token accounts
-    #[account(mut)] pub user_yes: Account<'info, TokenAccount>,
-    #[account(mut)] pub user_no:  Account<'info, TokenAccount>,
-    #[account(mut)] pub user_vusd: Account<'info, TokenAccount>,
+    // user token accounts; YES and NO are burned with the payer's authority
+    #[account(mut, constraint = user_yes.mint == yes_mint.key() @ QuantumError::InvalidTokenAccount)]
+    pub user_yes: Account<'info, TokenAccount>,
+    #[account(mut, constraint = user_no.mint == no_mint.key() @ QuantumError::InvalidTokenAccount)]
+    pub user_no:  Account<'info, TokenAccount>,
+    #[account(mut, constraint = user_vusd.mint == vusd_mint.key() @ QuantumError::InvalidTokenAccount)]
+    pub user_vusd: Account<'info, TokenAccount>,
 
     // vault that holds vUSD
     #[account(
         mut,
-        associated_token::mint = vusd_mint,
-        associated_token::authority = proposal_auth
+        address = get_associated_token_address(&proposal_auth.key(), &vusd_mint.key()) @ QuantumError::InvalidVault,
     )]
     pub vault_vusd: Account<'info, TokenAccount>,
 
@@ -36,6 +65,9 @@ pub struct RedeemYesNo<'info> {
 
 impl<'info> RedeemYesNo<'info> {
     pub fn handler(&mut self, bumps: RedeemYesNoBumps, amount: u64) -> Result<()> {
+        // after resolution the winning side redeems through redeem_winning
+        require!(!self.market.is_resolved(), QuantumError::MarketAlreadyResolved);
+
         // burn YES + NO from caller
         for (mint, from) in [
             (&self.yes_mint, &self.user_yes),
@@ -53,7 +85,8 @@ impl<'info> RedeemYesNo<'info> {
             )?;
         }
         
-        let auth_seeds: &[&[u8]] = &[b"proposal_auth", &[bumps.proposal_auth]];
+        let proposal_key = self.proposal.key();
+        let auth_seeds: &[&[u8]] = &[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]];
 
         anchor_spl::token::transfer(
             CpiContext::new_with_signer(
@@ -67,6 +100,14 @@ impl<'info> RedeemYesNo<'info> {
             ),
             amount,
         )?;
+
+        let proposal = self.proposal.load()?;
+        emit!(YesNoRedeemed {
+            proposal_id: proposal.id,
+            market_id:   proposal.market_id,
+            user:        self.payer.key(),
+            amount,
+        });
         Ok(())
     }
 }
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::MarketResolved;
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    /// must match the resolver stored at create_market
+    pub resolver: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+        has_one = resolver @ QuantumError::InvalidResolver,
+    )]
+    pub market: Account<'info, MarketConfig>,
+}
+
+impl<'info> ResolveMarket<'info> {
+    pub fn handler(&mut self, outcome_yes: bool) -> Result<()> {
+        require!(!self.market.has_oracle(), QuantumError::ResolvedByOracle);
+        require!(!self.market.is_resolved(), QuantumError::MarketAlreadyResolved);
+        require!(
+            self.market.status == MarketStatus::ProposalAccepted,
+            QuantumError::MarketNotAccepted
+        );
+
+        self.market.status = if outcome_yes {
+            MarketStatus::ResolvedYes
+        } else {
+            MarketStatus::ResolvedNo
+        };
+        self.market.resolved_at = Clock::get()?.unix_timestamp;
+
+        emit!(MarketResolved {
+            market_id:   self.market.id,
+            proposal_id: self.market.accepted_proposal,
+            resolver:    self.resolver.key(),
+            outcome_yes,
+            timestamp:   self.market.resolved_at,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::{MarketResolved, OracleSettled};
+
+/// Permissionless crank for markets created with an oracle: once the
+/// deadline has passed, the accepted market resolves YES if the feed's
+/// price is at least `strike_price` and NO otherwise. The reading must be
+/// published after the deadline, within the staleness limit, and have a
+/// confidence interval within the market's limit.
+#[derive(Accounts)]
+pub struct ResolveWithOracle<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Account<'info, MarketConfig>,
+
+    /// CHECK: the feed named at create_market; its layout is checked on read
+    #[account(address = market.oracle.feed @ QuantumError::InvalidOracleAccount)]
+    pub price_feed: UncheckedAccount<'info>,
+}
+
+impl<'info> ResolveWithOracle<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        require!(self.market.has_oracle(), QuantumError::NoOracle);
+        require!(!self.market.is_resolved(), QuantumError::MarketAlreadyResolved);
+        require!(
+            self.market.status == MarketStatus::ProposalAccepted,
+            QuantumError::MarketNotAccepted
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= self.market.deadline, QuantumError::DeadlineNotReached);
+
+        let oracle = self.market.oracle;
+        let reading = oracle.read(&self.price_feed.to_account_info())?;
+        require!(
+            reading.publish_time >= self.market.deadline
+                && now.saturating_sub(reading.publish_time) <= oracle.max_staleness,
+            QuantumError::OraclePriceStale
+        );
+        // confidence / |price| <= max_confidence_bps / BPS
+        let max_confidence = reading.price
+            .unsigned_abs()
+            .checked_mul(oracle.max_confidence_bps as u128)
+            .ok_or(QuantumError::Overflow)?;
+        require!(
+            reading.confidence.unsigned_abs().saturating_mul(BPS as u128) <= max_confidence,
+            QuantumError::OracleConfidenceTooWide
+        );
+
+        let outcome_yes = reading.price >= self.market.strike_price as i128;
+        self.market.status = if outcome_yes {
+            MarketStatus::ResolvedYes
+        } else {
+            MarketStatus::ResolvedNo
+        };
+        self.market.resolved_at = now;
+
+        let clamp = |value: i128| value.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        emit!(OracleSettled {
+            market_id:    self.market.id,
+            feed:         oracle.feed,
+            price:        clamp(reading.price),
+            confidence:   clamp(reading.confidence),
+            publish_time: reading.publish_time,
+            strike_price: self.market.strike_price,
+        });
+        emit!(MarketResolved {
+            market_id:   self.market.id,
+            proposal_id: self.market.accepted_proposal,
+            resolver:    oracle.feed,
+            outcome_yes,
+            timestamp:   now,
+        });
+        Ok(())
+    }
+}
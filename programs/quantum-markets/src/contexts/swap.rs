@@ -0,0 +1,215 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Transfer, transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::global::GlobalState;
+use crate::state::proposal::ProposalConfig;
+use crate::state::config::MarketConfig;
+use crate::state::pool::{Pool, SwapDirection};
+use crate::state::fees::Fees;
+use crate::state::registry::MarketProposals;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::Swapped;
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)] pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.load()?.id.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.market_id == market.id @ QuantumError::ProposalMismatch,
+        constraint = proposal.load()?.auth_version == PROPOSAL_AUTH_V1 @ QuantumError::LegacyProposalAuth,
+    )]
+    pub proposal: AccountLoader<'info, ProposalConfig>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    /// keeps the latest price of every proposal
+    #[account(
+        mut,
+        seeds = [PROPOSALS_SEED, market.key().as_ref()],
+        bump = registry.load()?.bump,
+    )]
+    pub registry: AccountLoader<'info, MarketProposals>,
+
+    /// YES or NO pool of this proposal
+    #[account(
+        mut,
+        has_one = token_vault @ QuantumError::InvalidVault,
+        has_one = vusd_vault @ QuantumError::InvalidVault,
+        constraint = pool.key() == proposal.load()?.yes_pool
+            || pool.key() == proposal.load()?.no_pool @ QuantumError::InvalidPool,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: signer via seeds
+    #[account(seeds = [PROPOSAL_AUTH_SEED, proposal.key().as_ref()], bump)]
+    pub proposal_auth: UncheckedAccount<'info>,
+
+    // mints
+    #[account(address = pool.token_mint @ QuantumError::InvalidProposalMint)]
+    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = proposal.load()?.vusd_mint @ QuantumError::InvalidProposalMint)]
+    pub vusd_mint: Box<Account<'info, Mint>>,
+
+    // pool vaults (owned by proposal_auth)
+    #[account(mut)] pub token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)] pub vusd_vault: Box<Account<'info, TokenAccount>>,
+
+    // user token accounts
+    #[account(init_if_needed, payer = payer, associated_token::mint = token_mint, associated_token::authority = payer)]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+    #[account(init_if_needed, payer = payer, associated_token::mint = vusd_mint, associated_token::authority = payer)]
+    pub user_vusd: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Swap<'info> {
+    pub fn exact_in(
+        &mut self,
+        bumps: SwapBumps,
+        direction: SwapDirection,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        require!(amount_in > 0, QuantumError::ZeroAmount);
+        let rates = self.market.fee_rates();
+        // fees come out of the vUSD leg: the input of a buy, the output of a sell
+        let (amount_out, fees) = match direction {
+            SwapDirection::Buy => {
+                let fees = rates.on_gross(amount_in);
+                (self.pool.quote_exact_in(direction, amount_in - fees.total())?, fees)
+            }
+            SwapDirection::Sell => {
+                let gross = self.pool.quote_exact_in(direction, amount_in)?;
+                let fees = rates.on_gross(gross);
+                (gross - fees.total(), fees)
+            }
+        };
+        require!(amount_out > 0, QuantumError::InsufficientLiquidity);
+        require!(amount_out >= min_amount_out, QuantumError::SlippageExceeded);
+        self.settle(bumps, direction, amount_in, amount_out, fees)
+    }
+
+    pub fn exact_out(
+        &mut self,
+        bumps: SwapBumps,
+        direction: SwapDirection,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<()> {
+        require!(amount_out > 0, QuantumError::ZeroAmount);
+        let rates = self.market.fee_rates();
+        let (amount_in, fees) = match direction {
+            SwapDirection::Buy => rates.gross_up(self.pool.quote_exact_out(direction, amount_out)?)?,
+            SwapDirection::Sell => {
+                let (gross, fees) = rates.gross_up(amount_out)?;
+                (self.pool.quote_exact_out(direction, gross)?, fees)
+            }
+        };
+        require!(amount_in <= max_amount_in, QuantumError::SlippageExceeded);
+        self.settle(bumps, direction, amount_in, amount_out, fees)
+    }
+
+    fn settle(
+        &mut self,
+        bumps: SwapBumps,
+        direction: SwapDirection,
+        amount_in: u64,
+        amount_out: u64,
+        fees: Fees,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(self.market.is_active(now), QuantumError::MarketClosed);
+
+        // close the oracle interval at the pre-trade price
+        self.pool.observe(now, Pool::observation_spacing(self.market.twap_window))?;
+        // the LP fee stays in the reserves, the rest is set aside in the vault
+        match direction {
+            SwapDirection::Buy => self.pool.apply(direction, amount_in - fees.set_aside(), amount_out)?,
+            SwapDirection::Sell => {
+                let vusd_out = amount_out.checked_add(fees.set_aside()).ok_or(QuantumError::Overflow)?;
+                self.pool.apply(direction, amount_in, vusd_out)?
+            }
+        }
+        let (proposal_id, market_id, registry_index) = {
+            let mut proposal = self.proposal.load_mut()?;
+            proposal.protocol_fees = proposal.protocol_fees
+                .checked_add(fees.protocol)
+                .ok_or(QuantumError::Overflow)?;
+            proposal.creator_fees = proposal.creator_fees
+                .checked_add(fees.creator)
+                .ok_or(QuantumError::Overflow)?;
+            (proposal.id, proposal.market_id, proposal.registry_index)
+        };
+        let price = self.pool.spot_price()?;
+        MarketProposals::record_price(&self.registry, registry_index, self.pool.side, price, now)?;
+
+        let (user_in, vault_in, vault_out, user_out) = match direction {
+            SwapDirection::Buy  => (&self.user_vusd, &self.vusd_vault, &self.token_vault, &self.user_token),
+            SwapDirection::Sell => (&self.user_token, &self.token_vault, &self.vusd_vault, &self.user_vusd),
+        };
+
+        // user → vault
+        transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from:      user_in.to_account_info(),
+                    to:        vault_in.to_account_info(),
+                    authority: self.payer.to_account_info(),
+                }),
+            amount_in,
+        )?;
+
+        // vault → user
+        let proposal_key = self.proposal.key();
+        let auth_seeds: &[&[u8]] = &[PROPOSAL_AUTH_SEED, proposal_key.as_ref(), &[bumps.proposal_auth]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from:      vault_out.to_account_info(),
+                    to:        user_out.to_account_info(),
+                    authority: self.proposal_auth.to_account_info(),
+                },
+                &[auth_seeds],
+            ),
+            amount_out,
+        )?;
+
+        emit!(Swapped {
+            proposal_id,
+            market_id,
+            user:          self.payer.key(),
+            side:          self.pool.side,
+            direction,
+            amount_in,
+            amount_out,
+            lp_fee:        fees.lp,
+            protocol_fee:  fees.protocol,
+            creator_fee:   fees.creator,
+            reserve_token: self.pool.reserve_token,
+            reserve_vusd:  self.pool.reserve_vusd,
+            price,
+            timestamp:     now,
+        });
+        Ok(())
+    }
+}
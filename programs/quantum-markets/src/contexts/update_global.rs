@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::global::GlobalState;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::GlobalConfigUpdated;
+
+/// Admin-only changes to the protocol configuration. Each takes effect at
+/// once, except the admin handover which the new admin must accept.
+#[derive(Accounts)]
+pub struct UpdateGlobal<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        has_one = admin @ QuantumError::NotAdmin,
+    )]
+    pub global: Account<'info, GlobalState>,
+}
+
+/// event carrying the configuration after an admin instruction
+pub(crate) fn config_updated(global: &GlobalState) -> GlobalConfigUpdated {
+    GlobalConfigUpdated {
+        admin:               global.admin,
+        pending_admin:       global.pending_admin,
+        fee_treasury:        global.fee_treasury,
+        protocol_fee_bps:    global.protocol_fee_bps,
+        max_lp_fee_bps:      global.max_lp_fee_bps,
+        max_creator_fee_bps: global.max_creator_fee_bps,
+        paused:              global.paused,
+    }
+}
+
+impl<'info> UpdateGlobal<'info> {
+    /// offer the admin role to `new_admin`; the default key cancels an offer
+    pub fn transfer_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.global.pending_admin = new_admin;
+        emit!(config_updated(&self.global));
+        Ok(())
+    }
+
+    pub fn set_fees(
+        &mut self,
+        protocol_fee_bps: u16,
+        max_lp_fee_bps: u16,
+        max_creator_fee_bps: u16,
+    ) -> Result<()> {
+        for bps in [protocol_fee_bps, max_lp_fee_bps, max_creator_fee_bps] {
+            require!(bps <= MAX_FEE_BPS, QuantumError::FeeTooHigh);
+        }
+        self.global.protocol_fee_bps = protocol_fee_bps;
+        self.global.max_lp_fee_bps = max_lp_fee_bps;
+        self.global.max_creator_fee_bps = max_creator_fee_bps;
+        emit!(config_updated(&self.global));
+        Ok(())
+    }
+
+    pub fn set_fee_treasury(&mut self, fee_treasury: Pubkey) -> Result<()> {
+        self.global.fee_treasury = fee_treasury;
+        emit!(config_updated(&self.global));
+        Ok(())
+    }
+
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        self.global.paused = paused;
+        emit!(config_updated(&self.global));
+        Ok(())
+    }
+}
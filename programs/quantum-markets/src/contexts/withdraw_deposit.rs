@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Transfer, transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::state::proposal::ProposalConfig;
+use crate::state::deposit::{DepositRecord, ClaimRecord};
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::DepositWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawDeposit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_SEED, market.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Accepted proposal, required unless the market timed out
+    pub accepted_proposal: Option<AccountLoader<'info, ProposalConfig>>,
+
+    /// Caller's claim PDA for the accepted proposal; may be uninitialized
+    /// CHECK: address and owner verified in the handler
+    pub accepted_claim: Option<UncheckedAccount<'info>>,
+
+    #[account(address = market.market_token @ QuantumError::InvalidRewardMint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(&market.key(), &reward_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = payer
+    )]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawDeposit<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let remaining = self.deposit_record.amount;
+        let refund = withdrawable(
+            &self.market,
+            &self.deposit_record,
+            self.accepted_proposal.as_ref(),
+            self.accepted_claim.as_ref(),
+            &self.payer.key(),
+        )?;
+        require!(refund > 0, QuantumError::NothingToClaim);
+
+        self.deposit_record.amount = remaining
+            .checked_sub(refund)
+            .ok_or(QuantumError::Underflow)?;
+
+        let id_bytes = self.market.id.to_le_bytes();
+        let market_seeds: &[&[u8]] = &[MARKET_SEED, &id_bytes, &[self.market.bump]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from:      self.market_vault.to_account_info(),
+                    to:        self.user_token.to_account_info(),
+                    authority: self.market.to_account_info(),
+                },
+                &[market_seeds],
+            ),
+            refund,
+        )?;
+
+        emit!(DepositWithdrawn {
+            market_id: self.market.id,
+            user:      self.payer.key(),
+            amount:    refund,
+            remaining: self.deposit_record.amount,
+        });
+        Ok(())
+    }
+}
+
+/// part of `user`'s deposit record the market no longer needs
+pub(crate) fn withdrawable(
+    market: &MarketConfig,
+    deposit: &DepositRecord,
+    accepted_proposal: Option<&AccountLoader<ProposalConfig>>,
+    accepted_claim: Option<&UncheckedAccount>,
+    user: &Pubkey,
+) -> Result<u64> {
+    let remaining = deposit.amount;
+    Ok(match market.status {
+        // nothing was accepted: every deposit goes back in full
+        MarketStatus::Timeout => remaining,
+        // positions in losing proposals are void; only what the user
+        // claimed into the accepted proposal stays in the market
+        MarketStatus::ProposalAccepted
+        | MarketStatus::ResolvedYes
+        | MarketStatus::ResolvedNo => {
+            remaining.saturating_sub(accepted_claimed(market, accepted_proposal, accepted_claim, user)?)
+        }
+        MarketStatus::Open => return err!(QuantumError::MarketNotSettled),
+    })
+}
+
+/// how much of the deposit `user` claimed into the accepted proposal
+fn accepted_claimed(
+    market: &MarketConfig,
+    accepted_proposal: Option<&AccountLoader<ProposalConfig>>,
+    accepted_claim: Option<&UncheckedAccount>,
+    user: &Pubkey,
+) -> Result<u64> {
+    let proposal = accepted_proposal.ok_or(QuantumError::ProposalNotAccepted)?;
+    let config = proposal.load()?;
+    require!(
+        config.market_id == market.id
+            && config.id == market.accepted_proposal,
+        QuantumError::ProposalNotAccepted
+    );
+
+    let claim = accepted_claim.ok_or(QuantumError::InvalidClaimRecord)?;
+    let (expected, _) = Pubkey::find_program_address(
+        &[CLAIM_SEED, proposal.key().as_ref(), user.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(claim.key(), expected, QuantumError::InvalidClaimRecord);
+
+    // never claimed into the accepted proposal
+    if claim.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(*claim.owner, crate::ID, QuantumError::InvalidClaimRecord);
+    let record = ClaimRecord::try_deserialize(&mut &claim.try_borrow_data()?[..])?;
+    Ok(record.claimed)
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Transfer, transfer, TokenAccount, Mint, Token};
+use anchor_spl::associated_token::get_associated_token_address;
+use crate::state::global::GlobalState;
+use crate::state::config::{MarketStatus, MarketConfig};
+use crate::state::deposit::DepositRecord;
+use crate::errors::QuantumError;
+use crate::constants::*;
+use crate::events::UnclaimedWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawUnclaimed<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global.bump,
+        constraint = !global.paused @ QuantumError::ProtocolPaused,
+    )]
+    pub global: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [MARKET_SEED, &market.id.to_le_bytes()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, MarketConfig>>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_SEED, market.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    #[account(address = market.market_token @ QuantumError::InvalidRewardMint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(&market.key(), &reward_mint.key()) @ QuantumError::InvalidVault,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    /// User’s ATA receiving the market token back
+    #[account(
+        mut,
+        constraint = user_token.mint == reward_mint.key() @ QuantumError::InvalidTokenAccount,
+        constraint = user_token.owner == payer.key() @ QuantumError::InvalidTokenAccount,
+    )]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawUnclaimed<'info> {
+    pub fn handler(&mut self, amount: u64) -> Result<()> {
+        require!(self.market.status == MarketStatus::Open, QuantumError::MarketNotOpen);
+        require!(amount > 0, QuantumError::ZeroAmount);
+
+        // vUSD minted in any proposal is still backed by this deposit
+        require!(amount <= self.deposit_record.unclaimed(), QuantumError::ExceedsUnclaimed);
+        self.deposit_record.amount = self
+            .deposit_record
+            .amount
+            .checked_sub(amount)
+            .ok_or(QuantumError::Underflow)?;
+
+        let id_bytes = self.market.id.to_le_bytes();
+        let market_seeds: &[&[u8]] = &[MARKET_SEED, &id_bytes, &[self.market.bump]];
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from:      self.market_vault.to_account_info(),
+                    to:        self.user_token.to_account_info(),
+                    authority: self.market.to_account_info(),
+                },
+                &[market_seeds],
+            ),
+            amount,
+        )?;
+
+        emit!(UnclaimedWithdrawn {
+            market_id: self.market.id,
+            user:      self.payer.key(),
+            amount,
+            remaining: self.deposit_record.amount,
+        });
+        Ok(())
+    }
+}
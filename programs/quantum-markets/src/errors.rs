@@ -13,5 +13,125 @@ pub enum QuantumError {
     #[msg("Market is closed.")]
     MarketClosed,
     #[msg("Nothing to claim.")]
-    NothingToClaim
+    NothingToClaim,
+    #[msg("Amount must be greater than zero.")]
+    ZeroAmount,
+    #[msg("Pool does not belong to this proposal.")]
+    InvalidPool,
+    #[msg("Not enough liquidity in the pool.")]
+    InsufficientLiquidity,
+    #[msg("Slippage limit exceeded.")]
+    SlippageExceeded,
+    #[msg("TWAP window must not be negative.")]
+    InvalidWindow,
+    #[msg("TWAP window is older than the oldest observation.")]
+    OracleWindowTooLong,
+    #[msg("Deadline must be in the future.")]
+    InvalidDeadline,
+    #[msg("Market deadline has not passed yet.")]
+    DeadlineNotReached,
+    #[msg("Market is not open.")]
+    MarketNotOpen,
+    #[msg("Proposal does not belong to this market.")]
+    ProposalMismatch,
+    #[msg("Proposal is younger than the market's TWAP window.")]
+    ProposalTooYoung,
+    #[msg("YES TWAP is not above the acceptance threshold.")]
+    BelowAcceptThreshold,
+    #[msg("Another proposal has a higher or equal YES TWAP.")]
+    NotHighestProposal,
+    #[msg("Every other proposal's YES pool must be supplied exactly once.")]
+    IncompleteProposalSet,
+    #[msg("Signer is not the market resolver.")]
+    InvalidResolver,
+    #[msg("Market has no accepted proposal.")]
+    MarketNotAccepted,
+    #[msg("Market is already resolved.")]
+    MarketAlreadyResolved,
+    #[msg("Market is not resolved.")]
+    MarketNotResolved,
+    #[msg("Proposal is not the market's accepted proposal.")]
+    ProposalNotAccepted,
+    #[msg("Token is not the winning side of the resolved market.")]
+    NotWinningToken,
+    #[msg("Market has not timed out or accepted a proposal.")]
+    MarketNotSettled,
+    #[msg("Claim record does not match the accepted proposal.")]
+    InvalidClaimRecord,
+    #[msg("Amount exceeds the deposit not yet claimed into any proposal.")]
+    ExceedsUnclaimed,
+    #[msg("Proposal still uses the global authority; run migrate_proposal_auth.")]
+    LegacyProposalAuth,
+    #[msg("Proposal authority is already migrated.")]
+    AlreadyMigrated,
+    #[msg("Global state already has the current layout.")]
+    GlobalAlreadyMigrated,
+    #[msg("Market id already matches its address.")]
+    MarketAlreadyMigrated,
+    #[msg("Mint is not the market's reward token.")]
+    InvalidRewardMint,
+    #[msg("Mint does not belong to this proposal.")]
+    InvalidProposalMint,
+    #[msg("Vault does not belong to this market, proposal or pool.")]
+    InvalidVault,
+    #[msg("Token account has the wrong mint or owner.")]
+    InvalidTokenAccount,
+    #[msg("Signer is not the program's upgrade authority.")]
+    NotUpgradeAuthority,
+    #[msg("Signer is not the protocol admin.")]
+    NotAdmin,
+    #[msg("Signer is not the pending protocol admin.")]
+    NotPendingAdmin,
+    #[msg("Fee rate is above the protocol maximum.")]
+    FeeTooHigh,
+    #[msg("Protocol is paused.")]
+    ProtocolPaused,
+    #[msg("Account is not the recipient of these fees.")]
+    InvalidFeeRecipient,
+    #[msg("Market must be resolved or timed out.")]
+    MarketNotFinal,
+    #[msg("Account still holds value owed to users.")]
+    BalanceNotZero,
+    #[msg("Accounts that depend on this one are still open.")]
+    DependentAccountsOpen,
+    #[msg("Account is not the rent payer of this record.")]
+    InvalidRentPayer,
+    #[msg("Proposal data is longer than MAX_DATA bytes.")]
+    ProposalDataTooLong,
+    #[msg("Proposal is not in the market's registry.")]
+    InvalidRegistryEntry,
+    #[msg("Proposal data is not a valid payload.")]
+    InvalidProposalPayload,
+    #[msg("Proposal payload version is not supported.")]
+    UnsupportedPayloadVersion,
+    #[msg("Execution delay must not be negative.")]
+    InvalidExecutionDelay,
+    #[msg("Proposal timelock has not elapsed.")]
+    TimelockActive,
+    #[msg("Proposal has already been executed.")]
+    ProposalAlreadyExecuted,
+    #[msg("Remaining accounts do not match the proposal's instructions.")]
+    ExecutionAccountsMismatch,
+    #[msg("Oracle feed, staleness or confidence limit is invalid.")]
+    InvalidOracleConfig,
+    #[msg("Market is not resolved by an oracle.")]
+    NoOracle,
+    #[msg("Market is resolved by its oracle.")]
+    ResolvedByOracle,
+    #[msg("Price feed account does not have the oracle's layout.")]
+    InvalidOracleAccount,
+    #[msg("Price feed has no current price.")]
+    OraclePriceUnavailable,
+    #[msg("Oracle price is older than the deadline or the staleness limit.")]
+    OraclePriceStale,
+    #[msg("Oracle confidence interval is too wide.")]
+    OracleConfidenceTooWide,
+    #[msg("Minimum deposit is too small to seed both pools.")]
+    MinDepositTooSmall,
+    #[msg("Accepted proposal has not been executed yet.")]
+    ProposalNotExecuted,
+    #[msg("Record already has the current layout.")]
+    RecordAlreadyMigrated,
+    #[msg("Claim records do not add up to the vUSD the proposal's claims minted.")]
+    ClaimRecordsMismatch
 }
\ No newline at end of file
@@ -0,0 +1,284 @@
+//! Events emitted by the handlers in `contexts/`. Each is logged with
+//! `emit!` as a `Program data:` line holding the base64 of the event's
+//! 8-byte discriminator followed by its Borsh-encoded fields, in the order
+//! declared here; the IDL lists the same layouts under `events`/`types`.
+
+use anchor_lang::prelude::*;
+use crate::state::fees::FeeKind;
+use crate::state::oracle::OracleKind;
+use crate::state::pool::SwapDirection;
+use crate::state::proposal::PoolSide;
+
+/// `initialize_global`; the payer, the upgrade authority, becomes admin
+#[event]
+pub struct GlobalInitialized {
+    pub global:     Pubkey,
+    pub payer:      Pubkey,
+}
+
+/// `create_market`
+#[event]
+pub struct MarketCreated {
+    pub market_id:        u64,
+    pub market:           Pubkey,
+    pub creator:          Pubkey,
+    pub reward_mint:      Pubkey,
+    pub resolver:         Pubkey,
+    pub min_deposit:      u64,
+    pub strike_price:     u64,
+    pub accept_threshold: u64,    // YES TWAP to beat, PRICE_SCALE decimals
+    pub twap_window:      i64,    // seconds
+    pub deadline:         i64,
+    pub created_at:       i64,
+    pub title:            String,
+    pub lp_fee_bps:       u16,
+    pub creator_fee_bps:  u16,
+    pub protocol_fee_bps: u16,
+    pub execution_delay:  i64,    // seconds between acceptance and execution
+    pub oracle_kind:      OracleKind,
+    pub oracle_feed:      Pubkey, // default unless resolved by an oracle
+}
+
+/// `deposit_to_market`
+#[event]
+pub struct Deposited {
+    pub market_id:  u64,
+    pub user:       Pubkey,
+    pub amount:     u64,
+    pub total:      u64,          // user's deposit record afterwards
+}
+
+/// `create_proposal`, and `migrate_proposal_auth` for a legacy proposal;
+/// `locked` is the min deposit D taken from the creator
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id:    u64,
+    pub market_id:      u64,
+    pub proposal:       Pubkey,
+    pub creator:        Pubkey,
+    pub vusd_mint:      Pubkey,
+    pub yes_mint:       Pubkey,
+    pub no_mint:        Pubkey,
+    pub yes_pool:       Pubkey,
+    pub no_pool:        Pubkey,
+    pub locked:         u64,
+    pub pool_tokens:    u64,      // YES/NO seeded into each pool, also minted to the creator
+    pub pool_vusd:      u64,      // vUSD seeded into each pool
+    pub created_at:     i64,
+    pub data:           Vec<u8>,
+}
+
+/// `claim_for_proposal`
+#[event]
+pub struct VusdClaimed {
+    pub proposal_id: u64,
+    pub market_id:   u64,
+    pub user:        Pubkey,
+    pub amount:      u64,         // vUSD minted
+}
+
+/// `mint_yes_no`: `amount` vUSD in, `amount` YES and NO out
+#[event]
+pub struct YesNoMinted {
+    pub proposal_id: u64,
+    pub market_id:   u64,
+    pub user:        Pubkey,
+    pub amount:      u64,
+}
+
+/// `redeem_yes_no`: `amount` YES and NO in, `amount` vUSD out
+#[event]
+pub struct YesNoRedeemed {
+    pub proposal_id: u64,
+    pub market_id:   u64,
+    pub user:        Pubkey,
+    pub amount:      u64,
+}
+
+/// `swap_exact_in` / `swap_exact_out`, with the pool state after the trade
+#[event]
+pub struct Swapped {
+    pub proposal_id:   u64,
+    pub market_id:     u64,
+    pub user:          Pubkey,
+    pub side:          PoolSide,
+    pub direction:     SwapDirection,
+    pub amount_in:     u64,
+    pub amount_out:    u64,
+    pub lp_fee:        u64,       // vUSD fees, included in the vUSD leg
+    pub protocol_fee:  u64,
+    pub creator_fee:   u64,
+    pub reserve_token: u64,
+    pub reserve_vusd:  u64,
+    pub price:         u64,       // spot vUSD per token, PRICE_SCALE decimals
+    pub timestamp:     i64,
+}
+
+/// `expire_market`
+#[event]
+pub struct MarketExpired {
+    pub market_id: u64,
+    pub timestamp: i64,
+}
+
+/// `graduate_proposal`
+#[event]
+pub struct ProposalAccepted {
+    pub market_id:   u64,
+    pub proposal_id: u64,
+    pub twap:        u64,         // YES TWAP over the market's window
+    pub timestamp:   i64,
+}
+
+/// `resolve_market`, and `resolve_with_oracle` with the feed as resolver
+#[event]
+pub struct MarketResolved {
+    pub market_id:   u64,
+    pub proposal_id: u64,         // the accepted proposal
+    pub resolver:    Pubkey,
+    pub outcome_yes: bool,
+    pub timestamp:   i64,
+}
+
+/// `redeem_winning`
+#[event]
+pub struct WinningRedeemed {
+    pub market_id:   u64,
+    pub proposal_id: u64,
+    pub user:        Pubkey,
+    pub side:        PoolSide,
+    pub amount:      u64,         // winning tokens burned
+    pub payout:      u64,         // reward tokens paid
+}
+
+/// `collect_creator_fees` / `collect_protocol_fees`
+#[event]
+pub struct FeesCollected {
+    pub market_id:   u64,
+    pub proposal_id: u64,
+    pub kind:        FeeKind,
+    pub recipient:   Pubkey,
+    pub amount:      u64,         // vUSD burned and reward tokens paid
+}
+
+/// `close_deposit_record`
+#[event]
+pub struct DepositRecordClosed {
+    pub market_id:  u64,
+    pub user:       Pubkey,
+    pub rent_payer: Pubkey,
+}
+
+/// `close_claim_record`
+#[event]
+pub struct ClaimRecordClosed {
+    pub market_id:   u64,
+    pub proposal_id: u64,
+    pub user:        Pubkey,
+    pub rent_payer:  Pubkey,
+}
+
+/// `close_proposal`: config, pools and vaults are gone, and `refunded`
+/// reward tokens of the stake went back to the creator
+#[event]
+pub struct ProposalClosed {
+    pub market_id:   u64,
+    pub proposal_id: u64,
+    pub rent_payer:  Pubkey,
+    pub refunded:    u64,
+}
+
+/// `close_market`; `swept` reward tokens went to the fee treasury
+#[event]
+pub struct MarketClosed {
+    pub market_id:  u64,
+    pub rent_payer: Pubkey,
+    pub swept:      u64,
+}
+
+/// `withdraw_deposit`
+#[event]
+pub struct DepositWithdrawn {
+    pub market_id: u64,
+    pub user:      Pubkey,
+    pub amount:    u64,
+    pub remaining: u64,           // user's deposit record afterwards
+}
+
+/// `withdraw_unclaimed`
+#[event]
+pub struct UnclaimedWithdrawn {
+    pub market_id: u64,
+    pub user:      Pubkey,
+    pub amount:    u64,
+    pub remaining: u64,
+}
+
+/// `migrate_proposal_auth`
+#[event]
+pub struct ProposalAuthMigrated {
+    pub proposal_id:   u64,
+    pub proposal_auth: Pubkey,
+    pub vusd_vault:    Pubkey,
+    pub yes_vault:     Pubkey,
+    pub no_vault:      Pubkey,
+}
+
+/// `migrate_global`
+#[event]
+pub struct GlobalMigrated {
+    pub global:           Pubkey,
+    pub admin:            Pubkey,
+    pub next_market_id:   u64,
+    pub next_proposal_id: u64,
+}
+
+/// `migrate_market`
+#[event]
+pub struct MarketMigrated {
+    pub market_id: u64,
+    pub market:    Pubkey,
+}
+
+/// every admin instruction; the protocol configuration afterwards
+#[event]
+pub struct GlobalConfigUpdated {
+    pub admin:               Pubkey,
+    pub pending_admin:       Pubkey,
+    pub fee_treasury:        Pubkey,
+    pub protocol_fee_bps:    u16,
+    pub max_lp_fee_bps:      u16,
+    pub max_creator_fee_bps: u16,
+    pub paused:              bool,
+}
+
+/// `execute_proposal`
+#[event]
+pub struct ProposalExecuted {
+    pub market_id:    u64,
+    pub proposal_id:  u64,
+    pub treasury:     Pubkey,
+    pub instructions: u32,        // CPIs made with the treasury as signer
+    pub executed_at:  i64,
+}
+
+/// `fund_treasury`
+#[event]
+pub struct TreasuryFunded {
+    pub market_id: u64,
+    pub funder:    Pubkey,
+    pub mint:      Pubkey,
+    pub amount:    u64,
+    pub balance:   u64,           // treasury's account of `mint` afterwards
+}
+
+/// `resolve_with_oracle`, before its MarketResolved; PRICE_SCALE decimals
+#[event]
+pub struct OracleSettled {
+    pub market_id:    u64,
+    pub feed:         Pubkey,
+    pub price:        i64,
+    pub confidence:   i64,
+    pub publish_time: i64,
+    pub strike_price: u64,
+}
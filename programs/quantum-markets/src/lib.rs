@@ -1,61 +1,292 @@
+#![allow(unexpected_cfgs)]
+
 use anchor_lang::prelude::*;
 
 mod contexts;
 use contexts::*;
-mod constants;
-mod state;
-mod errors;
+pub mod constants;
+pub mod state;
+pub mod errors;
+pub mod events;
+use state::pool::SwapDirection;
+use state::fees::FeeKind;
+use state::oracle::OracleConfig;
 
 declare_id!("ASnYjL8hE148BWM35vQ85ppjc7rRK5YDLENZhPyW2D7w");
 
-#[program]
-pub mod quantum_markets {
+// `#[program]` expands next to the module it annotates, and the IDL
+// instructions it generates still call the deprecated AccountInfo::realloc
+#[allow(deprecated)]
+mod interface {
     use super::*;
 
-    pub fn initialize_global(ctx: Context<InitializeGlobal>) -> Result<()> {
-        ctx.accounts.handler()
-    }
+    #[program]
+    pub mod quantum_markets {
+        use super::*;
 
-    pub fn create_market(
-        ctx: Context<CreateMarket>,
-        min_deposit: u64,
-        strike_price: u64,
-        title: String,
-    ) -> Result<()> {
-        ctx.accounts.handler(ctx.bumps, min_deposit, strike_price, title)
-    }
+        /// only the program's upgrade authority, who becomes the admin
+        pub fn initialize_global(ctx: Context<InitializeGlobal>) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps)
+        }
 
-    pub fn deposit_to_market(
-        ctx: Context<DepositToMarket>,
-        amount: u64,
-    ) -> Result<()> {
-        ctx.accounts.handler(amount)
-    }
+        /// first step of an admin handover; `Pubkey::default()` cancels it
+        pub fn transfer_admin(
+            ctx: Context<UpdateGlobal>,
+            new_admin: Pubkey,
+        ) -> Result<()> {
+            ctx.accounts.transfer_admin(new_admin)
+        }
 
-    pub fn mint_yes_no(
-        ctx: Context<MintYesNo>,
-        amount: u64,
-    ) -> Result<()> {
-        ctx.accounts.handler(ctx.bumps, amount)
-    }
+        pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+            ctx.accounts.handler()
+        }
 
-    pub fn redeem_yes_no(
-        ctx: Context<RedeemYesNo>,
-        amount: u64,
-    ) -> Result<()> {
-        ctx.accounts.handler(ctx.bumps, amount)
-    }
+        pub fn set_fees(
+            ctx: Context<UpdateGlobal>,
+            protocol_fee_bps: u16,
+            max_lp_fee_bps: u16,
+            max_creator_fee_bps: u16,
+        ) -> Result<()> {
+            ctx.accounts.set_fees(protocol_fee_bps, max_lp_fee_bps, max_creator_fee_bps)
+        }
 
-    pub fn claim_for_proposal(
-        ctx: Context<ClaimForProposal>
-    ) -> Result<()> {
-        ctx.accounts.handler(ctx.bumps)
-    }
+        pub fn set_fee_treasury(
+            ctx: Context<UpdateGlobal>,
+            fee_treasury: Pubkey,
+        ) -> Result<()> {
+            ctx.accounts.set_fee_treasury(fee_treasury)
+        }
+
+        /// while paused every other state-changing instruction fails
+        pub fn set_paused(
+            ctx: Context<UpdateGlobal>,
+            paused: bool,
+        ) -> Result<()> {
+            ctx.accounts.set_paused(paused)
+        }
+
+        /// fee rates must be within the global maximums; the protocol rate is
+        /// the global one at creation. The accepted proposal may be executed
+        /// `execution_delay` seconds after acceptance. With an `oracle` the
+        /// market resolves through `resolve_with_oracle` instead of its resolver.
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_market(
+            ctx: Context<CreateMarket>,
+            min_deposit: u64,
+            strike_price: u64,
+            deadline: i64,
+            accept_threshold: u64,
+            twap_window: i64,
+            title: String,
+            lp_fee_bps: u16,
+            creator_fee_bps: u16,
+            execution_delay: i64,
+            oracle: OracleConfig,
+        ) -> Result<()> {
+            ctx.accounts.handler(
+                ctx.bumps,
+                min_deposit,
+                strike_price,
+                deadline,
+                accept_threshold,
+                twap_window,
+                title,
+                lp_fee_bps,
+                creator_fee_bps,
+                execution_delay,
+                oracle,
+            )
+        }
+
+        pub fn expire_market(ctx: Context<ExpireMarket>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        /// remaining accounts: the YES pool of every other proposal in the market
+        pub fn graduate_proposal(ctx: Context<GraduateProposal>) -> Result<()> {
+            ctx.accounts.handler(ctx.remaining_accounts)
+        }
+
+        /// pays `amount` of any mint into the market treasury, which only
+        /// `execute_proposal` can spend from
+        pub fn fund_treasury(
+            ctx: Context<FundTreasury>,
+            amount: u64,
+        ) -> Result<()> {
+            ctx.accounts.handler(amount)
+        }
+
+        /// runs the accepted proposal's payload once its timelock has passed;
+        /// remaining accounts: for each of its instructions the program, then
+        /// the instruction's accounts in order
+        pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps, ctx.remaining_accounts)
+        }
+
+        pub fn resolve_market(
+            ctx: Context<ResolveMarket>,
+            outcome_yes: bool,
+        ) -> Result<()> {
+            ctx.accounts.handler(outcome_yes)
+        }
+
+        /// permissionless once the deadline passed: YES if the feed's price is
+        /// at least `strike_price`
+        pub fn resolve_with_oracle(ctx: Context<ResolveWithOracle>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        pub fn redeem_winning(
+            ctx: Context<RedeemWinning>,
+            amount: u64,
+        ) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps, amount)
+        }
+
+        /// pays the accepted proposal's creator fees to the market creator
+        pub fn collect_creator_fees(ctx: Context<CollectFees>) -> Result<()> {
+            ctx.accounts.collect(ctx.bumps, FeeKind::Creator)
+        }
+
+        /// pays the accepted proposal's protocol fees to the fee treasury
+        pub fn collect_protocol_fees(ctx: Context<CollectFees>) -> Result<()> {
+            ctx.accounts.collect(ctx.bumps, FeeKind::Protocol)
+        }
+
+        pub fn withdraw_deposit(ctx: Context<WithdrawDeposit>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        pub fn withdraw_unclaimed(
+            ctx: Context<WithdrawUnclaimed>,
+            amount: u64,
+        ) -> Result<()> {
+            ctx.accounts.handler(amount)
+        }
+
+        /// once the market is resolved or timed out and nothing is left to
+        /// withdraw; every close refunds the rent payer stored in the account
+        pub fn close_deposit_record(ctx: Context<CloseDepositRecord>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        /// a claim into the accepted proposal only after the deposit record
+        pub fn close_claim_record(ctx: Context<CloseClaimRecord>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        /// after its claim records; the accepted proposal also after every
+        /// deposit record, winning redemption and fee collection
+        pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps)
+        }
+
+        /// after its proposals and deposit records; leftover reward tokens go to
+        /// the fee treasury
+        pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        /// move a proposal in the original layout onto its own authority and
+        /// the current layout; its market must be migrated first. Remaining
+        /// accounts: each user with a claim record of the proposal, then
+        /// that record
+        pub fn migrate_proposal_auth(
+            ctx: Context<MigrateProposalAuth>,
+            proposal_id: u64,
+        ) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps, proposal_id, ctx.remaining_accounts)
+        }
+
+        /// bring the global account to the current layout; upgrade authority only
+        pub fn migrate_global(ctx: Context<MigrateGlobal>) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps)
+        }
+
+        /// bring a market in the original layout to the current one; admin
+        /// only, setting the terms the old layout lacked
+        pub fn migrate_market(
+            ctx: Context<MigrateMarket>,
+            market_id: u64,
+            deadline: i64,
+            accept_threshold: u64,
+            twap_window: i64,
+        ) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps, market_id, deadline, accept_threshold, twap_window)
+        }
+
+        /// bring a deposit record in the original layout to the current one;
+        /// its market must be migrated first
+        pub fn migrate_deposit_record(ctx: Context<MigrateDepositRecord>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        /// bring a claim record in the original layout to the current one;
+        /// its proposal must be migrated first
+        pub fn migrate_claim_record(ctx: Context<MigrateClaimRecord>) -> Result<()> {
+            ctx.accounts.handler()
+        }
+
+        pub fn deposit_to_market(
+            ctx: Context<DepositToMarket>,
+            amount: u64,
+        ) -> Result<()> {
+            ctx.accounts.handler(amount)
+        }
+
+        pub fn mint_yes_no(
+            ctx: Context<MintYesNo>,
+            amount: u64,
+        ) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps, amount)
+        }
+
+        pub fn redeem_yes_no(
+            ctx: Context<RedeemYesNo>,
+            amount: u64,
+        ) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps, amount)
+        }
+
+        pub fn claim_for_proposal(
+            ctx: Context<ClaimForProposal>
+        ) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps)
+        }
+
+        /// `data` is a `ProposalPayload` as encoded by `ProposalPayload::encode`
+        pub fn create_proposal(
+            ctx: Context<CreateProposal>,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            ctx.accounts.handler(ctx.bumps, data)
+        }
+
+        pub fn swap_exact_in(
+            ctx: Context<Swap>,
+            direction: SwapDirection,
+            amount_in: u64,
+            min_amount_out: u64,
+        ) -> Result<()> {
+            ctx.accounts.exact_in(ctx.bumps, direction, amount_in, min_amount_out)
+        }
+
+        pub fn swap_exact_out(
+            ctx: Context<Swap>,
+            direction: SwapDirection,
+            amount_out: u64,
+            max_amount_in: u64,
+        ) -> Result<()> {
+            ctx.accounts.exact_out(ctx.bumps, direction, amount_out, max_amount_in)
+        }
 
-    pub fn create_proposal(
-        ctx: Context<CreateProposal>,
-        data: Vec<u8>,
-    ) -> Result<()> {
-        ctx.accounts.handler(ctx.bumps, data)
+        /// YES-price TWAP over the last `window` seconds, returned via return data
+        pub fn yes_twap(
+            ctx: Context<ReadTwap>,
+            window: i64,
+        ) -> Result<u64> {
+            ctx.accounts.handler(window)
+        }
     }
 }
+pub use interface::*;
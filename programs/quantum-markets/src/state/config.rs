@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::state::fees::FeeRates;
+use crate::state::oracle::{OracleConfig, OracleKind};
+use crate::state::payload::MarketParameter;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
 pub enum MarketStatus {
@@ -14,14 +17,46 @@ pub enum MarketStatus {
 pub struct MarketConfig {
   pub id:        u64,
   pub created_at: i64,
+  pub deadline:  i64,           // trading ends; Open markets can then time out
   pub min_deposit: u64,
-  pub strike_price: u64,
+  pub strike_price: u64,       // oracle price resolving YES, PRICE_SCALE decimals
+  pub accept_threshold: u64,    // YES TWAP a proposal must beat, PRICE_SCALE decimals
+  pub twap_window: i64,         // seconds of TWAP used to graduate a proposal
+  pub proposal_count: u64,
+  pub accepted_proposal: u64,   // valid once status is ProposalAccepted
+  pub accepted_at: i64,
+  pub resolved_at: i64,
   pub creator:   Pubkey,
   pub market_token: Pubkey,
   pub resolver:  Pubkey,
   pub status:    MarketStatus,  // derive AnchorEnum
   pub title:     String,
   pub bump:      u8,
+  pub lp_fee_bps:       u16,    // swap fee left in the pools
+  pub creator_fee_bps:  u16,    // swap fee collected by `creator`
+  pub protocol_fee_bps: u16,    // global rate when the market was created
+  pub rent_payer:       Pubkey, // refunded when the market is closed
+  pub open_deposits:    u64,    // deposit records not closed yet
+  pub closed_proposals: u64,
+  pub execution_delay:  i64,    // seconds after acceptance before execute_proposal
+  pub oracle:           OracleConfig, // kind None: resolved by `resolver`
+  pub legacy_stake:     u64,    // min_deposit locked by proposals of the original layout
+}
+
+/// MarketConfig as created before the deadline, TWAP, fee and oracle
+/// settings existed. Only `migrate_market` reads it.
+#[derive(AnchorDeserialize)]
+pub struct LegacyMarketConfig {
+  pub id:        u64,           // never written, always 0
+  pub created_at: i64,
+  pub min_deposit: u64,
+  pub strike_price: u64,
+  pub creator:   Pubkey,
+  pub market_token: Pubkey,
+  pub resolver:  Pubkey,
+  pub status:    MarketStatus,
+  pub title:     String,
+  pub bump:      u8,
 }
 
 impl MarketConfig {
@@ -29,37 +64,139 @@ impl MarketConfig {
           DISCRIMINATOR
         + U64_L          // id: u64
         + U64_L          // created_at: i64 (8 bytes)
+        + I64_L          // deadline: i64
         + U64_L          // min_deposit: u64
         + U64_L          // strike_price: u64
+        + U64_L          // accept_threshold: u64
+        + I64_L          // twap_window: i64
+        + U64_L          // proposal_count: u64
+        + U64_L          // accepted_proposal: u64
+        + I64_L          // accepted_at: i64
+        + I64_L          // resolved_at: i64
         + PUBKEY_L       // creator: Pubkey
         + PUBKEY_L       // market_token: Pubkey
         + PUBKEY_L       // resolver: Pubkey
         + U8_L           // status: MarketStatus as a u8
         + STRING_PREFIX + STR_MAX_LEN // title: String
-        + U8_L;          // bump: u8
-    
+        + U8_L           // bump: u8
+        + U16_L * 3      // lp_fee_bps, creator_fee_bps, protocol_fee_bps
+        + PUBKEY_L       // rent_payer: Pubkey
+        + U64_L * 2      // open_deposits, closed_proposals
+        + I64_L          // execution_delay: i64
+        + OracleConfig::SIZE // oracle: OracleConfig
+        + U64_L;         // legacy_stake
+
+    /// account length of a LegacyMarketConfig: its SIZE counted the
+    /// discriminator and create_market added it once more
+    pub const LEGACY_LEN: usize =
+          DISCRIMINATOR * 2
+        + U64_L * 4      // id, created_at, min_deposit, strike_price
+        + PUBKEY_L * 3   // creator, market_token, resolver
+        + U8_L           // status
+        + STRING_PREFIX + STR_MAX_LEN // title
+        + U8_L;          // bump
+
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         &mut self,
         id: u64,
         created_at: i64,
+        deadline: i64,
         min_deposit: u64,
         strike_price: u64,
+        accept_threshold: u64,
+        twap_window: i64,
         creator: Pubkey,
         market_token: Pubkey,
         resolver: Pubkey,
         status: MarketStatus,
         title: String,
-        bump: u8
+        bump: u8,
+        lp_fee_bps: u16,
+        creator_fee_bps: u16,
+        protocol_fee_bps: u16,
+        rent_payer: Pubkey,
+        execution_delay: i64,
+        oracle: OracleConfig,
     ) {
         self.id = id;
         self.created_at = created_at;
+        self.deadline = deadline;
         self.min_deposit = min_deposit;
         self.strike_price = strike_price;
+        self.accept_threshold = accept_threshold;
+        self.twap_window = twap_window;
+        self.proposal_count = 0;
+        self.accepted_proposal = 0;
+        self.accepted_at = 0;
+        self.resolved_at = 0;
         self.creator = creator;
         self.market_token = market_token;
         self.resolver = resolver;
         self.status = status;
         self.title = title;
         self.bump = bump;
+        self.lp_fee_bps = lp_fee_bps;
+        self.creator_fee_bps = creator_fee_bps;
+        self.protocol_fee_bps = protocol_fee_bps;
+        self.rent_payer = rent_payer;
+        self.open_deposits = 0;
+        self.closed_proposals = 0;
+        self.execution_delay = execution_delay;
+        self.oracle = oracle;
+        self.legacy_stake = 0;
+    }
+
+    pub fn fee_rates(&self) -> FeeRates {
+        FeeRates {
+            lp_bps:       self.lp_fee_bps,
+            protocol_bps: self.protocol_fee_bps,
+            creator_bps:  self.creator_fee_bps,
+        }
+    }
+
+    /// an executed ParameterChange; the value was validated against the
+    /// parameter's bounds
+    pub fn set_parameter(&mut self, parameter: MarketParameter, value: u64) {
+        match parameter {
+            MarketParameter::MinDeposit      => self.min_deposit = value,
+            MarketParameter::AcceptThreshold => self.accept_threshold = value,
+            MarketParameter::TwapWindow      => self.twap_window = value as i64,
+            MarketParameter::LpFeeBps        => self.lp_fee_bps = value as u16,
+            MarketParameter::CreatorFeeBps   => self.creator_fee_bps = value as u16,
+        }
+    }
+
+    /// deposits, minting and swaps are allowed
+    pub fn is_active(&self, now: i64) -> bool {
+        match self.status {
+            MarketStatus::Open => now < self.deadline,
+            MarketStatus::ProposalAccepted => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        matches!(self.status, MarketStatus::ResolvedYes | MarketStatus::ResolvedNo)
+    }
+
+    /// resolved or timed out: nothing trades or pays out differently any more,
+    /// so its accounts may be closed
+    pub fn is_final(&self) -> bool {
+        self.is_resolved() || self.status == MarketStatus::Timeout
+    }
+
+    pub fn has_oracle(&self) -> bool {
+        self.oracle.kind != OracleKind::None
+    }
+
+    /// a proposal was accepted, whether or not the market has resolved since
+    pub fn was_accepted(&self) -> bool {
+        self.status == MarketStatus::ProposalAccepted || self.is_resolved()
+    }
+
+    /// new proposals are allowed
+    pub fn accepts_proposals(&self, now: i64) -> bool {
+        self.status == MarketStatus::Open && now < self.deadline
     }
 }
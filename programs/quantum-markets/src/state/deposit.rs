@@ -1,19 +1,34 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
 
 #[account]
 pub struct DepositRecord {
-    pub amount: u64,   // total deposited into this market by this user
+    pub amount: u64,       // total deposited into this market by this user
+    pub max_claimed: u64,  // largest claim into any single proposal
+    pub rent_payer: Pubkey, // refunded when the record is closed
 }
 
 impl DepositRecord {
-    pub const SIZE: usize = 8 /*disc*/ + 8; // just one u64
+    pub const SIZE: usize = 8 /*disc*/ + 8 + 8 + 32;
+
+    /// account length of the original record: `amount` only
+    pub const LEGACY_LEN: usize = DISCRIMINATOR + U64_L;
+
+    /// part of the deposit that no proposal has minted vUSD against
+    pub fn unclaimed(&self) -> u64 {
+        self.amount.saturating_sub(self.max_claimed)
+    }
 }
 
 #[account]
 pub struct ClaimRecord {
     pub claimed: u64,  // how much of that user’s deposit has been claimed into vUSD
+    pub rent_payer: Pubkey, // refunded when the record is closed
 }
 
 impl ClaimRecord {
-    pub const SIZE: usize = 8 /*disc*/ + 8;
+    pub const SIZE: usize = 8 /*disc*/ + 8 + 32;
+
+    /// account length of the original record: `claimed` only
+    pub const LEGACY_LEN: usize = DISCRIMINATOR + U64_L;
 }
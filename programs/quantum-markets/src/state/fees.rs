@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::QuantumError;
+
+/// who a fee is collected for
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FeeKind {
+    Creator,
+    Protocol,
+}
+
+/// A market's swap fee rates, in basis points of a trade's vUSD leg
+#[derive(Copy, Clone, Default)]
+pub struct FeeRates {
+    pub lp_bps:       u16,
+    pub protocol_bps: u16,
+    pub creator_bps:  u16,
+}
+
+/// vUSD one trade pays in fees. The LP share stays in the pool's reserves;
+/// the protocol and creator shares are set aside in the vUSD vault.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct Fees {
+    pub lp:       u64,
+    pub protocol: u64,
+    pub creator:  u64,
+}
+
+impl Fees {
+    pub fn total(&self) -> u64 {
+        self.lp + self.protocol + self.creator
+    }
+
+    /// the part that leaves the pool's reserves
+    pub fn set_aside(&self) -> u64 {
+        self.protocol + self.creator
+    }
+}
+
+impl FeeRates {
+    fn total_bps(&self) -> u64 {
+        self.lp_bps as u64 + self.protocol_bps as u64 + self.creator_bps as u64
+    }
+
+    /// fees charged on `gross` vUSD, each rounded down
+    pub fn on_gross(&self, gross: u64) -> Fees {
+        let cut = |bps: u16| (gross as u128 * bps as u128 / BPS as u128) as u64;
+        Fees {
+            lp:       cut(self.lp_bps),
+            protocol: cut(self.protocol_bps),
+            creator:  cut(self.creator_bps),
+        }
+    }
+
+    /// smallest gross amount that leaves `net` after fees, and those fees;
+    /// rounding goes to the LP share
+    pub fn gross_up(&self, net: u64) -> Result<(u64, Fees)> {
+        let keep = BPS
+            .checked_sub(self.total_bps())
+            .filter(|keep| *keep > 0)
+            .ok_or(QuantumError::FeeTooHigh)?;
+        let gross = (net as u128 * BPS as u128).div_ceil(keep as u128);
+        let gross = u64::try_from(gross).map_err(|_| error!(QuantumError::Overflow))?;
+        let mut fees = self.on_gross(gross);
+        fees.lp = gross - net - fees.set_aside();
+        Ok((gross, fees))
+    }
+}
@@ -1,6 +1,36 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
 
 #[account]
 pub struct GlobalState {
-    pub next_id: u64,
+    pub next_market_id:      u64,
+    pub next_proposal_id:    u64,
+    pub admin:               Pubkey,
+    pub pending_admin:       Pubkey,   // default while no transfer is pending
+    pub fee_treasury:        Pubkey,   // receives protocol fees
+    pub protocol_fee_bps:    u16,      // protocol's cut of every swap
+    pub max_lp_fee_bps:      u16,      // bound on a market's liquidity fee
+    pub max_creator_fee_bps: u16,      // bound on a market's creator fee
+    pub paused:              bool,     // kill switch for every mutating instruction
+    pub bump:                u8,
+}
+
+impl GlobalState {
+    pub const SIZE: usize =
+          U64_L * 2      // next_market_id, next_proposal_id
+        + PUBKEY_L * 3   // admin, pending_admin, fee_treasury
+        + U16_L * 3      // protocol_fee_bps, max_lp_fee_bps, max_creator_fee_bps
+        + U8_L           // paused: bool
+        + U8_L;          // bump: u8
+
+    /// account length before the counters were split: one shared `next_id`
+    pub const LEGACY_LEN: usize = DISCRIMINATOR + U64_L;
+
+    /// account length with split counters, before the admin and protocol
+    /// configuration were added
+    pub const COUNTERS_LEN: usize = DISCRIMINATOR + U64_L * 2;
+
+    pub fn has_pending_admin(&self) -> bool {
+        self.pending_admin != Pubkey::default()
+    }
 }
@@ -1,4 +1,9 @@
 pub mod config;
 pub mod global;
 pub mod proposal;
-pub mod deposit;
\ No newline at end of file
+pub mod deposit;
+pub mod pool;
+pub mod fees;
+pub mod registry;
+pub mod payload;
+pub mod oracle;
\ No newline at end of file
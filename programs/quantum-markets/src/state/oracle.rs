@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::errors::QuantumError;
+
+/// Layout of the price feed `resolve_with_oracle` reads
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum OracleKind {
+    /// no feed; the resolver settles the market with `resolve_market`
+    #[default]
+    None,
+    /// Pyth push price account: exponent, aggregate price, confidence and
+    /// status, and the publish time
+    Pyth,
+    /// Switchboard V2 aggregator: result and standard deviation of the
+    /// latest confirmed round, as decimals
+    Switchboard,
+}
+
+/// A market's price feed and the limits a reading must meet to settle it
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct OracleConfig {
+    pub kind:               OracleKind,
+    pub feed:               Pubkey,
+    pub max_staleness:      i64,    // seconds from publish time to resolution
+    pub max_confidence_bps: u16,    // confidence interval relative to the price
+}
+
+/// One feed reading in PRICE_SCALE decimals
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct OraclePrice {
+    pub price:        i128,
+    pub confidence:   i128,
+    pub publish_time: i64,
+}
+
+/// owner and byte offsets of a Pyth V2 price account
+pub mod pyth {
+    use anchor_lang::prelude::{pubkey, Pubkey};
+
+    /// the Pyth oracle program on mainnet
+    pub const PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+    pub const MAGIC: u32 = 0xa1b2c3d4;
+    pub const VERSION: u32 = 2;
+    pub const PRICE_ACCOUNT: u32 = 3;
+    pub const TRADING: u32 = 1;
+
+    pub const MAGIC_OFFSET: usize = 0;
+    pub const VERSION_OFFSET: usize = 4;
+    pub const TYPE_OFFSET: usize = 8;
+    pub const EXPONENT_OFFSET: usize = 20;       // i32
+    pub const TIMESTAMP_OFFSET: usize = 96;      // i64
+    pub const PRICE_OFFSET: usize = 208;         // i64, aggregate
+    pub const CONFIDENCE_OFFSET: usize = 216;    // u64
+    pub const STATUS_OFFSET: usize = 224;        // u32
+    pub const LEN: usize = 240;
+}
+
+/// owner and byte offsets of a Switchboard V2 AggregatorAccountData, which
+/// is packed; a decimal is an i128 mantissa followed by a u32 scale
+pub mod switchboard {
+    use anchor_lang::prelude::{pubkey, Pubkey};
+
+    /// the Switchboard V2 program on mainnet
+    pub const PROGRAM_ID: Pubkey = pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+
+    pub const SUCCESS_OFFSET: usize = 341;       // u32, oracles in the round
+    pub const TIMESTAMP_OFFSET: usize = 358;     // i64, round opened
+    pub const RESULT_OFFSET: usize = 366;
+    pub const STD_DEVIATION_OFFSET: usize = 386;
+    pub const LEN: usize = 406;
+}
+
+/// the Anchor discriminator of a Switchboard V2 aggregator account
+pub fn switchboard_discriminator() -> [u8; 8] {
+    let mut discriminator = [0; 8];
+    discriminator.copy_from_slice(&hash(b"account:AggregatorAccountData").to_bytes()[..8]);
+    discriminator
+}
+
+fn field<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| error!(QuantumError::InvalidOracleAccount))
+}
+
+/// `mantissa * 10^exponent` in PRICE_SCALE decimals, rounded down
+fn to_price_scale(mantissa: i128, exponent: i32) -> Result<i128> {
+    let shift = exponent
+        .checked_add(PRICE_SCALE.ilog10() as i32)
+        .ok_or(QuantumError::InvalidOracleAccount)?;
+    let factor = 10i128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(QuantumError::InvalidOracleAccount)?;
+    if shift >= 0 {
+        Ok(mantissa.checked_mul(factor).ok_or(QuantumError::Overflow)?)
+    } else {
+        Ok(mantissa.div_euclid(factor))
+    }
+}
+
+impl OracleConfig {
+    pub const SIZE: usize =
+          U8_L           // kind: OracleKind as a u8
+        + PUBKEY_L       // feed: Pubkey
+        + I64_L          // max_staleness: i64
+        + U16_L;         // max_confidence_bps: u16
+
+    pub fn validate(&self) -> Result<()> {
+        if self.kind != OracleKind::None {
+            require!(
+                self.feed != Pubkey::default()
+                    && self.max_staleness > 0
+                    && self.max_confidence_bps as u64 <= BPS,
+                QuantumError::InvalidOracleConfig
+            );
+        }
+        Ok(())
+    }
+
+    /// the reading in `feed`, which must be owned by this config's oracle
+    /// program and have its layout
+    pub fn read(&self, feed: &AccountInfo) -> Result<OraclePrice> {
+        let data = feed.try_borrow_data()?;
+        match self.kind {
+            OracleKind::None => err!(QuantumError::NoOracle),
+            OracleKind::Pyth => {
+                require_keys_eq!(*feed.owner, pyth::PROGRAM_ID, QuantumError::InvalidOracleAccount);
+                require!(
+                    data.len() >= pyth::LEN
+                        && u32::from_le_bytes(field(&data, pyth::MAGIC_OFFSET)?) == pyth::MAGIC
+                        && u32::from_le_bytes(field(&data, pyth::VERSION_OFFSET)?) == pyth::VERSION
+                        && u32::from_le_bytes(field(&data, pyth::TYPE_OFFSET)?) == pyth::PRICE_ACCOUNT,
+                    QuantumError::InvalidOracleAccount
+                );
+                require!(
+                    u32::from_le_bytes(field(&data, pyth::STATUS_OFFSET)?) == pyth::TRADING,
+                    QuantumError::OraclePriceUnavailable
+                );
+                let exponent = i32::from_le_bytes(field(&data, pyth::EXPONENT_OFFSET)?);
+                let price = i64::from_le_bytes(field(&data, pyth::PRICE_OFFSET)?);
+                let confidence = u64::from_le_bytes(field(&data, pyth::CONFIDENCE_OFFSET)?);
+                Ok(OraclePrice {
+                    price:        to_price_scale(price as i128, exponent)?,
+                    confidence:   to_price_scale(confidence as i128, exponent)?,
+                    publish_time: i64::from_le_bytes(field(&data, pyth::TIMESTAMP_OFFSET)?),
+                })
+            }
+            OracleKind::Switchboard => {
+                require_keys_eq!(*feed.owner, switchboard::PROGRAM_ID, QuantumError::InvalidOracleAccount);
+                require!(
+                    data.len() >= switchboard::LEN && data[..DISCRIMINATOR] == switchboard_discriminator(),
+                    QuantumError::InvalidOracleAccount
+                );
+                require!(
+                    u32::from_le_bytes(field(&data, switchboard::SUCCESS_OFFSET)?) > 0,
+                    QuantumError::OraclePriceUnavailable
+                );
+                let decimal = |offset: usize| -> Result<i128> {
+                    let mantissa = i128::from_le_bytes(field(&data, offset)?);
+                    let scale = u32::from_le_bytes(field(&data, offset + 16)?);
+                    let exponent = i32::try_from(scale).map_err(|_| QuantumError::InvalidOracleAccount)?;
+                    to_price_scale(mantissa, -exponent)
+                };
+                Ok(OraclePrice {
+                    price:        decimal(switchboard::RESULT_OFFSET)?,
+                    confidence:   decimal(switchboard::STD_DEVIATION_OFFSET)?,
+                    publish_time: i64::from_le_bytes(field(&data, switchboard::TIMESTAMP_OFFSET)?),
+                })
+            }
+        }
+    }
+}
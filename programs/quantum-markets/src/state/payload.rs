@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{spl_token, ID as TOKEN_PROGRAM_ID};
+use crate::constants::*;
+use crate::errors::QuantumError;
+use crate::state::global::GlobalState;
+
+/// What a proposal would do if accepted. Stored in `ProposalConfig.data`
+/// as a PAYLOAD_VERSION byte followed by the borsh encoding of the enum.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ProposalPayload {
+    /// off-chain description, pinned by the hash of its content
+    Text { uri: String, content_hash: [u8; 32] },
+    /// SPL transfer out of the market treasury into `destination`, a token account
+    Transfer { mint: Pubkey, destination: Pubkey, amount: u64 },
+    /// new value for one of the market's parameters
+    ParameterChange { parameter: MarketParameter, value: u64 },
+    /// instructions run with the market treasury as signer
+    Instructions(Vec<PayloadInstruction>),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MarketParameter {
+    MinDeposit,
+    AcceptThreshold,   // PRICE_SCALE decimals
+    TwapWindow,        // seconds
+    LpFeeBps,
+    CreatorFeeBps,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PayloadInstruction {
+    pub program_id: Pubkey,
+    pub accounts:   Vec<PayloadAccount>,
+    pub data:       Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PayloadAccount {
+    pub pubkey:      Pubkey,
+    pub is_signer:   bool,
+    pub is_writable: bool,
+}
+
+impl ProposalPayload {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = vec![PAYLOAD_VERSION];
+        self.serialize(&mut data).expect("writing to a Vec");
+        data
+    }
+
+    /// the payload in `data`; every byte must be accounted for
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (&version, body) = data.split_first().ok_or(QuantumError::InvalidProposalPayload)?;
+        require!(version == PAYLOAD_VERSION, QuantumError::UnsupportedPayloadVersion);
+        Self::try_from_slice(body).map_err(|_| error!(QuantumError::InvalidProposalPayload))
+    }
+
+    /// executing it changes something on chain
+    pub fn has_action(&self) -> bool {
+        !matches!(self, Self::Text { .. })
+    }
+
+    /// checks that don't need the chain state at execution; `treasury` is
+    /// the only account an instruction may expect to sign
+    pub fn validate(&self, global: &GlobalState, treasury: &Pubkey) -> Result<()> {
+        match self {
+            Self::Text { uri, .. } => require!(!uri.is_empty(), QuantumError::InvalidProposalPayload),
+            Self::Transfer { amount, .. } => require!(*amount > 0, QuantumError::ZeroAmount),
+            Self::ParameterChange { parameter, value } => match parameter {
+                MarketParameter::MinDeposit => {
+                    require!(*value >= MIN_DEPOSIT_FLOOR, QuantumError::MinDepositTooSmall)
+                }
+                MarketParameter::AcceptThreshold => {}
+                MarketParameter::TwapWindow => {
+                    require!(*value > 0 && *value <= i64::MAX as u64, QuantumError::InvalidWindow)
+                }
+                MarketParameter::LpFeeBps => {
+                    require!(*value <= global.max_lp_fee_bps as u64, QuantumError::FeeTooHigh)
+                }
+                MarketParameter::CreatorFeeBps => {
+                    require!(*value <= global.max_creator_fee_bps as u64, QuantumError::FeeTooHigh)
+                }
+            },
+            Self::Instructions(instructions) => {
+                require!(!instructions.is_empty(), QuantumError::InvalidProposalPayload);
+                // no way back into the program that holds the treasury
+                require!(
+                    instructions.iter().all(|ix| ix.program_id != crate::ID),
+                    QuantumError::InvalidProposalPayload
+                );
+                require!(
+                    instructions
+                        .iter()
+                        .flat_map(|ix| &ix.accounts)
+                        .all(|account| !account.is_signer || account.pubkey == *treasury),
+                    QuantumError::InvalidProposalPayload
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// the CPIs `execute_proposal` makes with `treasury` as signer, in order;
+    /// Text and ParameterChange have none. Transfers come out of the
+    /// treasury's associated token account for `mint`.
+    pub fn instructions(&self, treasury: &Pubkey) -> Result<Vec<Instruction>> {
+        Ok(match self {
+            Self::Text { .. } | Self::ParameterChange { .. } => Vec::new(),
+            Self::Transfer { mint, destination, amount } => vec![spl_token::instruction::transfer(
+                &TOKEN_PROGRAM_ID,
+                &get_associated_token_address(treasury, mint),
+                destination,
+                treasury,
+                &[],
+                *amount,
+            )?],
+            Self::Instructions(instructions) => instructions
+                .iter()
+                .map(|ix| Instruction {
+                    program_id: ix.program_id,
+                    accounts: ix
+                        .accounts
+                        .iter()
+                        .map(|a| AccountMeta { pubkey: a.pubkey, is_signer: a.is_signer, is_writable: a.is_writable })
+                        .collect(),
+                    data: ix.data.clone(),
+                })
+                .collect(),
+        })
+    }
+}
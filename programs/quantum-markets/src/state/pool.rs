@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::QuantumError;
+use crate::state::proposal::PoolSide;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
+pub enum SwapDirection {
+    Buy,  // vUSD in, YES/NO out
+    Sell, // YES/NO in, vUSD out
+}
+
+/// Cumulative price at a point in time. Every swap writes one before it
+/// moves the reserves; swaps closer than the market's spacing overwrite the
+/// newest slot, so its interval averages the prices it spans.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Default)]
+pub struct Observation {
+    pub timestamp:        i64,
+    pub price_cumulative: u128,
+}
+
+/// Constant-product pool between one outcome token (YES or NO) and the
+/// proposal's vUSD. Tokens live in the proposal vaults; the pool only
+/// tracks its share of them, since both sides draw on the same vUSD vault.
+#[account]
+pub struct Pool {
+    pub proposal_id:   u64,
+    pub market_id:     u64,
+    pub side:          PoolSide,
+
+    pub token_mint:    Pubkey,
+    pub token_vault:   Pubkey,
+    pub vusd_vault:    Pubkey,
+
+    pub reserve_token: u64,
+    pub reserve_vusd:  u64,
+
+    // TWAP oracle: price * seconds, plus a ring buffer of past values
+    pub price_cumulative:  u128,
+    pub last_update:       i64,
+    pub observation_index: u16,   // slot of the newest observation
+    pub observation_count: u16,   // filled slots, up to OBSERVATIONS
+    pub observations:      [Observation; OBSERVATIONS],
+
+    pub bump:          u8,
+}
+
+impl Pool {
+    pub const SIZE: usize =
+          U64_L * 2          // proposal_id, market_id
+        + U8_L               // side
+        + PUBKEY_L * 3       // token_mint, token_vault, vusd_vault
+        + U64_L * 2          // reserve_token, reserve_vusd
+        + U128_L             // price_cumulative
+        + I64_L              // last_update
+        + U16_L * 2          // observation_index, observation_count
+        + (I64_L + U128_L) * OBSERVATIONS
+        + U8_L;              // bump
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        &mut self,
+        proposal_id: u64,
+        market_id: u64,
+        side: PoolSide,
+        token_mint: Pubkey,
+        token_vault: Pubkey,
+        vusd_vault: Pubkey,
+        reserve_token: u64,
+        reserve_vusd: u64,
+        now: i64,
+        bump: u8,
+    ) {
+        self.proposal_id = proposal_id;
+        self.market_id = market_id;
+        self.side = side;
+        self.token_mint = token_mint;
+        self.token_vault = token_vault;
+        self.vusd_vault = vusd_vault;
+        self.reserve_token = reserve_token;
+        self.reserve_vusd = reserve_vusd;
+        self.price_cumulative = 0;
+        self.last_update = now;
+        self.observation_index = 0;
+        self.observation_count = 1;
+        self.observations[0] = Observation { timestamp: now, price_cumulative: 0 };
+        self.bump = bump;
+    }
+
+    /// (reserve_in, reserve_out) for a trade in `direction`
+    fn reserves(&self, direction: SwapDirection) -> (u64, u64) {
+        match direction {
+            SwapDirection::Buy  => (self.reserve_vusd, self.reserve_token),
+            SwapDirection::Sell => (self.reserve_token, self.reserve_vusd),
+        }
+    }
+
+    /// out = reserve_out * in / (reserve_in + in), rounded down
+    pub fn quote_exact_in(&self, direction: SwapDirection, amount_in: u64) -> Result<u64> {
+        let (r_in, r_out) = self.reserves(direction);
+        let numerator = (r_out as u128)
+            .checked_mul(amount_in as u128)
+            .ok_or(QuantumError::Overflow)?;
+        let denominator = (r_in as u128)
+            .checked_add(amount_in as u128)
+            .ok_or(QuantumError::Overflow)?;
+        require!(denominator > 0, QuantumError::InsufficientLiquidity);
+        Ok((numerator / denominator) as u64)
+    }
+
+    /// in = reserve_in * out / (reserve_out - out), rounded up
+    pub fn quote_exact_out(&self, direction: SwapDirection, amount_out: u64) -> Result<u64> {
+        let (r_in, r_out) = self.reserves(direction);
+        require!(amount_out < r_out, QuantumError::InsufficientLiquidity);
+        let numerator = (r_in as u128)
+            .checked_mul(amount_out as u128)
+            .ok_or(QuantumError::Overflow)?;
+        let denominator = (r_out - amount_out) as u128;
+        let amount_in = numerator.div_ceil(denominator);
+        u64::try_from(amount_in).map_err(|_| error!(QuantumError::Overflow))
+    }
+
+    /// vUSD per token, scaled by PRICE_SCALE
+    pub fn spot_price(&self) -> Result<u64> {
+        require!(self.reserve_token > 0, QuantumError::InsufficientLiquidity);
+        let price = (self.reserve_vusd as u128)
+            .checked_mul(PRICE_SCALE as u128)
+            .ok_or(QuantumError::Overflow)?
+            / self.reserve_token as u128;
+        u64::try_from(price).map_err(|_| error!(QuantumError::Overflow))
+    }
+
+    /// accumulator extrapolated with the current price, for `at` ≥ last_update
+    fn cumulative_at(&self, at: i64) -> Result<u128> {
+        let elapsed = at
+            .checked_sub(self.last_update)
+            .ok_or(QuantumError::Underflow)?;
+        let accrued = (self.spot_price()? as u128)
+            .checked_mul(elapsed.max(0) as u128)
+            .ok_or(QuantumError::Overflow)?;
+        Ok(self.price_cumulative
+            .checked_add(accrued)
+            .ok_or(QuantumError::Overflow)?)
+    }
+
+    /// minimum gap between stored observations for the ring to cover
+    /// `window`: every gap but the newest is a full spacing
+    pub fn observation_spacing(window: i64) -> i64 {
+        let gaps = (OBSERVATIONS - 2) as i64;
+        (window.max(0) + gaps - 1) / gaps
+    }
+
+    /// accrue the current price up to `now` and record an observation;
+    /// a new slot is only taken once the newest one is `spacing` seconds
+    /// after its predecessor, otherwise the newest slot moves to `now`.
+    /// Must run before the reserves change
+    pub fn observe(&mut self, now: i64, spacing: i64) -> Result<()> {
+        if now <= self.last_update {
+            return Ok(()); // same second, nothing has accrued
+        }
+        self.price_cumulative = self.cumulative_at(now)?;
+        self.last_update = now;
+
+        let newest = self.observation_index as usize;
+        let previous = self.observations[(newest + OBSERVATIONS - 1) % OBSERVATIONS];
+        let slot = if self.observation_count > 1
+            && self.observations[newest].timestamp - previous.timestamp < spacing
+        {
+            newest
+        } else {
+            if (self.observation_count as usize) < OBSERVATIONS {
+                self.observation_count += 1;
+            }
+            (newest + 1) % OBSERVATIONS
+        };
+        self.observations[slot] = Observation {
+            timestamp:        now,
+            price_cumulative: self.price_cumulative,
+        };
+        self.observation_index = slot as u16;
+        Ok(())
+    }
+
+    fn oldest_observation(&self) -> Observation {
+        if (self.observation_count as usize) < OBSERVATIONS {
+            self.observations[0]
+        } else {
+            self.observations[(self.observation_index as usize + 1) % OBSERVATIONS]
+        }
+    }
+
+    /// accumulator value at a past `target` covered by the ring buffer
+    fn cumulative_before(&self, target: i64) -> Result<u128> {
+        if target >= self.last_update {
+            return self.cumulative_at(target);
+        }
+        // walk back from the newest observation to the first one at or before target
+        let newest = self.observation_index as usize;
+        let mut later = self.observations[newest];
+        for k in 1..self.observation_count as usize {
+            let obs = self.observations[(newest + OBSERVATIONS - k) % OBSERVATIONS];
+            if obs.timestamp <= target {
+                // the interval's average price stands in for its path
+                let span = (later.timestamp - obs.timestamp) as u128;
+                let delta = later.price_cumulative - obs.price_cumulative;
+                let into = (target - obs.timestamp) as u128;
+                return Ok(obs.price_cumulative + delta * into / span);
+            }
+            later = obs;
+        }
+        Ok(later.price_cumulative)
+    }
+
+    /// time-weighted average price over the last `window` seconds; windows
+    /// reaching past the pool's creation are clamped to its lifetime
+    pub fn twap(&self, now: i64, window: i64) -> Result<u64> {
+        require!(window >= 0, QuantumError::InvalidWindow);
+        let oldest = self.oldest_observation();
+        let mut start = now.checked_sub(window).ok_or(QuantumError::Underflow)?;
+        if start < oldest.timestamp {
+            // once the ring has wrapped, the oldest slot is no longer creation
+            require!(
+                (self.observation_count as usize) < OBSERVATIONS,
+                QuantumError::OracleWindowTooLong
+            );
+            start = oldest.timestamp;
+        }
+        if start >= now {
+            return self.spot_price();
+        }
+        let end_cum = self.cumulative_at(now)?;
+        let start_cum = self.cumulative_before(start)?;
+        let avg = end_cum
+            .checked_sub(start_cum)
+            .ok_or(QuantumError::Underflow)?
+            / (now - start) as u128;
+        u64::try_from(avg).map_err(|_| error!(QuantumError::Overflow))
+    }
+
+    /// book a filled trade against the reserves
+    pub fn apply(&mut self, direction: SwapDirection, amount_in: u64, amount_out: u64) -> Result<()> {
+        let (r_in, r_out) = match direction {
+            SwapDirection::Buy  => (&mut self.reserve_vusd, &mut self.reserve_token),
+            SwapDirection::Sell => (&mut self.reserve_token, &mut self.reserve_vusd),
+        };
+        *r_in = r_in.checked_add(amount_in).ok_or(QuantumError::Overflow)?;
+        *r_out = r_out.checked_sub(amount_out).ok_or(QuantumError::Underflow)?;
+        Ok(())
+    }
+}
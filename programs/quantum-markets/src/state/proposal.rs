@@ -1,21 +1,60 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::state::payload::ProposalPayload;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq)]
 pub enum PoolSide { Yes, No }
 
-#[account]
+/// Zero-copy so the trading paths read it in place instead of
+/// borsh-decoding `data` on every instruction.
+#[account(zero_copy)]
 pub struct ProposalConfig {
     pub id:          u64,
     pub market_id:   u64,
     pub created_at:  i64,
+
+    // swap fees in vUSD held in the vault until collected
+    pub protocol_fees: u64,
+    pub creator_fees:  u64,
+    pub open_claims:   u64,      // claim records not closed yet
+    pub stake:         u64,      // reward tokens locked from the creator's deposit
+
+    pub creator:     Pubkey,
+
+    pub vusd_mint:   Pubkey,
+    pub yes_mint:    Pubkey,
+    pub no_mint:     Pubkey,
+
+    pub yes_pool:    Pubkey,
+    pub no_pool:     Pubkey,
+
+    pub rent_payer:  Pubkey,     // refunded when the proposal is closed
+
+    pub data:        [u8; MAX_DATA],
+    pub data_len:    u16,        // bytes of `data` in use
+    pub bump:        u8,
+
+    // 0 = legacy global `proposal_auth`, PROPOSAL_AUTH_V1 = per-proposal
+    pub auth_version: u8,
+
+    pub registry_index: u32,     // slot in the market's MarketProposals
+
+    pub executed_at: i64,        // 0 until execute_proposal ran
+}
+
+/// ProposalConfig as the original create_proposal wrote it, borsh-encoded
+/// with `data` as a Vec. Only `migrate_proposal_auth` reads it.
+#[derive(AnchorDeserialize)]
+pub struct LegacyProposalConfig {
+    pub id:          u64,
+    pub market_id:   u64,
+    pub created_at:  i64,
     pub creator:     Pubkey,
 
     pub vusd_mint:   Pubkey,
     pub yes_mint:    Pubkey,
     pub no_mint:     Pubkey,
 
-    // pool keys will be written later when pools are created
     pub yes_pool:    Pubkey,
     pub no_pool:     Pubkey,
 
@@ -26,10 +65,45 @@ pub struct ProposalConfig {
 impl ProposalConfig {
    pub const SIZE: usize =
           U64_L * 2          // id, market_id
-        + U64_L              // created_at
-        + PUBKEY_L * 5       // creator + 3 mints + bump-packed in struct above
+        + I64_L              // created_at
+        + U64_L * 2          // protocol_fees, creator_fees
+        + U64_L              // open_claims
+        + U64_L              // stake
+        + PUBKEY_L           // creator
+        + PUBKEY_L * 3       // vusd_mint, yes_mint, no_mint
         + PUBKEY_L * 2       // yes_pool, no_pool
-        + 4                  // Vec length prefix
-        + MAX_DATA           // data bytes
+        + PUBKEY_L           // rent_payer
+        + MAX_DATA           // data
+        + U16_L              // data_len
+        + U8_L               // bump
+        + U8_L               // auth_version
+        + U32_L              // registry_index
+        + I64_L;             // executed_at
+
+    /// account length of a LegacyProposalConfig, whose SIZE counted five
+    /// keys for the creator and the three mints
+    pub const LEGACY_LEN: usize =
+          DISCRIMINATOR
+        + U64_L * 3          // id, market_id, created_at
+        + PUBKEY_L * 5       // creator, vusd_mint, yes_mint, no_mint
+        + PUBKEY_L * 2       // yes_pool, no_pool
+        + STRING_PREFIX      // Vec length prefix
+        + MAX_DATA           // data
         + U8_L;              // bump
+
+    /// the encoded ProposalPayload
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.data_len as usize]
+    }
+
+    pub fn payload(&self) -> Result<ProposalPayload> {
+        ProposalPayload::decode(self.data())
+    }
+
+    /// vUSD in the vault that backs fees rather than outcome tokens
+    pub fn uncollected_fees(&self) -> u64 {
+        self.protocol_fees.saturating_add(self.creator_fees)
+    }
 }
+
+const _: () = assert!(ProposalConfig::SIZE == std::mem::size_of::<ProposalConfig>());
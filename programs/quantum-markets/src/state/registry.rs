@@ -0,0 +1,112 @@
+use std::mem::size_of;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use bytemuck::{from_bytes_mut, try_cast_slice_mut};
+use crate::constants::*;
+use crate::errors::QuantumError;
+use crate::state::proposal::PoolSide;
+
+/// Every proposal of a market in creation order. The header is followed by
+/// `capacity` entry slots of which the first `len` are used; the account is
+/// reallocated REGISTRY_GROWTH slots larger whenever it runs full.
+#[account(zero_copy)]
+pub struct MarketProposals {
+    pub market_id: u64,
+    pub len:       u32,
+    pub capacity:  u32,
+    pub bump:      u8,
+    pub _padding:  [u8; 7],
+}
+
+/// One proposal's mints and the spot prices after its last trade.
+#[zero_copy]
+pub struct ProposalEntry {
+    pub id:         u64,
+    pub yes_price:  u64,      // vUSD per token, PRICE_SCALE decimals
+    pub no_price:   u64,
+    pub updated_at: i64,
+    pub vusd_mint:  Pubkey,
+    pub yes_mint:   Pubkey,
+    pub no_mint:    Pubkey,
+}
+
+impl ProposalEntry {
+    pub const SIZE: usize = size_of::<ProposalEntry>();
+}
+
+impl MarketProposals {
+    /// discriminator and header; the entry slots start here
+    pub const HEADER: usize = DISCRIMINATOR + size_of::<MarketProposals>();
+
+    pub fn space(capacity: u32) -> usize {
+        Self::HEADER + capacity as usize * ProposalEntry::SIZE
+    }
+
+    /// header and every slot of a registry account's data
+    fn split(data: &mut [u8]) -> Result<(&mut MarketProposals, &mut [ProposalEntry])> {
+        let (header, slots) = data[DISCRIMINATOR..].split_at_mut(size_of::<MarketProposals>());
+        let slots = try_cast_slice_mut(slots).map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+        Ok((from_bytes_mut(header), slots))
+    }
+
+    /// append `entry`, growing the account at `payer`'s expense when every
+    /// slot is taken; returns the slot index
+    pub fn push<'info>(
+        registry: &AccountLoader<'info, MarketProposals>,
+        entry: ProposalEntry,
+        payer: &Signer<'info>,
+        system_program: &Program<'info, System>,
+    ) -> Result<u32> {
+        let info = registry.to_account_info();
+        let (len, capacity) = {
+            let header = registry.load()?;
+            (header.len, header.capacity)
+        };
+
+        if len == capacity {
+            let capacity = capacity
+                .checked_add(REGISTRY_GROWTH)
+                .ok_or(QuantumError::Overflow)?;
+            let space = Self::space(capacity);
+            let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+            if shortfall > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.to_account_info(),
+                        Transfer { from: payer.to_account_info(), to: info.clone() },
+                    ),
+                    shortfall,
+                )?;
+            }
+            info.resize(space)?;
+            registry.load_mut()?.capacity = capacity;
+        }
+
+        let mut data = info.try_borrow_mut_data()?;
+        let (header, slots) = Self::split(&mut data)?;
+        slots[len as usize] = entry;
+        header.len = len + 1;
+        Ok(len)
+    }
+
+    /// record the spot price of one side of the proposal in slot `index`
+    pub fn record_price(
+        registry: &AccountLoader<MarketProposals>,
+        index: u32,
+        side: PoolSide,
+        price: u64,
+        now: i64,
+    ) -> Result<()> {
+        let info = registry.to_account_info();
+        let mut data = info.try_borrow_mut_data()?;
+        let (header, slots) = Self::split(&mut data)?;
+        require!(index < header.len, QuantumError::InvalidRegistryEntry);
+        let entry = &mut slots[index as usize];
+        match side {
+            PoolSide::Yes => entry.yes_price = price,
+            PoolSide::No  => entry.no_price = price,
+        }
+        entry.updated_at = now;
+        Ok(())
+    }
+}
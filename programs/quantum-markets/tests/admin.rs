@@ -0,0 +1,109 @@
+//! Protocol admin: handover, fee configuration and the pause switch.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::constants::MAX_FEE_BPS;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::{PoolSide, SwapDirection};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+#[test]
+fn admin_handover_takes_two_steps() {
+    let mut h = Harness::new();
+    h.open_market();
+    let admin = h.admin.insecure_clone();
+    let (alice, bob) = (h.user(), h.user());
+
+    let result = h.send(&alice, &[ix::transfer_admin(&alice.pubkey(), &alice.pubkey())]);
+    assert_error(result, QuantumError::NotAdmin);
+
+    h.send(&admin, &[ix::transfer_admin(&admin.pubkey(), &alice.pubkey())]).unwrap();
+    assert_eq!(h.global().pending_admin, alice.pubkey());
+    // the offer changes nothing until accepted, and only by its recipient
+    assert_eq!(h.global().admin, admin.pubkey());
+    assert_error(h.send(&bob, &[ix::accept_admin(&bob.pubkey())]), QuantumError::NotPendingAdmin);
+
+    h.send(&alice, &[ix::accept_admin(&alice.pubkey())]).unwrap();
+    let global = h.global();
+    assert_eq!(global.admin, alice.pubkey());
+    assert!(!global.has_pending_admin());
+    let result = h.send(&admin, &[ix::set_paused(&admin.pubkey(), true)]);
+    assert_error(result, QuantumError::NotAdmin);
+
+    // a withdrawn offer can't be accepted
+    h.send(&alice, &[ix::transfer_admin(&alice.pubkey(), &bob.pubkey())]).unwrap();
+    h.send(&alice, &[ix::transfer_admin(&alice.pubkey(), &Pubkey::default())]).unwrap();
+    assert_error(h.send(&bob, &[ix::accept_admin(&bob.pubkey())]), QuantumError::NotPendingAdmin);
+}
+
+#[test]
+fn fees_and_treasury_are_set_by_the_admin_within_bounds() {
+    let mut h = Harness::new();
+    h.open_market();
+    let admin = h.admin.insecure_clone();
+    let alice = h.user();
+
+    let result = h.send(&alice, &[ix::set_fees(&alice.pubkey(), 10, 30, 20)]);
+    assert_error(result, QuantumError::NotAdmin);
+    let result = h.send(&admin, &[ix::set_fees(&admin.pubkey(), 10, MAX_FEE_BPS + 1, 20)]);
+    assert_error(result, QuantumError::FeeTooHigh);
+
+    h.send(&admin, &[ix::set_fees(&admin.pubkey(), 10, 30, MAX_FEE_BPS)]).unwrap();
+    let global = h.global();
+    assert_eq!((global.protocol_fee_bps, global.max_lp_fee_bps, global.max_creator_fee_bps), (10, 30, MAX_FEE_BPS));
+
+    let treasury = Pubkey::new_unique();
+    let result = h.send(&alice, &[ix::set_fee_treasury(&alice.pubkey(), &treasury)]);
+    assert_error(result, QuantumError::NotAdmin);
+    h.send(&admin, &[ix::set_fee_treasury(&admin.pubkey(), &treasury)]).unwrap();
+    assert_eq!(h.global().fee_treasury, treasury);
+}
+
+#[test]
+fn pause_stops_every_state_change_until_lifted() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let admin = h.admin.insecure_clone();
+    let alice = h.user();
+    let id = h.propose(&alice, market_id, 1_000);
+    let user = alice.pubkey();
+    let reward_mint = h.reward_mint;
+
+    let result = h.send(&alice, &[ix::set_paused(&user, true)]);
+    assert_error(result, QuantumError::NotAdmin);
+    h.send(&admin, &[ix::set_paused(&admin.pubkey(), true)]).unwrap();
+    assert!(h.global().paused);
+
+    let params = h.market_params();
+    let next_market = h.global().next_market_id;
+    let create = ix::create_market(&admin.pubkey(), &reward_mint, &admin.pubkey(), next_market, &params);
+    assert_error(h.send(&admin, &[create]), QuantumError::ProtocolPaused);
+    let (_, result) = h.create_proposal(&alice, market_id);
+    assert_error(result, QuantumError::ProtocolPaused);
+    for instruction in [
+        ix::deposit_to_market(&user, market_id, &reward_mint, 10),
+        ix::claim_for_proposal(&user, market_id, id),
+        ix::mint_yes_no(&user, market_id, id, 1),
+        ix::redeem_yes_no(&user, market_id, id, 1),
+        ix::swap_exact_in(&user, market_id, id, PoolSide::Yes, SwapDirection::Buy, 1, 0),
+        ix::graduate_proposal(market_id, id, &[]),
+        ix::withdraw_unclaimed(&user, market_id, &reward_mint, 1),
+        ix::withdraw_deposit(&user, market_id, &reward_mint, None),
+        ix::redeem_winning(&user, market_id, &reward_mint, id, PoolSide::Yes, 1),
+        ix::collect_creator_fees(&user, market_id, &reward_mint, id, &admin.pubkey()),
+        ix::expire_market(market_id),
+    ] {
+        assert_error(h.send(&alice, &[instruction]), QuantumError::ProtocolPaused);
+    }
+    let resolve = ix::resolve_market(&admin.pubkey(), market_id, true);
+    assert_error(h.send(&admin, &[resolve]), QuantumError::ProtocolPaused);
+
+    h.send(&admin, &[ix::set_paused(&admin.pubkey(), false)]).unwrap();
+    h.send(&alice, &[ix::mint_yes_no(&user, market_id, id, 1)]).unwrap();
+    h.deposit(&alice, market_id, 10).unwrap();
+}
@@ -0,0 +1,163 @@
+//! Closing records, proposals and markets once a market is final, with the
+//! rent going back to whoever paid it.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{PoolSide, SwapDirection};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn lamports(h: &Harness, address: &Pubkey) -> u64 {
+    h.svm.get_account(address).map_or(0, |account| account.lamports)
+}
+
+/// market with alice's proposal; bob cranks every close so alice's
+/// lamports only move by the rent she gets back
+fn setup() -> (Harness, Keypair, Keypair, u64, ProposalKeys) {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let (alice, bob) = (h.user(), h.user());
+    let p = ProposalKeys::new(h.propose(&alice, market_id, 1_000));
+    (h, alice, bob, market_id, p)
+}
+
+#[test]
+fn accounts_stay_open_until_the_market_is_final() {
+    let (mut h, alice, bob, market_id, p) = setup();
+    let user = alice.pubkey();
+
+    let result = h.send(&bob, &[ix::close_deposit_record(&user, market_id, None, &user)]);
+    assert_error(result, QuantumError::MarketNotFinal);
+    let result = h.send(&bob, &[ix::close_claim_record(&user, market_id, p.id, &user)]);
+    assert_error(result, QuantumError::MarketNotFinal);
+}
+
+#[test]
+fn a_timed_out_market_closes_from_its_records_up() {
+    let (mut h, alice, bob, market_id, p) = setup();
+    let user = alice.pubkey();
+    let reward_mint = h.reward_mint;
+    let admin = h.admin.pubkey();
+    h.warp(DURATION);
+    h.send(&bob, &[ix::expire_market(market_id)]).unwrap();
+
+    // dependents first
+    let close_market = || ix::close_market(&bob.pubkey(), market_id, &reward_mint, &admin, &admin);
+    assert_error(h.send(&bob, &[close_market()]), QuantumError::DependentAccountsOpen);
+    let close_proposal = || ix::close_proposal(market_id, p.id, &reward_mint, &user, &user);
+    assert_error(h.send(&bob, &[close_proposal()]), QuantumError::DependentAccountsOpen);
+
+    // the 400 left of alice's deposit must come out first, to her
+    let close_deposit = |rent_payer: &Pubkey| ix::close_deposit_record(&user, market_id, None, rent_payer);
+    assert_error(h.send(&bob, &[close_deposit(&user)]), QuantumError::BalanceNotZero);
+    h.send(&alice, &[ix::withdraw_deposit(&user, market_id, &reward_mint, None)]).unwrap();
+    assert_error(h.send(&bob, &[close_deposit(&bob.pubkey())]), QuantumError::InvalidRentPayer);
+
+    let deposit = pda::deposit(&pda::market(market_id), &user);
+    let (before, rent) = (lamports(&h, &user), lamports(&h, &deposit));
+    h.send(&bob, &[close_deposit(&user)]).unwrap();
+    assert!(!h.exists(&deposit));
+    assert_eq!(lamports(&h, &user), before + rent);
+    assert_eq!(h.market(market_id).open_deposits, 0);
+
+    h.send(&bob, &[ix::close_claim_record(&user, market_id, p.id, &user)]).unwrap();
+    assert!(!h.exists(&pda::claim(&p.proposal, &user)));
+    assert_eq!(h.proposal(p.id).open_claims, 0);
+
+    let accounts = [p.proposal, p.yes_pool, p.no_pool, p.vusd_vault, p.yes_vault, p.no_vault];
+    let rent: u64 = accounts.iter().map(|a| lamports(&h, a)).sum();
+    let before = lamports(&h, &user);
+    // nothing was accepted, so alice's stake comes back with the proposal
+    let stake_before = h.balance(&pda::ata(&user, &reward_mint));
+    h.send(&bob, &[close_proposal()]).unwrap();
+    assert!(accounts.iter().all(|a| !h.exists(a)));
+    assert_eq!(lamports(&h, &user), before + rent);
+    assert_eq!(h.balance(&pda::ata(&user, &reward_mint)), stake_before + MIN_DEPOSIT);
+    assert_eq!(h.market(market_id).closed_proposals, 1);
+
+    // the vault is empty, so the treasury gets nothing
+    let treasury_before = h.balance(&pda::ata(&admin, &reward_mint));
+    h.send(&bob, &[close_market()]).unwrap();
+    assert!(!h.exists(&pda::market(market_id)));
+    assert!(!h.exists(&pda::ata(&pda::market(market_id), &reward_mint)));
+    assert_eq!(h.balance(&pda::ata(&admin, &reward_mint)), treasury_before);
+}
+
+#[test]
+fn the_accepted_proposal_closes_after_winners_and_deposits() {
+    let (mut h, alice, bob, market_id, p) = setup();
+    let user = alice.pubkey();
+    let reward_mint = h.reward_mint;
+    let admin = h.admin.insecure_clone();
+    let buy = ix::swap_exact_in(&user, market_id, p.id, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(&alice, &[buy]).unwrap();
+    h.warp(TWAP_WINDOW);
+    h.send(&admin, &[ix::graduate_proposal(market_id, p.id, &[])]).unwrap();
+    h.send(&admin, &[ix::resolve_market(&admin.pubkey(), market_id, true)]).unwrap();
+
+    // the claim decides alice's withdrawal while her deposit record exists
+    let close_claim = || ix::close_claim_record(&user, market_id, p.id, &user);
+    assert_error(h.send(&bob, &[close_claim()]), QuantumError::DependentAccountsOpen);
+    // all of her remaining deposit backs the claim, so there is nothing to withdraw
+    h.send(&bob, &[ix::close_deposit_record(&user, market_id, Some(p.id), &user)]).unwrap();
+    h.send(&bob, &[close_claim()]).unwrap();
+
+    // alice's YES are still owed their share of the vUSD
+    let close_proposal = || ix::close_proposal(market_id, p.id, &reward_mint, &user, &user);
+    assert_error(h.send(&bob, &[close_proposal()]), QuantumError::BalanceNotZero);
+    let held = h.balance(&pda::ata(&user, &p.yes_mint));
+    h.send(&alice, &[ix::redeem_winning(&user, market_id, &reward_mint, p.id, PoolSide::Yes, held)]).unwrap();
+
+    h.send(&bob, &[close_proposal()]).unwrap();
+    assert!(!h.exists(&p.proposal));
+    assert_eq!(h.supply(&p.yes_mint), 0);
+    let close_market = ix::close_market(&bob.pubkey(), market_id, &reward_mint, &admin.pubkey(), &admin.pubkey());
+    h.send(&bob, &[close_market]).unwrap();
+    assert!(!h.exists(&pda::market(market_id)));
+}
+
+#[test]
+fn a_refunded_stake_never_backs_claimed_vusd() {
+    let (mut h, alice, bob, market_id, a) = setup();
+    let reward_mint = h.reward_mint;
+    let market_vault = pda::ata(&pda::market(market_id), &reward_mint);
+
+    // bob claims his whole deposit into alice's proposal, then tries to
+    // stake the same funds
+    h.deposit(&bob, market_id, 1_000).unwrap();
+    h.send(&bob, &[ix::claim_for_proposal(&bob.pubkey(), market_id, a.id)]).unwrap();
+    let (_, result) = h.create_proposal(&bob, market_id);
+    assert_error(result, QuantumError::MinDeposit);
+
+    // staking fresh deposit is fine
+    h.deposit(&bob, market_id, MIN_DEPOSIT).unwrap();
+    let (b, result) = h.create_proposal(&bob, market_id);
+    result.unwrap();
+
+    let mint = ix::mint_yes_no(&bob.pubkey(), market_id, a.id, 1_000);
+    let buy = ix::swap_exact_in(&alice.pubkey(), market_id, a.id, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(&bob, &[mint]).unwrap();
+    h.send(&alice, &[buy]).unwrap();
+    h.warp(TWAP_WINDOW);
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::graduate_proposal(market_id, a.id, &[b])]).unwrap();
+    h.send(&admin, &[ix::resolve_market(&admin.pubkey(), market_id, true)]).unwrap();
+
+    // b's stake comes back, and the vault still covers every winning token
+    let before = h.balance(&pda::ata(&bob.pubkey(), &reward_mint));
+    let bob_key = bob.pubkey();
+    h.send(&alice, &[ix::close_proposal(market_id, b, &reward_mint, &bob_key, &bob_key)]).unwrap();
+    assert_eq!(h.balance(&pda::ata(&bob_key, &reward_mint)), before + MIN_DEPOSIT);
+    for user in [&alice, &bob] {
+        let held = h.balance(&pda::ata(&user.pubkey(), &a.yes_mint));
+        let redeem = ix::redeem_winning(&user.pubkey(), market_id, &reward_mint, a.id, PoolSide::Yes, held);
+        h.send(user, &[redeem]).unwrap();
+    }
+    assert!(h.balance(&market_vault) >= h.balance(&a.vusd_vault));
+}
@@ -0,0 +1,282 @@
+//! In-process SVM harness for the integration tests. It loads the compiled
+//! program from `target/deploy/quantum_markets.so` (`anchor build`), or from
+//! `$SBF_OUT_DIR` when set. The suites only build with the `svm-tests`
+//! feature, and fail when the program has not been compiled.
+
+#![allow(dead_code, clippy::result_large_err)]
+
+use std::path::PathBuf;
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use litesvm::types::TransactionResult;
+use litesvm::LiteSVM;
+use quantum_markets::constants::PRICE_SCALE;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions::{self as ix, CreateMarketParams};
+use quantum_markets_client::svm::deploy_upgradeable;
+use quantum_markets_client::{
+    decode, pda, GlobalState, MarketConfig, MarketProposals, OracleConfig, Pool, ProposalConfig, ProposalEntry,
+    ProposalPayload,
+};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_system_interface::instruction as system_instruction;
+
+/// clock at the start of every test
+pub const START: i64 = 1_700_000_000;
+pub const MIN_DEPOSIT: u64 = 600;       // D: 200 YES + 200 NO + 200 vUSD
+pub const TWAP_WINDOW: i64 = 3_600;
+pub const DURATION: i64 = 86_400;
+/// price the pools open at (100 vUSD / 200 tokens); a proposal must beat it
+pub const ACCEPT_THRESHOLD: u64 = PRICE_SCALE / 2;
+/// reward tokens every test user starts with
+pub const USER_FUNDS: u64 = 1_000_000;
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    /// upgrade authority and protocol admin; pays for setup, owns the reward
+    /// mint and resolves markets
+    pub admin: Keypair,
+    pub reward_mint: Pubkey,
+}
+
+fn program_path() -> PathBuf {
+    match std::env::var_os("SBF_OUT_DIR") {
+        Some(dir) => PathBuf::from(dir).join("quantum_markets.so"),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy/quantum_markets.so"),
+    }
+}
+
+impl Harness {
+    /// fresh SVM at `START` with the program and a reward mint
+    pub fn new() -> Self {
+        let path = program_path();
+        let elf = std::fs::read(&path)
+            .unwrap_or_else(|e| panic!("{}: {e}; run `anchor build` first", path.display()));
+        let mut svm = LiteSVM::new();
+        let admin = Keypair::new();
+        deploy_upgradeable(&mut svm, &elf, &admin.pubkey()).expect("load program");
+
+        let mut clock: Clock = svm.get_sysvar();
+        clock.unix_timestamp = START;
+        svm.set_sysvar(&clock);
+
+        svm.airdrop(&admin.pubkey(), 100 * LAMPORTS_PER_SOL).expect("airdrop");
+
+        let mut h = Self { svm, admin, reward_mint: Pubkey::default() };
+        h.reward_mint = h.create_mint();
+        h
+    }
+
+    /// send `instructions` paid and signed by `signer`
+    pub fn send(&mut self, signer: &Keypair, instructions: &[Instruction]) -> TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            instructions, Some(&signer.pubkey()), &[signer], self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        // repeated identical transactions must not be rejected as duplicates
+        self.svm.expire_blockhash();
+        result
+    }
+
+    /// new 6-decimal mint with `admin` as the mint authority
+    pub fn create_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let admin = self.admin.pubkey();
+        let space = spl_token::state::Mint::LEN;
+        let rent = self.svm.minimum_balance_for_rent_exemption(space);
+        let create = [
+            system_instruction::create_account(&admin, &mint.pubkey(), rent, space as u64, &spl_token::ID),
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &admin, None, 6).unwrap(),
+        ];
+        let tx = Transaction::new_signed_with_payer(
+            &create, Some(&admin), &[&self.admin, &mint], self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx).expect("create mint");
+        mint.pubkey()
+    }
+
+    /// mint `amount` of an admin-owned `mint` to `owner`'s associated account
+    pub fn fund(&mut self, owner: &Pubkey, mint: &Pubkey, amount: u64) {
+        let admin = self.admin.pubkey();
+        let account = pda::ata(owner, mint);
+        let fund = [
+            create_associated_token_account_idempotent(&admin, owner, mint, &spl_token::ID),
+            spl_token::instruction::mint_to(&spl_token::ID, mint, &account, &admin, &[], amount).unwrap(),
+        ];
+        let admin_kp = self.admin.insecure_clone();
+        self.send(&admin_kp, &fund).expect("fund account");
+    }
+
+    /// funded account holding `USER_FUNDS` reward tokens
+    pub fn user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        self.svm.airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL).expect("airdrop");
+        let reward_mint = self.reward_mint;
+        self.fund(&user.pubkey(), &reward_mint, USER_FUNDS);
+        user
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.unix_timestamp += seconds;
+        self.svm.set_sysvar(&clock);
+    }
+
+    pub fn warp_to(&mut self, timestamp: i64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.unix_timestamp = timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self.svm.get_account(address).expect("account exists");
+        decode::decode(&account.data).expect("decode account")
+    }
+
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.svm.get_account(address).is_some_and(|a| a.lamports > 0)
+    }
+
+    /// token account balance, zero if the account does not exist
+    pub fn balance(&self, token_account: &Pubkey) -> u64 {
+        match self.svm.get_account(token_account) {
+            Some(account) if !account.data.is_empty() => {
+                spl_token::state::Account::unpack(&account.data).unwrap().amount
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn supply(&self, mint: &Pubkey) -> u64 {
+        let account = self.svm.get_account(mint).expect("mint exists");
+        spl_token::state::Mint::unpack(&account.data).unwrap().supply
+    }
+
+    pub fn global(&self) -> GlobalState {
+        decode::global(&self.svm.get_account(&pda::global()).unwrap().data).unwrap()
+    }
+
+    pub fn market(&self, id: u64) -> MarketConfig {
+        self.account(&pda::market(id))
+    }
+
+    pub fn proposal(&self, id: u64) -> ProposalConfig {
+        decode::proposal(&self.svm.get_account(&pda::proposal(id)).unwrap().data).unwrap()
+    }
+
+    pub fn registry(&self, market_id: u64) -> (MarketProposals, Vec<ProposalEntry>) {
+        let address = pda::market_proposals(&pda::market(market_id));
+        decode::market_proposals(&self.svm.get_account(&address).unwrap().data).unwrap()
+    }
+
+    pub fn pool(&self, address: &Pubkey) -> Pool {
+        self.account(address)
+    }
+
+    pub fn market_params(&self) -> CreateMarketParams {
+        CreateMarketParams {
+            min_deposit: MIN_DEPOSIT,
+            strike_price: PRICE_SCALE,
+            deadline: self.now() + DURATION,
+            accept_threshold: ACCEPT_THRESHOLD,
+            twap_window: TWAP_WINDOW,
+            title: "test market".to_string(),
+            lp_fee_bps: 0,
+            creator_fee_bps: 0,
+            execution_delay: 0,
+            oracle: OracleConfig::default(),
+        }
+    }
+
+    /// global state with the admin in charge, if not there yet
+    pub fn initialize_global(&mut self) {
+        let admin = self.admin.insecure_clone();
+        if !self.exists(&pda::global()) {
+            self.send(&admin, &[ix::initialize_global(&admin.pubkey())]).expect("initialize_global");
+        }
+    }
+
+    /// global state plus one market with the default parameters
+    pub fn open_market(&mut self) -> u64 {
+        let params = self.market_params();
+        self.open_market_with(&params)
+    }
+
+    /// global state plus one market created by the admin, who also resolves it
+    pub fn open_market_with(&mut self, params: &CreateMarketParams) -> u64 {
+        self.initialize_global();
+        let admin = self.admin.insecure_clone();
+        let id = self.global().next_market_id;
+        self.send(
+            &admin,
+            &[ix::create_market(&admin.pubkey(), &self.reward_mint, &admin.pubkey(), id, params)],
+        )
+        .expect("create_market");
+        id
+    }
+
+    pub fn deposit(&mut self, user: &Keypair, market_id: u64, amount: u64) -> TransactionResult {
+        let reward_mint = self.reward_mint;
+        self.send(user, &[ix::deposit_to_market(&user.pubkey(), market_id, &reward_mint, amount)])
+    }
+
+    /// proposal from `user`'s deposit, returning the id it gets
+    pub fn create_proposal(&mut self, user: &Keypair, market_id: u64) -> (u64, TransactionResult) {
+        self.create_proposal_with(user, market_id, payload())
+    }
+
+    pub fn create_proposal_with(&mut self, user: &Keypair, market_id: u64, data: Vec<u8>) -> (u64, TransactionResult) {
+        let id = self.global().next_proposal_id;
+        let reward_mint = self.reward_mint;
+        let result = self.send(
+            user,
+            &[ix::create_proposal(&user.pubkey(), market_id, &reward_mint, id, data)],
+        );
+        (id, result)
+    }
+
+    /// deposit `amount`, propose, and claim the rest of the deposit as vUSD
+    pub fn propose(&mut self, user: &Keypair, market_id: u64, amount: u64) -> u64 {
+        self.propose_with(user, market_id, amount, payload())
+    }
+
+    pub fn propose_with(&mut self, user: &Keypair, market_id: u64, amount: u64, data: Vec<u8>) -> u64 {
+        self.deposit(user, market_id, amount).expect("deposit_to_market");
+        let (id, result) = self.create_proposal_with(user, market_id, data);
+        result.expect("create_proposal");
+        self.send(user, &[ix::claim_for_proposal(&user.pubkey(), market_id, id)])
+            .expect("claim_for_proposal");
+        id
+    }
+}
+
+/// the payload of every proposal the harness creates
+pub fn payload() -> Vec<u8> {
+    ProposalPayload::Text { uri: "ipfs://proposal".into(), content_hash: [0; 32] }.encode()
+}
+
+/// assert that the transaction failed with `expected`
+pub fn assert_error(result: TransactionResult, expected: QuantumError) {
+    let expected = u32::from(expected);
+    match result {
+        Ok(_) => panic!("expected error {expected}, transaction succeeded"),
+        Err(failed) => match failed.err {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                assert_eq!(code, expected, "logs: {:#?}", failed.meta.logs)
+            }
+            other => panic!("expected error {expected}, got {other:?}; logs: {:#?}", failed.meta.logs),
+        },
+    }
+}
@@ -0,0 +1,66 @@
+//! Events decoded from transaction logs.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets_client::events::{parse_logs, QuantumEvent};
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{PoolSide, SwapDirection};
+use solana_sdk::signature::Signer;
+
+#[test]
+fn handlers_emit_decodable_events() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::initialize_global(&admin.pubkey())]).unwrap();
+
+    let params = h.market_params();
+    let meta = h
+        .send(&admin, &[ix::create_market(&admin.pubkey(), &h.reward_mint, &admin.pubkey(), 0, &params)])
+        .unwrap();
+    let events = parse_logs(&meta.logs);
+    assert_eq!(events.len(), 1);
+    let QuantumEvent::MarketCreated(created) = &events[0] else { panic!("expected MarketCreated") };
+    assert_eq!(created.market, pda::market(0));
+    assert_eq!(created.reward_mint, h.reward_mint);
+    assert_eq!(created.deadline, params.deadline);
+    assert_eq!(created.created_at, START);
+    assert_eq!(created.title, params.title);
+
+    let alice = h.user();
+    let meta = h.deposit(&alice, 0, 1_000).unwrap();
+    let [QuantumEvent::Deposited(deposited)] = &parse_logs(&meta.logs)[..] else { panic!("expected Deposited") };
+    assert_eq!((deposited.user, deposited.amount, deposited.total), (alice.pubkey(), 1_000, 1_000));
+
+    let (id, result) = h.create_proposal(&alice, 0);
+    let meta = result.unwrap();
+    let [QuantumEvent::ProposalCreated(proposal)] = &parse_logs(&meta.logs)[..] else {
+        panic!("expected ProposalCreated")
+    };
+    let keys = ProposalKeys::new(id);
+    assert_eq!(proposal.proposal_id, id);
+    assert_eq!((proposal.yes_pool, proposal.no_pool), (keys.yes_pool, keys.no_pool));
+    assert_eq!((proposal.locked, proposal.pool_tokens, proposal.pool_vusd), (MIN_DEPOSIT, 200, 100));
+
+    let meta = h.send(&alice, &[ix::claim_for_proposal(&alice.pubkey(), 0, id)]).unwrap();
+    let [QuantumEvent::VusdClaimed(claimed)] = &parse_logs(&meta.logs)[..] else { panic!("expected VusdClaimed") };
+    assert_eq!(claimed.amount, 400);
+
+    h.warp(5);
+    let meta = h
+        .send(&alice, &[ix::swap_exact_in(&alice.pubkey(), 0, id, PoolSide::Yes, SwapDirection::Buy, 50, 0)])
+        .unwrap();
+    let [QuantumEvent::Swapped(swap)] = &parse_logs(&meta.logs)[..] else { panic!("expected Swapped") };
+    assert!(swap.side == PoolSide::Yes && swap.direction == SwapDirection::Buy);
+    assert_eq!((swap.amount_in, swap.amount_out), (50, 66));
+    assert_eq!((swap.reserve_token, swap.reserve_vusd), (134, 150));
+    assert_eq!(swap.timestamp, START + 5);
+
+    let meta = h.send(&alice, &[ix::mint_yes_no(&alice.pubkey(), 0, id, 10)]).unwrap();
+    assert!(matches!(&parse_logs(&meta.logs)[..], [QuantumEvent::YesNoMinted(e)] if e.amount == 10));
+    let meta = h.send(&alice, &[ix::redeem_yes_no(&alice.pubkey(), 0, id, 10)]).unwrap();
+    assert!(matches!(&parse_logs(&meta.logs)[..], [QuantumEvent::YesNoRedeemed(e)] if e.amount == 10));
+}
@@ -0,0 +1,165 @@
+//! Executing the accepted proposal: only after the market's timelock, only
+//! once, and only with the accounts its payload names, with the market
+//! treasury signing the CPIs.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use anchor_spl::token::spl_token;
+use common::*;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions::{self as ix, CreateMarketParams};
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{
+    MarketParameter, PayloadAccount, PayloadInstruction, PoolSide, ProposalPayload, SwapDirection,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// market with `execution_delay` in which alice's proposal carrying
+/// `payload` has been accepted; returns the market and proposal ids
+fn accepted(h: &mut Harness, alice: &Keypair, execution_delay: i64, payload: &ProposalPayload) -> (u64, u64) {
+    let params = CreateMarketParams { execution_delay, ..h.market_params() };
+    let market_id = h.open_market_with(&params);
+    let id = h.propose_with(alice, market_id, 1_000, payload.encode());
+    let buy = ix::swap_exact_in(&alice.pubkey(), market_id, id, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(alice, &[buy]).unwrap();
+    h.warp(TWAP_WINDOW);
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::graduate_proposal(market_id, id, &[])]).unwrap();
+    (market_id, id)
+}
+
+/// the treasury of the next market the harness opens
+fn next_treasury(h: &mut Harness) -> Pubkey {
+    h.initialize_global();
+    pda::treasury(&pda::market(h.global().next_market_id))
+}
+
+/// 1_000 of `mint` paid into the market treasury by `funder`
+fn fund_treasury(h: &mut Harness, funder: &Keypair, market_id: u64, mint: &Pubkey) {
+    h.fund(&funder.pubkey(), mint, 1_000);
+    h.send(funder, &[ix::fund_treasury(&funder.pubkey(), market_id, mint, 1_000)]).unwrap();
+}
+
+#[test]
+fn parameter_changes_wait_for_the_timelock_and_apply_once() {
+    let mut h = Harness::new();
+    let alice = h.user();
+    let change = ProposalPayload::ParameterChange { parameter: MarketParameter::MinDeposit, value: 900 };
+    let (market_id, id) = accepted(&mut h, &alice, 3_600, &change);
+    let execute = || ix::execute_proposal(market_id, id, &change);
+
+    assert_error(h.send(&alice, &[execute()]), QuantumError::TimelockActive);
+    h.warp(3_600);
+    h.send(&alice, &[execute()]).unwrap();
+    assert_eq!(h.market(market_id).min_deposit, 900);
+    assert_eq!(h.proposal(id).executed_at, h.now());
+
+    assert_error(h.send(&alice, &[execute()]), QuantumError::ProposalAlreadyExecuted);
+}
+
+#[test]
+fn only_the_accepted_proposal_executes() {
+    let mut h = Harness::new();
+    let (alice, bob) = (h.user(), h.user());
+    let market_id = h.open_market();
+    let first = h.propose(&alice, market_id, 1_000);
+    let second = h.propose(&bob, market_id, 1_000);
+    let text = ProposalPayload::decode(&payload()).unwrap();
+
+    let buy = ix::swap_exact_in(&alice.pubkey(), market_id, first, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(&alice, &[buy]).unwrap();
+    h.warp(TWAP_WINDOW);
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::graduate_proposal(market_id, first, &[second])]).unwrap();
+
+    let result = h.send(&bob, &[ix::execute_proposal(market_id, second, &text)]);
+    assert_error(result, QuantumError::ProposalNotAccepted);
+    // a text proposal has nothing to run but is still marked executed
+    h.send(&bob, &[ix::execute_proposal(market_id, first, &text)]).unwrap();
+    assert_ne!(h.proposal(first).executed_at, 0);
+}
+
+#[test]
+fn transfers_pay_out_of_the_treasury() {
+    let mut h = Harness::new();
+    let (alice, bob) = (h.user(), Pubkey::new_unique());
+    let mint = h.create_mint();
+    let treasury = next_treasury(&mut h);
+    let destination = pda::ata(&bob, &mint);
+    let transfer = ProposalPayload::Transfer { mint, destination, amount: 400 };
+    let (market_id, id) = accepted(&mut h, &alice, 0, &transfer);
+
+    fund_treasury(&mut h, &alice, market_id, &mint);
+    h.fund(&bob, &mint, 0);
+    h.send(&alice, &[ix::execute_proposal(market_id, id, &transfer)]).unwrap();
+    assert_eq!(h.balance(&pda::ata(&treasury, &mint)), 600);
+    assert_eq!(h.balance(&destination), 400);
+}
+
+#[test]
+fn instructions_need_exactly_their_accounts() {
+    let mut h = Harness::new();
+    let (alice, bob) = (h.user(), Pubkey::new_unique());
+    let mint = h.create_mint();
+    let treasury = next_treasury(&mut h);
+    let (source, destination) = (pda::ata(&treasury, &mint), pda::ata(&bob, &mint));
+    let transfer = spl_token::instruction::transfer(&spl_token::ID, &source, &destination, &treasury, &[], 250).unwrap();
+    let instructions = ProposalPayload::Instructions(vec![PayloadInstruction {
+        program_id: transfer.program_id,
+        accounts: transfer
+            .accounts
+            .iter()
+            .map(|meta| PayloadAccount { pubkey: meta.pubkey, is_signer: meta.is_signer, is_writable: meta.is_writable })
+            .collect(),
+        data: transfer.data,
+    }]);
+    let (market_id, id) = accepted(&mut h, &alice, 0, &instructions);
+    fund_treasury(&mut h, &alice, market_id, &mint);
+    h.fund(&bob, &mint, 0);
+
+    let execute = || ix::execute_proposal(market_id, id, &instructions);
+    // 4 named accounts, then the token program, source, destination, treasury
+    let mut missing = execute();
+    missing.accounts.truncate(6);
+    let mut swapped = execute();
+    swapped.accounts.swap(5, 6);
+    let mut readonly = execute();
+    readonly.accounts[6].is_writable = false;
+    let mut extra = execute();
+    extra.accounts.push(extra.accounts[4].clone());
+    for bad in [missing, swapped, readonly, extra] {
+        assert_error(h.send(&alice, &[bad]), QuantumError::ExecutionAccountsMismatch);
+    }
+
+    h.send(&alice, &[execute()]).unwrap();
+    assert_eq!(h.balance(&source), 750);
+    assert_eq!(h.balance(&destination), 250);
+}
+
+#[test]
+fn the_accepted_proposal_closes_only_once_executed() {
+    let mut h = Harness::new();
+    let alice = h.user();
+    let user = alice.pubkey();
+    let change = ProposalPayload::ParameterChange { parameter: MarketParameter::TwapWindow, value: 60 };
+    let (market_id, id) = accepted(&mut h, &alice, 0, &change);
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::resolve_market(&admin.pubkey(), market_id, true)]).unwrap();
+
+    // everything else that keeps it open is settled
+    let p = ProposalKeys::new(id);
+    h.send(&alice, &[ix::close_deposit_record(&user, market_id, Some(id), &user)]).unwrap();
+    h.send(&alice, &[ix::close_claim_record(&user, market_id, id, &user)]).unwrap();
+    let held = h.balance(&pda::ata(&user, &p.yes_mint));
+    let reward_mint = h.reward_mint;
+    h.send(&alice, &[ix::redeem_winning(&user, market_id, &reward_mint, id, PoolSide::Yes, held)]).unwrap();
+
+    let close = || ix::close_proposal(market_id, id, &reward_mint, &user, &user);
+    assert_error(h.send(&alice, &[close()]), QuantumError::ProposalNotExecuted);
+    h.send(&alice, &[ix::execute_proposal(market_id, id, &change)]).unwrap();
+    h.send(&alice, &[close()]).unwrap();
+    assert!(!h.exists(&p.proposal));
+}
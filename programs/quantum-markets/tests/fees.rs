@@ -0,0 +1,135 @@
+//! Swap fees: rates bounded by the global configuration, split between the
+//! pool, the protocol and the market creator, and collected after resolution.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{PoolSide, SwapDirection};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const PROTOCOL_FEE_BPS: u16 = 500;
+const LP_FEE_BPS: u16 = 300;
+const CREATOR_FEE_BPS: u16 = 200;
+
+/// global fees configured, and a market charging the maximum rates
+fn fee_market(h: &mut Harness) -> u64 {
+    h.initialize_global();
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::set_fees(&admin.pubkey(), PROTOCOL_FEE_BPS, LP_FEE_BPS, CREATOR_FEE_BPS)])
+        .unwrap();
+    let mut params = h.market_params();
+    params.lp_fee_bps = LP_FEE_BPS;
+    params.creator_fee_bps = CREATOR_FEE_BPS;
+    h.open_market_with(&params)
+}
+
+/// fee market with alice's proposal, after she bought YES for 50 vUSD
+fn setup() -> (Harness, Keypair, u64, ProposalKeys) {
+    let mut h = Harness::new();
+    let market_id = fee_market(&mut h);
+    let alice = h.user();
+    let p = ProposalKeys::new(h.propose(&alice, market_id, 1_000));
+    let buy = ix::swap_exact_in(&alice.pubkey(), market_id, p.id, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(&alice, &[buy]).unwrap();
+    (h, alice, market_id, p)
+}
+
+#[test]
+fn market_fee_rates_stay_within_the_global_bounds() {
+    let mut h = Harness::new();
+    let market_id = fee_market(&mut h);
+    let admin = h.admin.insecure_clone();
+    let reward_mint = h.reward_mint;
+
+    let market = h.market(market_id);
+    assert_eq!(
+        (market.lp_fee_bps, market.creator_fee_bps, market.protocol_fee_bps),
+        (LP_FEE_BPS, CREATOR_FEE_BPS, PROTOCOL_FEE_BPS),
+    );
+
+    for (lp, creator) in [(LP_FEE_BPS + 1, 0), (0, CREATOR_FEE_BPS + 1)] {
+        let mut params = h.market_params();
+        params.lp_fee_bps = lp;
+        params.creator_fee_bps = creator;
+        let id = h.global().next_market_id;
+        let create = ix::create_market(&admin.pubkey(), &reward_mint, &admin.pubkey(), id, &params);
+        assert_error(h.send(&admin, &[create]), QuantumError::FeeTooHigh);
+    }
+
+    // later changes to the protocol rate leave existing markets alone
+    h.send(&admin, &[ix::set_fees(&admin.pubkey(), 0, LP_FEE_BPS, CREATOR_FEE_BPS)]).unwrap();
+    assert_eq!(h.market(market_id).protocol_fee_bps, PROTOCOL_FEE_BPS);
+}
+
+#[test]
+fn swaps_split_fees_between_pool_protocol_and_creator() {
+    let (mut h, alice, market_id, p) = setup();
+    let user = alice.pubkey();
+
+    // 50 vUSD in pays 1 LP, 2 protocol and 1 creator fee; 46 buys
+    // 200 * 46 / 146 = 63 YES, and the LP fee stays in the pool
+    let pool = h.pool(&p.yes_pool);
+    assert_eq!((pool.reserve_token, pool.reserve_vusd), (137, 147));
+    assert_eq!(h.balance(&pda::ata(&user, &p.yes_mint)), 263);
+    assert_eq!(h.balance(&p.vusd_vault), 650);
+    let proposal = h.proposal(p.id);
+    assert_eq!((proposal.protocol_fees, proposal.creator_fees), (2, 1));
+
+    // 20 vUSD out of a sell needs 23 gross (1 protocol, 2 LP after rounding),
+    // which costs ceil(200 * 23 / 77) = 60 NO
+    let sell = |max_in| ix::swap_exact_out(&user, market_id, p.id, PoolSide::No, SwapDirection::Sell, 20, max_in);
+    assert_error(h.send(&alice, &[sell(59)]), QuantumError::SlippageExceeded);
+    h.send(&alice, &[sell(60)]).unwrap();
+
+    let pool = h.pool(&p.no_pool);
+    assert_eq!((pool.reserve_token, pool.reserve_vusd), (260, 79));
+    assert_eq!(h.balance(&pda::ata(&user, &p.vusd_mint)), 370);
+    assert_eq!(h.balance(&p.vusd_vault), 630);
+    let proposal = h.proposal(p.id);
+    assert_eq!((proposal.protocol_fees, proposal.creator_fees), (3, 1));
+}
+
+#[test]
+fn fees_are_collected_in_the_reward_token_after_resolution() {
+    let (mut h, alice, market_id, p) = setup();
+    let admin = h.admin.insecure_clone();
+    let user = alice.pubkey();
+    let reward_mint = h.reward_mint;
+    let treasury = Pubkey::new_unique();
+    h.send(&admin, &[ix::set_fee_treasury(&admin.pubkey(), &treasury)]).unwrap();
+    let creator_fees = |to: &Pubkey| ix::collect_creator_fees(&user, market_id, &reward_mint, p.id, to);
+    let protocol_fees = |to: &Pubkey| ix::collect_protocol_fees(&user, market_id, &reward_mint, p.id, to);
+
+    h.warp(TWAP_WINDOW);
+    h.send(&admin, &[ix::graduate_proposal(market_id, p.id, &[])]).unwrap();
+    assert_error(h.send(&alice, &[creator_fees(&admin.pubkey())]), QuantumError::MarketNotResolved);
+    h.send(&admin, &[ix::resolve_market(&admin.pubkey(), market_id, true)]).unwrap();
+
+    // 647 vUSD besides the fees back the 400 YES, so 263 of them pay 263
+    h.send(&alice, &[ix::redeem_winning(&user, market_id, &reward_mint, p.id, PoolSide::Yes, 263)]).unwrap();
+    assert_eq!(h.balance(&p.vusd_vault), 650 - 263);
+
+    assert_error(h.send(&alice, &[creator_fees(&user)]), QuantumError::InvalidFeeRecipient);
+    assert_error(h.send(&alice, &[protocol_fees(&admin.pubkey())]), QuantumError::InvalidFeeRecipient);
+
+    let market_vault = pda::ata(&pda::market(market_id), &reward_mint);
+    let vault_before = h.balance(&market_vault);
+    let creator_before = h.balance(&pda::ata(&admin.pubkey(), &reward_mint));
+    h.send(&alice, &[creator_fees(&admin.pubkey())]).unwrap();
+    h.send(&alice, &[protocol_fees(&treasury)]).unwrap();
+
+    assert_eq!(h.balance(&pda::ata(&admin.pubkey(), &reward_mint)), creator_before + 1);
+    assert_eq!(h.balance(&pda::ata(&treasury, &reward_mint)), 2);
+    assert_eq!(h.balance(&market_vault), vault_before - 3);
+    assert_eq!(h.balance(&p.vusd_vault), 650 - 263 - 3);
+    let proposal = h.proposal(p.id);
+    assert_eq!((proposal.protocol_fees, proposal.creator_fees), (0, 0));
+
+    assert_error(h.send(&alice, &[creator_fees(&admin.pubkey())]), QuantumError::NothingToClaim);
+}
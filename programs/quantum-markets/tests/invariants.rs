@@ -0,0 +1,155 @@
+//! Random sequences of deposit / create_proposal / claim / mint / redeem /
+//! swap, checking after every step that conditional tokens stay backed:
+//! each proposal's vaults hold exactly the pools' reserves, the fees set
+//! aside and one vUSD per YES/NO pair, wherever the pair's tokens are.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{DepositRecord, PoolSide, SwapDirection};
+use solana_sdk::signature::{Keypair, Signer};
+
+const USERS: usize = 3;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Deposit { user: usize, amount: u64 },
+    CreateProposal { user: usize },
+    Claim { user: usize, proposal: usize },
+    MintYesNo { user: usize, proposal: usize, amount: u64 },
+    RedeemYesNo { user: usize, proposal: usize, amount: u64 },
+    Swap { user: usize, proposal: usize, yes: bool, buy: bool, amount: u64 },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let user = 0..USERS;
+    let proposal = any::<usize>();
+    prop_oneof![
+        (user.clone(), 1..2 * MIN_DEPOSIT).prop_map(|(user, amount)| Op::Deposit { user, amount }),
+        user.clone().prop_map(|user| Op::CreateProposal { user }),
+        (user.clone(), proposal).prop_map(|(user, proposal)| Op::Claim { user, proposal }),
+        (user.clone(), proposal, 1..500u64)
+            .prop_map(|(user, proposal, amount)| Op::MintYesNo { user, proposal, amount }),
+        (user.clone(), proposal, 1..500u64)
+            .prop_map(|(user, proposal, amount)| Op::RedeemYesNo { user, proposal, amount }),
+        (user, proposal, any::<bool>(), any::<bool>(), 1..200u64)
+            .prop_map(|(user, proposal, yes, buy, amount)| Op::Swap { user, proposal, yes, buy, amount }),
+    ]
+}
+
+struct World {
+    h: Harness,
+    market_id: u64,
+    users: Vec<Keypair>,
+    proposals: Vec<u64>,
+}
+
+impl World {
+    fn new() -> Self {
+        let mut h = Harness::new();
+        let market_id = h.open_market();
+        let users = (0..USERS).map(|_| h.user()).collect();
+        Self { h, market_id, users, proposals: Vec::new() }
+    }
+
+    /// run one step; steps the program rejects are part of the sequence too
+    fn apply(&mut self, op: &Op) {
+        let market_id = self.market_id;
+        let pick = |proposals: &[u64], i: usize| (!proposals.is_empty()).then(|| proposals[i % proposals.len()]);
+        match *op {
+            Op::Deposit { user, amount } => {
+                let user = self.users[user].insecure_clone();
+                let _ = self.h.deposit(&user, market_id, amount);
+            }
+            Op::CreateProposal { user } => {
+                let user = self.users[user].insecure_clone();
+                let (id, result) = self.h.create_proposal(&user, market_id);
+                if result.is_ok() {
+                    self.proposals.push(id);
+                }
+            }
+            Op::Claim { user, proposal } => {
+                let Some(id) = pick(&self.proposals, proposal) else { return };
+                let user = self.users[user].insecure_clone();
+                let _ = self.h.send(&user, &[ix::claim_for_proposal(&user.pubkey(), market_id, id)]);
+            }
+            Op::MintYesNo { user, proposal, amount } => {
+                let Some(id) = pick(&self.proposals, proposal) else { return };
+                let user = self.users[user].insecure_clone();
+                let _ = self.h.send(&user, &[ix::mint_yes_no(&user.pubkey(), market_id, id, amount)]);
+            }
+            Op::RedeemYesNo { user, proposal, amount } => {
+                let Some(id) = pick(&self.proposals, proposal) else { return };
+                let user = self.users[user].insecure_clone();
+                let _ = self.h.send(&user, &[ix::redeem_yes_no(&user.pubkey(), market_id, id, amount)]);
+            }
+            Op::Swap { user, proposal, yes, buy, amount } => {
+                let Some(id) = pick(&self.proposals, proposal) else { return };
+                let user = self.users[user].insecure_clone();
+                let side = if yes { PoolSide::Yes } else { PoolSide::No };
+                let direction = if buy { SwapDirection::Buy } else { SwapDirection::Sell };
+                let swap = ix::swap_exact_in(&user.pubkey(), market_id, id, side, direction, amount, 0);
+                let _ = self.h.send(&user, &[swap]);
+            }
+        }
+    }
+
+    fn check(&self, step: usize) -> Result<(), TestCaseError> {
+        let h = &self.h;
+        let market = pda::market(self.market_id);
+
+        let deposits: u64 = self
+            .users
+            .iter()
+            .map(|user| pda::deposit(&market, &user.pubkey()))
+            .filter(|record| h.exists(record))
+            .map(|record| h.account::<DepositRecord>(&record).amount)
+            .sum();
+        let vault = h.balance(&pda::ata(&market, &h.reward_mint));
+        prop_assert!(vault >= deposits, "step {step}: market vault {vault} < deposits {deposits}");
+
+        for &id in &self.proposals {
+            let p = ProposalKeys::new(id);
+            let backing = h.balance(&p.vusd_vault);
+            prop_assert_eq!(
+                h.supply(&p.yes_mint), h.supply(&p.no_mint),
+                "step {}: proposal {} YES and NO supply diverged", step, id
+            );
+
+            // each pool's inventory is all of its token vault
+            let (yes_pool, no_pool) = (h.pool(&p.yes_pool), h.pool(&p.no_pool));
+            prop_assert_eq!(yes_pool.reserve_token, h.balance(&p.yes_vault), "step {}: proposal {} YES pool", step, id);
+            prop_assert_eq!(no_pool.reserve_token, h.balance(&p.no_vault), "step {}: proposal {} NO pool", step, id);
+            // and the vUSD vault holds the pools' reserves, the fees set
+            // aside, and one vUSD per YES/NO pair
+            let accounted = yes_pool.reserve_vusd
+                + no_pool.reserve_vusd
+                + h.proposal(id).uncollected_fees()
+                + h.supply(&p.yes_mint);
+            prop_assert_eq!(backing, accounted, "step {}: proposal {} vUSD vault", step, id);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn conditional_tokens_stay_backed() {
+    let mut runner = TestRunner::new(Config { cases: 32, ..Config::default() });
+    runner
+        .run(&vec(op(), 1..40), |ops| {
+            let mut world = World::new();
+            for (step, op) in ops.iter().enumerate() {
+                world.apply(op);
+                world.check(step)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+}
@@ -0,0 +1,268 @@
+//! Market setup, deposits, proposals, claims and refunds.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::constants::MIN_DEPOSIT_FLOOR;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{ClaimRecord, DepositRecord, MarketStatus};
+use solana_sdk::signature::Signer;
+
+#[test]
+fn initialize_global_starts_the_counter_once() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+
+    // only the upgrade authority may initialize
+    let mallory = h.user();
+    let result = h.send(&mallory, &[ix::initialize_global(&mallory.pubkey())]);
+    assert_error(result, QuantumError::NotUpgradeAuthority);
+
+    h.send(&admin, &[ix::initialize_global(&admin.pubkey())]).unwrap();
+    let global = h.global();
+    assert_eq!((global.next_market_id, global.next_proposal_id), (0, 0));
+    assert_eq!((global.admin, global.fee_treasury), (admin.pubkey(), admin.pubkey()));
+    assert!(!global.paused);
+
+    h.warp(1);
+    assert!(h.send(&admin, &[ix::initialize_global(&admin.pubkey())]).is_err());
+}
+
+#[test]
+fn create_market_records_parameters_and_clock() {
+    let mut h = Harness::new();
+    h.warp(42);
+    let id = h.open_market();
+
+    let market = h.market(id);
+    assert_eq!(market.created_at, START + 42);
+    assert_eq!(market.deadline, START + 42 + DURATION);
+    assert_eq!(market.min_deposit, MIN_DEPOSIT);
+    assert_eq!(market.accept_threshold, ACCEPT_THRESHOLD);
+    assert_eq!(market.twap_window, TWAP_WINDOW);
+    assert_eq!(market.market_token, h.reward_mint);
+    assert_eq!(market.creator, h.admin.pubkey());
+    assert_eq!(market.resolver, h.admin.pubkey());
+    assert_eq!(market.proposal_count, 0);
+    assert!(market.status == MarketStatus::Open);
+    assert_eq!(h.global().next_market_id, id + 1);
+}
+
+#[test]
+fn later_markets_store_their_id_and_take_deposits() {
+    let mut h = Harness::new();
+    h.open_market();
+    let id = h.open_market();
+    assert_eq!(id, 1);
+    assert_eq!(h.market(id).id, id);
+
+    let alice = h.user();
+    h.deposit(&alice, id, 1_000).unwrap();
+    let (proposal_id, result) = h.create_proposal(&alice, id);
+    result.unwrap();
+    // proposals count separately from markets
+    assert_eq!(proposal_id, 0);
+    assert_eq!(h.proposal(proposal_id).market_id, id);
+}
+
+#[test]
+fn create_market_rejects_a_past_deadline_a_negative_delay_and_a_tiny_deposit() {
+    let mut h = Harness::new();
+    h.open_market();
+    let admin = h.admin.insecure_clone();
+    let mut params = h.market_params();
+    params.deadline = h.now();
+
+    let id = h.global().next_market_id;
+    let result = h.send(
+        &admin,
+        &[ix::create_market(&admin.pubkey(), &h.reward_mint, &admin.pubkey(), id, &params)],
+    );
+    assert_error(result, QuantumError::InvalidDeadline);
+
+    params.deadline = h.now() + DURATION;
+    params.execution_delay = -1;
+    let result = h.send(
+        &admin,
+        &[ix::create_market(&admin.pubkey(), &h.reward_mint, &admin.pubkey(), id, &params)],
+    );
+    assert_error(result, QuantumError::InvalidExecutionDelay);
+
+    // too small to leave either pool a vUSD reserve
+    params.execution_delay = 0;
+    params.min_deposit = MIN_DEPOSIT_FLOOR - 1;
+    let result = h.send(
+        &admin,
+        &[ix::create_market(&admin.pubkey(), &h.reward_mint, &admin.pubkey(), id, &params)],
+    );
+    assert_error(result, QuantumError::MinDepositTooSmall);
+}
+
+#[test]
+fn deposits_accumulate_in_the_record_and_vault() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+
+    h.deposit(&alice, market_id, 300).unwrap();
+    h.deposit(&alice, market_id, 200).unwrap();
+
+    let market = pda::market(market_id);
+    let record: DepositRecord = h.account(&pda::deposit(&market, &alice.pubkey()));
+    assert_eq!(record.amount, 500);
+    assert_eq!(record.max_claimed, 0);
+    assert_eq!(h.balance(&pda::ata(&market, &h.reward_mint)), 500);
+    assert_eq!(h.balance(&pda::ata(&alice.pubkey(), &h.reward_mint)), USER_FUNDS - 500);
+}
+
+#[test]
+fn deposit_after_the_deadline_fails_with_market_closed() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+
+    h.warp(DURATION);
+    assert_error(h.deposit(&alice, market_id, 100), QuantumError::MarketClosed);
+}
+
+#[test]
+fn create_proposal_below_min_deposit_fails() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+
+    h.deposit(&alice, market_id, MIN_DEPOSIT - 1).unwrap();
+    let (_, result) = h.create_proposal(&alice, market_id);
+    assert_error(result, QuantumError::MinDeposit);
+}
+
+#[test]
+fn create_proposal_seeds_pools_at_creation_time() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    h.deposit(&alice, market_id, 1_000).unwrap();
+
+    h.warp(77);
+    let (id, result) = h.create_proposal(&alice, market_id);
+    result.unwrap();
+
+    let keys = ProposalKeys::new(id);
+    let proposal = h.proposal(id);
+    assert_eq!(proposal.id, id);
+    assert_eq!(proposal.created_at, START + 77);
+    assert_eq!(proposal.creator, alice.pubkey());
+    assert_eq!(proposal.yes_pool, keys.yes_pool);
+    assert_eq!(proposal.no_pool, keys.no_pool);
+
+    // D = 600: 200 YES + 200 NO to the caller, 200 of each in the vaults
+    // against 100 vUSD per pool, and 400 vUSD backing the 400 pairs
+    assert_eq!(h.balance(&pda::ata(&alice.pubkey(), &keys.yes_mint)), 200);
+    assert_eq!(h.balance(&pda::ata(&alice.pubkey(), &keys.no_mint)), 200);
+    assert_eq!(h.balance(&keys.yes_vault), 200);
+    assert_eq!(h.balance(&keys.no_vault), 200);
+    assert_eq!(h.balance(&keys.vusd_vault), MIN_DEPOSIT);
+    for address in [keys.yes_pool, keys.no_pool] {
+        let pool = h.pool(&address);
+        assert_eq!((pool.reserve_token, pool.reserve_vusd), (200, 100));
+        assert_eq!(pool.last_update, proposal.created_at);
+        assert_eq!(pool.observations[0].timestamp, proposal.created_at);
+    }
+
+    let market = pda::market(market_id);
+    let record: DepositRecord = h.account(&pda::deposit(&market, &alice.pubkey()));
+    assert_eq!(record.amount, 1_000 - MIN_DEPOSIT);
+    assert_eq!(h.market(market_id).proposal_count, 1);
+    assert_eq!(h.global().next_proposal_id, id + 1);
+}
+
+#[test]
+fn create_proposal_after_the_deadline_fails_with_market_closed() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    h.deposit(&alice, market_id, 1_000).unwrap();
+
+    h.warp(DURATION);
+    let (_, result) = h.create_proposal(&alice, market_id);
+    assert_error(result, QuantumError::MarketClosed);
+}
+
+#[test]
+fn claim_mints_the_deposit_as_vusd_once() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let id = h.propose(&alice, market_id, 1_000);
+
+    let keys = ProposalKeys::new(id);
+    assert_eq!(h.balance(&pda::ata(&alice.pubkey(), &keys.vusd_mint)), 400);
+    let claim: ClaimRecord = h.account(&pda::claim(&keys.proposal, &alice.pubkey()));
+    assert_eq!(claim.claimed, 400);
+    let record: DepositRecord = h.account(&pda::deposit(&pda::market(market_id), &alice.pubkey()));
+    assert_eq!(record.max_claimed, 400);
+
+    let result = h.send(&alice, &[ix::claim_for_proposal(&alice.pubkey(), market_id, id)]);
+    assert_error(result, QuantumError::NothingToClaim);
+}
+
+#[test]
+fn withdraw_unclaimed_is_limited_to_the_unclaimed_part() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    h.propose(&alice, market_id, 1_000);
+    h.deposit(&alice, market_id, 300).unwrap();
+
+    // 700 on record, 400 of it claimed
+    let reward_mint = h.reward_mint;
+    let result = h.send(&alice, &[ix::withdraw_unclaimed(&alice.pubkey(), market_id, &reward_mint, 301)]);
+    assert_error(result, QuantumError::ExceedsUnclaimed);
+
+    h.send(&alice, &[ix::withdraw_unclaimed(&alice.pubkey(), market_id, &reward_mint, 300)]).unwrap();
+    let record: DepositRecord = h.account(&pda::deposit(&pda::market(market_id), &alice.pubkey()));
+    assert_eq!(record.amount, 400);
+    assert_eq!(h.balance(&pda::ata(&alice.pubkey(), &reward_mint)), USER_FUNDS - 1_000);
+}
+
+#[test]
+fn expire_market_only_after_the_deadline() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let admin = h.admin.insecure_clone();
+
+    h.warp(DURATION - 1);
+    assert_error(h.send(&admin, &[ix::expire_market(market_id)]), QuantumError::DeadlineNotReached);
+
+    h.warp(1);
+    h.send(&admin, &[ix::expire_market(market_id)]).unwrap();
+    assert!(h.market(market_id).status == MarketStatus::Timeout);
+
+    h.warp(1);
+    assert_error(h.send(&admin, &[ix::expire_market(market_id)]), QuantumError::MarketNotOpen);
+}
+
+#[test]
+fn withdraw_deposit_refunds_everything_after_a_timeout() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let reward_mint = h.reward_mint;
+    h.propose(&alice, market_id, 1_000);
+
+    let withdraw = || ix::withdraw_deposit(&alice.pubkey(), market_id, &reward_mint, None);
+    assert_error(h.send(&alice, &[withdraw()]), QuantumError::MarketNotSettled);
+
+    h.warp(DURATION);
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::expire_market(market_id)]).unwrap();
+    h.send(&alice, &[withdraw()]).unwrap();
+
+    // the D locked by create_proposal stays with the market
+    assert_eq!(h.balance(&pda::ata(&alice.pubkey(), &reward_mint)), USER_FUNDS - MIN_DEPOSIT);
+    assert_error(h.send(&alice, &[withdraw()]), QuantumError::NothingToClaim);
+}
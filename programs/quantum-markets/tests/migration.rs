@@ -0,0 +1,381 @@
+//! Migrations run against accounts written in their legacy layouts:
+//! `migrate_proposal_auth` on a borsh-encoded, pool-less proposal under the
+//! shared `proposal_auth` PDA, `migrate_global` on the single `next_id` counter and on split
+//! counters without the admin configuration, `migrate_market` on a
+//! market written byte for byte in the original layout, and the record
+//! migrations on deposit and claim records holding a single amount.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use anchor_lang::{AccountSerialize, Discriminator};
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::solana_program::program_option::COption;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use common::*;
+use quantum_markets::constants::{MARKET_SEED, PRICE_SCALE, PROPOSAL_AUTH_V1, PROPOSAL_SEED};
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::events::{parse_logs, QuantumEvent};
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{
+    ClaimRecord, DepositRecord, GlobalState, MarketConfig, MarketStatus, PoolSide, ProposalConfig, SwapDirection,
+    PROGRAM_ID,
+};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// rent-exempt account holding `data`
+fn put(h: &mut Harness, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+    let lamports = h.svm.minimum_balance_for_rent_exemption(data.len());
+    h.svm.set_account(address, Account { lamports, data, owner, executable: false, rent_epoch: 0 }).unwrap();
+}
+
+/// initialized SPL mint
+fn mint_account(h: &mut Harness, address: Pubkey, authority: Pubkey, supply: u64, decimals: u8) {
+    let state = spl_token::state::Mint {
+        mint_authority: COption::Some(authority),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint::pack(state, &mut data).unwrap();
+    put(h, address, spl_token::ID, data);
+}
+
+/// `owner`'s associated account of `mint` holding `amount`
+fn token_account(h: &mut Harness, mint: Pubkey, owner: Pubkey, amount: u64) {
+    let state = spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account::pack(state, &mut data).unwrap();
+    put(h, pda::ata(&owner, &mint), spl_token::ID, data);
+}
+
+/// proposal `id` as the original create_proposal left it: borsh-encoded,
+/// without pools, its mints under the shared `proposal_auth` PDA and D/3
+/// each of vUSD, YES and NO in that PDA's vaults
+fn legacy_proposal(h: &mut Harness, market_id: u64, id: u64, creator: &Pubkey) -> ProposalKeys {
+    let p = ProposalKeys::new(id);
+    let legacy = pda::legacy_proposal_auth();
+    let third = MIN_DEPOSIT / 3;
+
+    for (mint, decimals, supply) in [(p.vusd_mint, 6, third), (p.yes_mint, 0, 2 * third), (p.no_mint, 0, 2 * third)] {
+        mint_account(h, mint, legacy, supply, decimals);
+
+        // the creator got the other D/3 YES and NO
+        let holders: &[Pubkey] = if mint == p.vusd_mint { &[legacy] } else { &[legacy, *creator] };
+        for owner in holders {
+            token_account(h, mint, *owner, third);
+        }
+    }
+
+    let (_, bump) = Pubkey::find_program_address(&[PROPOSAL_SEED, &id.to_le_bytes()], &PROGRAM_ID);
+    let data = payload();
+    let mut bytes = [
+        ProposalConfig::DISCRIMINATOR,
+        &id.to_le_bytes(),
+        &market_id.to_le_bytes(),
+        &START.to_le_bytes(),
+        creator.as_ref(),
+        p.vusd_mint.as_ref(),
+        p.yes_mint.as_ref(),
+        p.no_mint.as_ref(),
+        Pubkey::default().as_ref(), // yes_pool
+        Pubkey::default().as_ref(), // no_pool
+        &(data.len() as u32).to_le_bytes(),
+        &data,
+        &[bump],
+    ]
+    .concat();
+    bytes.resize(ProposalConfig::LEGACY_LEN, 0);
+    put(h, p.proposal, PROGRAM_ID, bytes);
+    p
+}
+
+#[test]
+fn migrate_moves_a_legacy_proposal_to_its_own_authority() {
+    let mut h = Harness::new();
+    h.initialize_global();
+    let admin = h.admin.insecure_clone();
+    let market_id = 1;
+    legacy_market(&mut h, market_id, &admin.pubkey());
+    let deadline = h.now() + DURATION;
+    let migrate = ix::migrate_market(&admin.pubkey(), market_id, deadline, ACCEPT_THRESHOLD, TWAP_WINDOW);
+    h.send(&admin, &[migrate]).unwrap();
+
+    let alice = h.user();
+    let id = h.global().next_proposal_id;
+    let p = legacy_proposal(&mut h, market_id, id, &alice.pubkey());
+
+    // legacy proposals are unreadable until migrated
+    h.deposit(&alice, market_id, 1_000).unwrap();
+    let result = h.send(&alice, &[ix::claim_for_proposal(&alice.pubkey(), market_id, id)]);
+    assert!(result.is_err());
+
+    let meta = h.send(&alice, &[ix::migrate_proposal_auth(&alice.pubkey(), market_id, id, &[])]).unwrap();
+
+    // indexed like a new proposal from here on
+    let [QuantumEvent::ProposalCreated(created), QuantumEvent::ProposalAuthMigrated(_)] = &parse_logs(&meta.logs)[..]
+    else {
+        panic!("expected ProposalCreated and ProposalAuthMigrated")
+    };
+    assert_eq!((created.proposal_id, created.market_id, created.creator), (id, market_id, alice.pubkey()));
+    assert_eq!((created.locked, created.pool_vusd, created.created_at), (MIN_DEPOSIT, MIN_DEPOSIT / 6, START));
+
+    let proposal = h.proposal(id);
+    assert_eq!(proposal.auth_version, PROPOSAL_AUTH_V1);
+    assert_eq!((proposal.id, proposal.market_id, proposal.rent_payer), (id, market_id, alice.pubkey()));
+    assert_eq!(proposal.data(), &payload()[..]);
+    assert_eq!((proposal.yes_pool, proposal.no_pool), (p.yes_pool, p.no_pool));
+    assert_eq!(h.market(market_id).proposal_count, 1);
+    let (_, entries) = h.registry(market_id);
+    assert_eq!((entries.len(), entries[0].id, proposal.registry_index), (1, id, 0));
+    for mint in [p.vusd_mint, p.yes_mint, p.no_mint] {
+        let state = spl_token::state::Mint::unpack(&h.svm.get_account(&mint).unwrap().data).unwrap();
+        assert_eq!(state.mint_authority, COption::Some(p.proposal_auth));
+    }
+    let third = MIN_DEPOSIT / 3;
+    // the pools' 200 vUSD plus one for each of the 400 pairs
+    assert_eq!([h.balance(&p.vusd_vault), h.balance(&p.yes_vault), h.balance(&p.no_vault)], [MIN_DEPOSIT, third, third]);
+
+    let legacy = pda::legacy_proposal_auth();
+    for mint in [p.vusd_mint, p.yes_mint, p.no_mint] {
+        assert!(!h.exists(&pda::ata(&legacy, &mint)));
+    }
+    // pools over the vault inventory with create_proposal's vUSD split
+    for (address, vault) in [(p.yes_pool, p.yes_vault), (p.no_pool, p.no_vault)] {
+        let pool = h.pool(&address);
+        assert_eq!((pool.proposal_id, pool.market_id), (id, market_id));
+        assert_eq!((pool.token_vault, pool.vusd_vault), (vault, p.vusd_vault));
+        assert_eq!((pool.reserve_token, pool.reserve_vusd), (third, third / 2));
+    }
+    assert_eq!(entries[0].yes_price, PRICE_SCALE / 2);
+
+    // the proposal trades again
+    h.send(&alice, &[ix::claim_for_proposal(&alice.pubkey(), market_id, id)]).unwrap();
+    let buy = ix::swap_exact_in(&alice.pubkey(), market_id, id, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(&alice, &[buy]).unwrap();
+    assert!(h.pool(&p.yes_pool).reserve_token < third);
+
+    // once only: its legacy vaults are closed
+    let result = h.send(&alice, &[ix::migrate_proposal_auth(&alice.pubkey(), market_id, id, &[])]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn migrate_global_splits_the_shared_counter() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    h.propose(&alice, market_id, 1_000);
+
+    // one shared counter that has handed out market 0 and proposal 1
+    let next_id: u64 = 2;
+    let mut account = h.svm.get_account(&pda::global()).unwrap();
+    account.data = [GlobalState::DISCRIMINATOR, &next_id.to_le_bytes()[..]].concat();
+    account.lamports = h.svm.minimum_balance_for_rent_exemption(account.data.len());
+    h.svm.set_account(pda::global(), account).unwrap();
+
+    let admin = h.admin.insecure_clone();
+    let params = h.market_params();
+    let result = h.send(&admin, &[ix::create_market(&admin.pubkey(), &h.reward_mint, &admin.pubkey(), next_id, &params)]);
+    assert!(result.is_err());
+
+    let result = h.send(&alice, &[ix::migrate_global(&alice.pubkey())]);
+    assert_error(result, QuantumError::NotUpgradeAuthority);
+
+    h.send(&admin, &[ix::migrate_global(&admin.pubkey())]).unwrap();
+    let global = h.global();
+    assert_eq!((global.next_market_id, global.next_proposal_id), (next_id, next_id));
+    assert_eq!(global.admin, admin.pubkey());
+
+    let result = h.send(&admin, &[ix::migrate_global(&admin.pubkey())]);
+    assert_error(result, QuantumError::GlobalAlreadyMigrated);
+
+    assert_eq!(h.open_market(), next_id);
+    assert_eq!(h.propose(&alice, next_id, 1_000), next_id);
+}
+
+#[test]
+fn migrate_global_adds_the_admin_configuration_to_split_counters() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    h.propose(&alice, market_id, 1_000);
+
+    // counters only, as written before the admin and fees existed
+    let (next_market_id, next_proposal_id): (u64, u64) = (1, 1);
+    let mut account = h.svm.get_account(&pda::global()).unwrap();
+    account.data =
+        [GlobalState::DISCRIMINATOR, &next_market_id.to_le_bytes()[..], &next_proposal_id.to_le_bytes()[..]].concat();
+    h.svm.set_account(pda::global(), account).unwrap();
+
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::migrate_global(&admin.pubkey())]).unwrap();
+    let global = h.global();
+    assert_eq!((global.next_market_id, global.next_proposal_id), (next_market_id, next_proposal_id));
+    assert_eq!((global.admin, global.fee_treasury), (admin.pubkey(), admin.pubkey()));
+    assert!(!global.paused && !global.has_pending_admin());
+
+    assert_eq!(h.open_market(), next_market_id);
+}
+
+/// market `market_id` byte for byte as the original create_market wrote it
+fn legacy_market(h: &mut Harness, market_id: u64, creator: &Pubkey) {
+    let (address, bump) = Pubkey::find_program_address(&[MARKET_SEED, &market_id.to_le_bytes()], &PROGRAM_ID);
+    let title = b"legacy market";
+    let mut data = [
+        MarketConfig::DISCRIMINATOR,
+        &0u64.to_le_bytes(), // id, never written
+        &START.to_le_bytes(),
+        &MIN_DEPOSIT.to_le_bytes(),
+        &PRICE_SCALE.to_le_bytes(),
+        creator.as_ref(),
+        h.reward_mint.as_ref(),
+        creator.as_ref(), // resolver
+        &[0],             // Open
+        &(title.len() as u32).to_le_bytes(),
+        title,
+        &[bump],
+    ]
+    .concat();
+    data.resize(MarketConfig::LEGACY_LEN, 0);
+    put(h, address, PROGRAM_ID, data);
+}
+
+#[test]
+fn migrate_market_rewrites_the_original_layout() {
+    let mut h = Harness::new();
+    h.initialize_global();
+    let admin = h.admin.insecure_clone();
+    // market 1, which the original programs could not deposit into
+    let market_id = 1;
+    legacy_market(&mut h, market_id, &admin.pubkey());
+    let alice = h.user();
+    assert!(h.deposit(&alice, market_id, 1_000).is_err());
+
+    let deadline = h.now() + DURATION;
+    let migrate = |admin: &Pubkey| ix::migrate_market(admin, market_id, deadline, ACCEPT_THRESHOLD, TWAP_WINDOW);
+    assert_error(h.send(&alice, &[migrate(&alice.pubkey())]), QuantumError::NotAdmin);
+    h.send(&admin, &[migrate(&admin.pubkey())]).unwrap();
+
+    let market = h.market(market_id);
+    assert_eq!((market.id, market.created_at, market.min_deposit), (market_id, START, MIN_DEPOSIT));
+    assert_eq!((market.strike_price, market.market_token), (PRICE_SCALE, h.reward_mint));
+    assert_eq!((market.deadline, market.accept_threshold, market.twap_window), (deadline, ACCEPT_THRESHOLD, TWAP_WINDOW));
+    assert_eq!((market.creator, market.resolver, market.rent_payer), (admin.pubkey(), admin.pubkey(), admin.pubkey()));
+    assert_eq!(market.title, "legacy market");
+    assert!(market.status == MarketStatus::Open && !market.has_oracle());
+
+    // deposits and proposals work, and land in the new registry
+    h.propose(&alice, market_id, 1_000);
+    assert_eq!(h.market(market_id).proposal_count, 1);
+    assert_eq!(h.registry(market_id).0.len, 1);
+
+    let result = h.send(&admin, &[migrate(&admin.pubkey())]);
+    assert_error(result, QuantumError::MarketAlreadyMigrated);
+}
+
+/// deposit or claim record in the original layout: one amount
+fn legacy_record(h: &mut Harness, address: Pubkey, discriminator: &[u8], amount: u64) {
+    put(h, address, PROGRAM_ID, [discriminator, &amount.to_le_bytes()[..]].concat());
+}
+
+#[test]
+fn migrated_records_withdraw_once_the_market_settles() {
+    let mut h = Harness::new();
+    h.initialize_global();
+    let admin = h.admin.insecure_clone();
+    // market 0, the only one the original deposit_to_market could reach
+    let market_id = 0;
+    legacy_market(&mut h, market_id, &admin.pubkey());
+    let deadline = h.now() + DURATION;
+    let migrate = ix::migrate_market(&admin.pubkey(), market_id, deadline, ACCEPT_THRESHOLD, TWAP_WINDOW);
+    h.send(&admin, &[migrate]).unwrap();
+
+    // alice deposited 1_000, locked 600 in a proposal and claimed the 400
+    // left into it; bob deposited 500 and never claimed
+    let (alice, bob) = (h.user(), h.user());
+    let id = h.global().next_proposal_id;
+    let p = legacy_proposal(&mut h, market_id, id, &alice.pubkey());
+    let claimed = 400;
+    mint_account(&mut h, p.vusd_mint, pda::legacy_proposal_auth(), MIN_DEPOSIT / 3 + claimed, 6);
+    token_account(&mut h, p.vusd_mint, alice.pubkey(), claimed);
+
+    let market = pda::market(market_id);
+    let reward_mint = h.reward_mint;
+    token_account(&mut h, reward_mint, market, 1_500);
+    let alice_deposit = pda::deposit(&market, &alice.pubkey());
+    let bob_deposit = pda::deposit(&market, &bob.pubkey());
+    let alice_claim = pda::claim(&p.proposal, &alice.pubkey());
+    legacy_record(&mut h, alice_deposit, DepositRecord::DISCRIMINATOR, 1_000 - MIN_DEPOSIT);
+    legacy_record(&mut h, bob_deposit, DepositRecord::DISCRIMINATOR, 500);
+    legacy_record(&mut h, alice_claim, ClaimRecord::DISCRIMINATOR, claimed);
+
+    // legacy records are unreadable until migrated, claims after their proposal
+    let withdraw = |user: &Pubkey, amount| ix::withdraw_unclaimed(user, market_id, &reward_mint, amount);
+    assert!(h.send(&bob, &[withdraw(&bob.pubkey(), 1)]).is_err());
+    let migrate_claim = || ix::migrate_claim_record(&bob.pubkey(), id, &alice.pubkey());
+    assert!(h.send(&bob, &[migrate_claim()]).is_err());
+
+    // a ParameterChange executed since does not change what was locked
+    let mut config = h.market(market_id);
+    config.min_deposit = 2 * MIN_DEPOSIT;
+    let mut account = h.svm.get_account(&market).unwrap();
+    let mut data = Vec::new();
+    config.try_serialize(&mut data).unwrap();
+    data.resize(account.data.len(), 0);
+    account.data = data;
+    h.svm.set_account(market, account).unwrap();
+
+    // every claim record must be counted
+    let migrate_proposal = |claimants: &[Pubkey]| ix::migrate_proposal_auth(&bob.pubkey(), market_id, id, claimants);
+    assert_error(h.send(&bob, &[migrate_proposal(&[])]), QuantumError::ClaimRecordsMismatch);
+    let result = h.send(&bob, &[migrate_proposal(&[alice.pubkey(), alice.pubkey()])]);
+    assert_error(result, QuantumError::InvalidClaimRecord);
+    h.send(&bob, &[migrate_proposal(&[alice.pubkey()])]).unwrap();
+    let proposal = h.proposal(id);
+    assert_eq!((proposal.stake, proposal.open_claims), (MIN_DEPOSIT, 1));
+    assert_eq!(h.pool(&p.yes_pool).reserve_vusd, MIN_DEPOSIT / 6);
+
+    // permissionless; the users stay the rent payers
+    for user in [alice.pubkey(), bob.pubkey()] {
+        h.send(&bob, &[ix::migrate_deposit_record(&bob.pubkey(), market_id, &user)]).unwrap();
+    }
+    h.send(&bob, &[migrate_claim()]).unwrap();
+
+    // claims were not tracked against deposits, so all of it stays backing
+    let record: DepositRecord = h.account(&alice_deposit);
+    assert_eq!((record.amount, record.max_claimed, record.rent_payer), (400, 400, alice.pubkey()));
+    let record: DepositRecord = h.account(&bob_deposit);
+    assert_eq!((record.amount, record.max_claimed, record.rent_payer), (500, 500, bob.pubkey()));
+    let record: ClaimRecord = h.account(&alice_claim);
+    assert_eq!((record.claimed, record.rent_payer), (claimed, alice.pubkey()));
+    assert_eq!((h.market(market_id).open_deposits, h.proposal(id).open_claims), (2, 1));
+    assert_error(h.send(&bob, &[withdraw(&bob.pubkey(), 1)]), QuantumError::ExceedsUnclaimed);
+
+    // once only
+    let result = h.send(&bob, &[ix::migrate_deposit_record(&bob.pubkey(), market_id, &bob.pubkey())]);
+    assert_error(result, QuantumError::RecordAlreadyMigrated);
+    assert_error(h.send(&bob, &[migrate_claim()]), QuantumError::RecordAlreadyMigrated);
+
+    // nothing was accepted, so both deposits come back in full
+    h.warp(DURATION);
+    h.send(&bob, &[ix::expire_market(market_id)]).unwrap();
+    for (user, amount) in [(&alice, 400), (&bob, 500)] {
+        h.send(user, &[ix::withdraw_deposit(&user.pubkey(), market_id, &reward_mint, None)]).unwrap();
+        assert_eq!(h.balance(&pda::ata(&user.pubkey(), &reward_mint)), USER_FUNDS + amount);
+    }
+    // what is left is alice's stake, refunded when the proposal closes
+    assert_eq!(h.balance(&pda::ata(&market, &reward_mint)), MIN_DEPOSIT);
+}
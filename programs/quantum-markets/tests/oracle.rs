@@ -0,0 +1,191 @@
+//! Resolving against a price feed: mock Pyth and Switchboard accounts are
+//! written straight into the SVM at the offsets the program reads, owned
+//! by the oracle programs.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::constants::PRICE_SCALE;
+use quantum_markets::errors::QuantumError;
+use quantum_markets::state::oracle::{pyth, switchboard, switchboard_discriminator};
+use quantum_markets_client::instructions::{self as ix, CreateMarketParams};
+use quantum_markets_client::{MarketStatus, OracleConfig, OracleKind, PoolSide, SwapDirection};
+use solana_sdk::account::Account;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+const STRIKE: u64 = 42 * PRICE_SCALE;
+const MAX_STALENESS: i64 = 60;
+
+fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+/// Pyth price account quoting `price * 10^exponent`
+fn pyth_feed(price: i64, confidence: u64, exponent: i32, publish_time: i64) -> Vec<u8> {
+    let mut data = vec![0; pyth::LEN];
+    put(&mut data, pyth::MAGIC_OFFSET, &pyth::MAGIC.to_le_bytes());
+    put(&mut data, pyth::VERSION_OFFSET, &pyth::VERSION.to_le_bytes());
+    put(&mut data, pyth::TYPE_OFFSET, &pyth::PRICE_ACCOUNT.to_le_bytes());
+    put(&mut data, pyth::EXPONENT_OFFSET, &exponent.to_le_bytes());
+    put(&mut data, pyth::TIMESTAMP_OFFSET, &publish_time.to_le_bytes());
+    put(&mut data, pyth::PRICE_OFFSET, &price.to_le_bytes());
+    put(&mut data, pyth::CONFIDENCE_OFFSET, &confidence.to_le_bytes());
+    put(&mut data, pyth::STATUS_OFFSET, &pyth::TRADING.to_le_bytes());
+    data
+}
+
+/// Switchboard aggregator whose last round returned `result` with
+/// `std_deviation`, both as (mantissa, scale)
+fn switchboard_feed(result: (i128, u32), std_deviation: (i128, u32), timestamp: i64) -> Vec<u8> {
+    let mut data = vec![0; switchboard::LEN];
+    put(&mut data, 0, &switchboard_discriminator());
+    put(&mut data, switchboard::SUCCESS_OFFSET, &1u32.to_le_bytes());
+    put(&mut data, switchboard::TIMESTAMP_OFFSET, &timestamp.to_le_bytes());
+    let decimals = [(switchboard::RESULT_OFFSET, result), (switchboard::STD_DEVIATION_OFFSET, std_deviation)];
+    for (offset, (mantissa, scale)) in decimals {
+        put(&mut data, offset, &mantissa.to_le_bytes());
+        put(&mut data, offset + 16, &scale.to_le_bytes());
+    }
+    data
+}
+
+/// `data` at `feed`, owned by the program of the oracle it was laid out for
+fn write_feed(h: &mut Harness, feed: Pubkey, data: Vec<u8>) {
+    let owner = if data.len() == pyth::LEN { pyth::PROGRAM_ID } else { switchboard::PROGRAM_ID };
+    write_feed_owned_by(h, feed, data, owner);
+}
+
+fn write_feed_owned_by(h: &mut Harness, feed: Pubkey, data: Vec<u8>, owner: Pubkey) {
+    let account = Account { lamports: LAMPORTS_PER_SOL, data, owner, executable: false, rent_epoch: 0 };
+    h.svm.set_account(feed, account).unwrap();
+}
+
+/// accepted market resolved from a `kind` feed, warped to its deadline
+fn accepted_market(h: &mut Harness, kind: OracleKind) -> (u64, Pubkey) {
+    let feed = Pubkey::new_unique();
+    let oracle = OracleConfig { kind, feed, max_staleness: MAX_STALENESS, max_confidence_bps: 100 };
+    let params = CreateMarketParams { strike_price: STRIKE, oracle, ..h.market_params() };
+    let market_id = h.open_market_with(&params);
+    let alice = h.user();
+    let id = h.propose(&alice, market_id, 1_000);
+    let buy = ix::swap_exact_in(&alice.pubkey(), market_id, id, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(&alice, &[buy]).unwrap();
+    h.warp(TWAP_WINDOW);
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::graduate_proposal(market_id, id, &[])]).unwrap();
+    (market_id, feed)
+}
+
+#[test]
+fn a_pyth_price_at_the_strike_resolves_yes_after_the_deadline() {
+    let mut h = Harness::new();
+    let (market_id, feed) = accepted_market(&mut h, OracleKind::Pyth);
+    let admin = h.admin.insecure_clone();
+    // 42.00000000 ± 0.1, 24 bps
+    let now = h.now();
+    write_feed(&mut h, feed, pyth_feed(4_200_000_000, 10_000_000, -8, now));
+    assert_error(h.send(&admin, &[ix::resolve_with_oracle(market_id, &feed)]), QuantumError::DeadlineNotReached);
+
+    let deadline = h.market(market_id).deadline;
+    h.warp_to(deadline);
+    write_feed(&mut h, feed, pyth_feed(4_200_000_000, 10_000_000, -8, deadline));
+    // the resolver has no say in an oracle market
+    let manual = ix::resolve_market(&admin.pubkey(), market_id, false);
+    assert_error(h.send(&admin, &[manual]), QuantumError::ResolvedByOracle);
+
+    h.send(&admin, &[ix::resolve_with_oracle(market_id, &feed)]).unwrap();
+    let market = h.market(market_id);
+    assert!(market.status == MarketStatus::ResolvedYes);
+    assert_eq!(market.resolved_at, deadline);
+    let again = ix::resolve_with_oracle(market_id, &feed);
+    assert_error(h.send(&admin, &[again]), QuantumError::MarketAlreadyResolved);
+}
+
+#[test]
+fn a_switchboard_price_below_the_strike_resolves_no() {
+    let mut h = Harness::new();
+    let (market_id, feed) = accepted_market(&mut h, OracleKind::Switchboard);
+    let deadline = h.market(market_id).deadline;
+    h.warp_to(deadline + 10);
+    // 41.999 ± 0.05
+    write_feed(&mut h, feed, switchboard_feed((41_999, 3), (5, 2), deadline + 5));
+
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::resolve_with_oracle(market_id, &feed)]).unwrap();
+    assert!(h.market(market_id).status == MarketStatus::ResolvedNo);
+}
+
+#[test]
+fn readings_outside_the_limits_do_not_resolve() {
+    let mut h = Harness::new();
+    let (market_id, feed) = accepted_market(&mut h, OracleKind::Pyth);
+    let deadline = h.market(market_id).deadline;
+    h.warp_to(deadline + 100);
+    let now = h.now();
+
+    let mut not_trading = pyth_feed(4_200_000, 1_000, -5, now);
+    put(&mut not_trading, pyth::STATUS_OFFSET, &0u32.to_le_bytes());
+    let mut not_pyth = pyth_feed(4_200_000, 1_000, -5, now);
+    put(&mut not_pyth, pyth::MAGIC_OFFSET, &0u32.to_le_bytes());
+    let cases = [
+        (pyth_feed(4_200_000, 1_000, -5, deadline - 1), QuantumError::OraclePriceStale),
+        (pyth_feed(4_200_000, 1_000, -5, now - MAX_STALENESS - 1), QuantumError::OraclePriceStale),
+        // ± 0.5 at 42 is 119 bps
+        (pyth_feed(4_200_000, 50_000, -5, now), QuantumError::OracleConfidenceTooWide),
+        (not_trading, QuantumError::OraclePriceUnavailable),
+        (not_pyth, QuantumError::InvalidOracleAccount),
+    ];
+    let admin = h.admin.insecure_clone();
+    for (data, expected) in cases {
+        write_feed(&mut h, feed, data);
+        assert_error(h.send(&admin, &[ix::resolve_with_oracle(market_id, &feed)]), expected);
+    }
+
+    // a valid Pyth layout counts only in an account of the Pyth program
+    let owners = [Pubkey::new_unique(), switchboard::PROGRAM_ID];
+    for owner in owners {
+        write_feed_owned_by(&mut h, feed, pyth_feed(4_200_000, 1_000, -5, now), owner);
+        let result = h.send(&admin, &[ix::resolve_with_oracle(market_id, &feed)]);
+        assert_error(result, QuantumError::InvalidOracleAccount);
+    }
+    // and the other oracle's layout does not pass for it
+    write_feed_owned_by(&mut h, feed, switchboard_feed((42, 0), (0, 0), now), pyth::PROGRAM_ID);
+    assert_error(h.send(&admin, &[ix::resolve_with_oracle(market_id, &feed)]), QuantumError::InvalidOracleAccount);
+
+    // only the feed named at creation
+    let other = Pubkey::new_unique();
+    write_feed(&mut h, other, pyth_feed(4_200_000, 1_000, -5, now));
+    let result = h.send(&admin, &[ix::resolve_with_oracle(market_id, &other)]);
+    assert_error(result, QuantumError::InvalidOracleAccount);
+    assert!(h.market(market_id).status == MarketStatus::ProposalAccepted);
+}
+
+#[test]
+fn oracle_settings_are_checked_at_creation() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let admin = h.admin.insecure_clone();
+    let (resolver, reward_mint) = (admin.pubkey(), h.reward_mint);
+
+    let feed = Pubkey::new_unique();
+    let pyth = OracleConfig { kind: OracleKind::Pyth, feed, max_staleness: 60, max_confidence_bps: 100 };
+    let invalid = [
+        OracleConfig { max_staleness: 0, ..pyth },
+        OracleConfig { feed: Pubkey::default(), ..pyth },
+        OracleConfig { max_confidence_bps: 10_001, ..pyth },
+    ];
+    for oracle in invalid {
+        let params = CreateMarketParams { oracle, ..h.market_params() };
+        let id = h.global().next_market_id;
+        let create = ix::create_market(&admin.pubkey(), &reward_mint, &resolver, id, &params);
+        assert_error(h.send(&admin, &[create]), QuantumError::InvalidOracleConfig);
+    }
+
+    // a market without a feed stores the default one
+    let manual = ix::resolve_with_oracle(market_id, &Pubkey::default());
+    assert_error(h.send(&admin, &[manual]), QuantumError::NoOracle);
+}
@@ -0,0 +1,114 @@
+//! Proposal payloads: every variant is stored as encoded and decodes back,
+//! and malformed or out-of-bounds payloads are rejected at creation.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::constants::{MAX_DATA, MIN_DEPOSIT_FLOOR, PAYLOAD_VERSION};
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::{
+    payload, MarketParameter, PayloadAccount, PayloadInstruction, ProposalPayload, PROGRAM_ID,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// market where alice has deposited enough for a few proposals
+fn setup() -> (Harness, Keypair, u64) {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    h.deposit(&alice, market_id, MIN_DEPOSIT * 4).unwrap();
+    (h, alice, market_id)
+}
+
+fn create(h: &mut Harness, alice: &Keypair, market_id: u64, data: Vec<u8>) -> (u64, litesvm::types::TransactionResult) {
+    let id = h.global().next_proposal_id;
+    let reward_mint = h.reward_mint;
+    let result = h.send(alice, &[ix::create_proposal(&alice.pubkey(), market_id, &reward_mint, id, data)]);
+    (id, result)
+}
+
+fn instruction(program_id: Pubkey) -> PayloadInstruction {
+    PayloadInstruction {
+        program_id,
+        accounts: vec![PayloadAccount { pubkey: Pubkey::new_unique(), is_signer: false, is_writable: true }],
+        data: vec![1, 2, 3],
+    }
+}
+
+#[test]
+fn every_payload_variant_round_trips() {
+    let (mut h, alice, market_id) = setup();
+    let payloads = [
+        ProposalPayload::Text { uri: "https://example.com/proposal.md".into(), content_hash: [9; 32] },
+        ProposalPayload::Transfer { mint: Pubkey::new_unique(), destination: Pubkey::new_unique(), amount: 500 },
+        ProposalPayload::ParameterChange { parameter: MarketParameter::TwapWindow, value: 7_200 },
+        ProposalPayload::Instructions(vec![instruction(Pubkey::new_unique())]),
+    ];
+    for expected in payloads {
+        let (id, result) = create(&mut h, &alice, market_id, expected.encode());
+        result.unwrap();
+        let proposal = h.proposal(id);
+        assert_eq!(proposal.data()[0], PAYLOAD_VERSION);
+        let decoded = payload::decode(&proposal).unwrap();
+        assert_eq!(decoded, expected);
+        assert!(!payload::describe(&decoded).is_empty());
+    }
+}
+
+#[test]
+fn malformed_payloads_are_rejected() {
+    let (mut h, alice, market_id) = setup();
+    let text = ProposalPayload::Text { uri: "ipfs://proposal".into(), content_hash: [0; 32] }.encode();
+
+    let mut trailing = text.clone();
+    trailing.push(0);
+    let mut future = text.clone();
+    future[0] = PAYLOAD_VERSION + 1;
+    let long = ProposalPayload::Text { uri: "x".repeat(MAX_DATA), content_hash: [0; 32] }.encode();
+    let cases = [
+        (b"hello-world".to_vec(), QuantumError::InvalidProposalPayload),
+        (Vec::new(), QuantumError::InvalidProposalPayload),
+        (trailing, QuantumError::InvalidProposalPayload),
+        (future, QuantumError::UnsupportedPayloadVersion),
+        (long, QuantumError::ProposalDataTooLong),
+    ];
+    for (data, expected) in cases {
+        assert_error(create(&mut h, &alice, market_id, data).1, expected);
+    }
+}
+
+#[test]
+fn payload_contents_are_validated() {
+    let (mut h, alice, market_id) = setup();
+    let cases = [
+        (ProposalPayload::Text { uri: String::new(), content_hash: [0; 32] }, QuantumError::InvalidProposalPayload),
+        (
+            ProposalPayload::Transfer { mint: Pubkey::new_unique(), destination: Pubkey::new_unique(), amount: 0 },
+            QuantumError::ZeroAmount,
+        ),
+        // the harness leaves the global fee maximums at zero
+        (ProposalPayload::ParameterChange { parameter: MarketParameter::LpFeeBps, value: 1 }, QuantumError::FeeTooHigh),
+        (ProposalPayload::ParameterChange { parameter: MarketParameter::TwapWindow, value: 0 }, QuantumError::InvalidWindow),
+        (
+            ProposalPayload::ParameterChange { parameter: MarketParameter::MinDeposit, value: MIN_DEPOSIT_FLOOR - 1 },
+            QuantumError::MinDepositTooSmall,
+        ),
+        (ProposalPayload::Instructions(Vec::new()), QuantumError::InvalidProposalPayload),
+        (ProposalPayload::Instructions(vec![instruction(PROGRAM_ID)]), QuantumError::InvalidProposalPayload),
+        // only the market treasury signs
+        (
+            ProposalPayload::Instructions(vec![PayloadInstruction {
+                accounts: vec![PayloadAccount { pubkey: alice.pubkey(), is_signer: true, is_writable: false }],
+                ..instruction(Pubkey::new_unique())
+            }]),
+            QuantumError::InvalidProposalPayload,
+        ),
+    ];
+    for (payload, expected) in cases {
+        assert_error(create(&mut h, &alice, market_id, payload.encode()).1, expected);
+    }
+}
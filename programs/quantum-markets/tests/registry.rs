@@ -0,0 +1,62 @@
+//! The per-market proposal registry: one entry per proposal in creation
+//! order, grown when full, with each proposal's latest spot prices.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::constants::REGISTRY_GROWTH;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{MarketProposals, PoolSide, SwapDirection};
+use solana_sdk::signature::Signer;
+
+#[test]
+fn proposals_are_listed_with_their_mints_and_latest_prices() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let (header, entries) = h.registry(market_id);
+    assert_eq!((header.market_id, header.len, header.capacity), (market_id, 0, REGISTRY_GROWTH));
+    assert!(entries.is_empty());
+
+    let p = ProposalKeys::new(h.propose(&alice, market_id, 1_000));
+    let (_, entries) = h.registry(market_id);
+    let entry = entries[0];
+    assert_eq!(entry.id, p.id);
+    assert_eq!((entry.vusd_mint, entry.yes_mint, entry.no_mint), (p.vusd_mint, p.yes_mint, p.no_mint));
+    assert_eq!((entry.yes_price, entry.no_price), (ACCEPT_THRESHOLD, ACCEPT_THRESHOLD));
+    assert_eq!(h.proposal(p.id).registry_index, 0);
+
+    h.warp(60);
+    let buy = ix::swap_exact_in(&alice.pubkey(), market_id, p.id, PoolSide::Yes, SwapDirection::Buy, 50, 0);
+    h.send(&alice, &[buy]).unwrap();
+    let (_, entries) = h.registry(market_id);
+    assert_eq!(entries[0].yes_price, h.pool(&p.yes_pool).spot_price().unwrap());
+    assert_eq!(entries[0].no_price, ACCEPT_THRESHOLD);
+    assert_eq!(entries[0].updated_at, h.now());
+}
+
+#[test]
+fn a_full_registry_grows_for_the_next_proposal() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let count = REGISTRY_GROWTH as u64 + 1;
+    h.deposit(&alice, market_id, MIN_DEPOSIT * count).unwrap();
+    let ids: Vec<u64> = (0..count)
+        .map(|_| {
+            let (id, result) = h.create_proposal(&alice, market_id);
+            result.unwrap();
+            id
+        })
+        .collect();
+
+    let (header, entries) = h.registry(market_id);
+    assert_eq!((header.len, header.capacity), (count as u32, REGISTRY_GROWTH * 2));
+    assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), ids);
+    let address = pda::market_proposals(&pda::market(market_id));
+    assert_eq!(h.svm.get_account(&address).unwrap().data.len(), MarketProposals::space(REGISTRY_GROWTH * 2));
+    assert_eq!(h.proposal(ids[REGISTRY_GROWTH as usize]).registry_index, REGISTRY_GROWTH);
+}
@@ -0,0 +1,197 @@
+//! Graduating a proposal, resolving the market and paying out.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{MarketStatus, PoolSide, SwapDirection};
+use solana_sdk::signature::{Keypair, Signer};
+
+/// buy YES of `proposal_id` with `vusd`
+fn buy_yes(h: &mut Harness, user: &Keypair, market_id: u64, proposal_id: u64, vusd: u64) {
+    h.send(
+        user,
+        &[ix::swap_exact_in(&user.pubkey(), market_id, proposal_id, PoolSide::Yes, SwapDirection::Buy, vusd, 0)],
+    )
+    .unwrap();
+}
+
+/// market whose only proposal trades above the threshold from creation on
+fn setup() -> (Harness, Keypair, u64, ProposalKeys) {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let id = h.propose(&alice, market_id, 1_000);
+    buy_yes(&mut h, &alice, market_id, id, 50);
+    (h, alice, market_id, ProposalKeys::new(id))
+}
+
+fn accept(h: &mut Harness, market_id: u64, proposal_id: u64) {
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::graduate_proposal(market_id, proposal_id, &[])]).unwrap();
+}
+
+fn resolve(h: &mut Harness, market_id: u64, outcome_yes: bool) {
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::resolve_market(&admin.pubkey(), market_id, outcome_yes)]).unwrap();
+}
+
+#[test]
+fn graduate_waits_a_full_window_after_creation() {
+    let (mut h, alice, market_id, p) = setup();
+    let created_at = h.proposal(p.id).created_at;
+    let graduate = || ix::graduate_proposal(market_id, p.id, &[]);
+
+    assert_error(h.send(&alice, &[graduate()]), QuantumError::ProposalTooYoung);
+    h.warp_to(created_at + TWAP_WINDOW - 1);
+    assert_error(h.send(&alice, &[graduate()]), QuantumError::ProposalTooYoung);
+
+    h.warp_to(created_at + TWAP_WINDOW);
+    h.send(&alice, &[graduate()]).unwrap();
+    let market = h.market(market_id);
+    assert!(market.status == MarketStatus::ProposalAccepted);
+    assert_eq!(market.accepted_proposal, p.id);
+    assert_eq!(market.accepted_at, created_at + TWAP_WINDOW);
+
+    h.warp(1);
+    assert_error(h.send(&alice, &[graduate()]), QuantumError::MarketNotOpen);
+}
+
+#[test]
+fn graduate_needs_a_twap_above_the_threshold() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let id = h.propose(&alice, market_id, 1_000);
+
+    // still at the opening price, which equals the threshold
+    h.warp(TWAP_WINDOW);
+    let result = h.send(&alice, &[ix::graduate_proposal(market_id, id, &[])]);
+    assert_error(result, QuantumError::BelowAcceptThreshold);
+}
+
+#[test]
+fn graduate_compares_against_every_other_proposal() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let bob = h.user();
+    let high = h.propose(&alice, market_id, 1_000);
+    let low = h.propose(&bob, market_id, 1_000);
+    buy_yes(&mut h, &alice, market_id, high, 50);
+    buy_yes(&mut h, &bob, market_id, low, 10);
+    h.warp(TWAP_WINDOW);
+
+    let result = h.send(&alice, &[ix::graduate_proposal(market_id, high, &[])]);
+    assert_error(result, QuantumError::IncompleteProposalSet);
+    let result = h.send(&alice, &[ix::graduate_proposal(market_id, high, &[high])]);
+    assert_error(result, QuantumError::IncompleteProposalSet);
+    let result = h.send(&bob, &[ix::graduate_proposal(market_id, low, &[high])]);
+    assert_error(result, QuantumError::NotHighestProposal);
+
+    h.send(&alice, &[ix::graduate_proposal(market_id, high, &[low])]).unwrap();
+    assert_eq!(h.market(market_id).accepted_proposal, high);
+}
+
+#[test]
+fn graduate_after_the_deadline_fails_with_market_closed() {
+    let (mut h, alice, market_id, p) = setup();
+    h.warp(DURATION);
+    let result = h.send(&alice, &[ix::graduate_proposal(market_id, p.id, &[])]);
+    assert_error(result, QuantumError::MarketClosed);
+}
+
+#[test]
+fn resolve_is_limited_to_the_resolver_of_an_accepted_market() {
+    let (mut h, alice, market_id, p) = setup();
+    let admin = h.admin.insecure_clone();
+
+    let result = h.send(&admin, &[ix::resolve_market(&admin.pubkey(), market_id, true)]);
+    assert_error(result, QuantumError::MarketNotAccepted);
+
+    h.warp(TWAP_WINDOW);
+    accept(&mut h, market_id, p.id);
+    let result = h.send(&alice, &[ix::resolve_market(&alice.pubkey(), market_id, true)]);
+    assert_error(result, QuantumError::InvalidResolver);
+
+    h.warp(10);
+    resolve(&mut h, market_id, true);
+    let market = h.market(market_id);
+    assert!(market.status == MarketStatus::ResolvedYes);
+    assert_eq!(market.resolved_at, h.now());
+
+    h.warp(1);
+    let result = h.send(&admin, &[ix::resolve_market(&admin.pubkey(), market_id, false)]);
+    assert_error(result, QuantumError::MarketAlreadyResolved);
+}
+
+#[test]
+fn redeem_winning_pays_out_of_the_vusd_backing() {
+    let (mut h, alice, market_id, p) = setup();
+    let user = alice.pubkey();
+    let reward_mint = h.reward_mint;
+    let redeem = |side, amount| ix::redeem_winning(&user, market_id, &reward_mint, p.id, side, amount);
+
+    h.warp(TWAP_WINDOW);
+    accept(&mut h, market_id, p.id);
+    assert_error(h.send(&alice, &[redeem(PoolSide::Yes, 1)]), QuantumError::MarketNotResolved);
+    resolve(&mut h, market_id, true);
+    assert_error(h.send(&alice, &[redeem(PoolSide::No, 1)]), QuantumError::NotWinningToken);
+
+    // 266 of 400 YES outstanding, backed by 650 vUSD, so they pay 1:1
+    let held = h.balance(&pda::ata(&user, &p.yes_mint));
+    assert_eq!(held, 266);
+    assert_eq!(h.supply(&p.yes_mint), 400);
+    assert_eq!(h.balance(&p.vusd_vault), 650);
+    let payout = held;
+
+    let market_vault = pda::ata(&pda::market(market_id), &reward_mint);
+    let vault_before = h.balance(&market_vault);
+    let user_before = h.balance(&pda::ata(&user, &reward_mint));
+    h.send(&alice, &[redeem(PoolSide::Yes, held)]).unwrap();
+
+    assert_eq!(h.balance(&pda::ata(&user, &p.yes_mint)), 0);
+    assert_eq!(h.supply(&p.yes_mint), 400 - held);
+    assert_eq!(h.balance(&p.vusd_vault), 650 - payout);
+    assert_eq!(h.balance(&pda::ata(&user, &reward_mint)), user_before + payout);
+    assert_eq!(h.balance(&market_vault), vault_before - payout);
+}
+
+#[test]
+fn withdraw_deposit_keeps_what_was_claimed_into_the_accepted_proposal() {
+    let (mut h, alice, market_id, p) = setup();
+    let bob = h.user();
+    h.deposit(&bob, market_id, 500).unwrap();
+    let reward_mint = h.reward_mint;
+
+    h.warp(TWAP_WINDOW);
+    accept(&mut h, market_id, p.id);
+
+    let result = h.send(&bob, &[ix::withdraw_deposit(&bob.pubkey(), market_id, &reward_mint, None)]);
+    assert_error(result, QuantumError::ProposalNotAccepted);
+
+    // bob never claimed into the accepted proposal
+    h.send(&bob, &[ix::withdraw_deposit(&bob.pubkey(), market_id, &reward_mint, Some(p.id))]).unwrap();
+    assert_eq!(h.balance(&pda::ata(&bob.pubkey(), &reward_mint)), USER_FUNDS);
+
+    // all of alice's remaining deposit backs her claim
+    let result = h.send(&alice, &[ix::withdraw_deposit(&alice.pubkey(), market_id, &reward_mint, Some(p.id))]);
+    assert_error(result, QuantumError::NothingToClaim);
+}
+
+#[test]
+fn yes_no_stop_redeeming_into_vusd_once_resolved() {
+    let (mut h, alice, market_id, p) = setup();
+    let user = alice.pubkey();
+    h.send(&alice, &[ix::mint_yes_no(&user, market_id, p.id, 10)]).unwrap();
+
+    h.warp(TWAP_WINDOW);
+    accept(&mut h, market_id, p.id);
+    resolve(&mut h, market_id, true);
+    let result = h.send(&alice, &[ix::redeem_yes_no(&user, market_id, p.id, 10)]);
+    assert_error(result, QuantumError::MarketAlreadyResolved);
+}
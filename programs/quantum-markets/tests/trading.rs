@@ -0,0 +1,152 @@
+//! Splitting and merging vUSD, pool swaps and the TWAP view.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::constants::{OBSERVATIONS, PRICE_SCALE};
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{PoolSide, SwapDirection};
+use solana_sdk::signature::{Keypair, Signer};
+
+/// market with one proposal by a user holding 400 vUSD, 200 YES and 200 NO
+fn setup() -> (Harness, Keypair, u64, ProposalKeys) {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let id = h.propose(&alice, market_id, 1_000);
+    (h, alice, market_id, ProposalKeys::new(id))
+}
+
+#[test]
+fn mint_and_redeem_yes_no_move_vusd_one_to_one() {
+    let (mut h, alice, market_id, p) = setup();
+    let user = alice.pubkey();
+
+    h.send(&alice, &[ix::mint_yes_no(&user, market_id, p.id, 150)]).unwrap();
+    assert_eq!(h.balance(&pda::ata(&user, &p.vusd_mint)), 250);
+    assert_eq!(h.balance(&pda::ata(&user, &p.yes_mint)), 350);
+    assert_eq!(h.balance(&pda::ata(&user, &p.no_mint)), 350);
+    assert_eq!(h.balance(&p.vusd_vault), 750);
+
+    h.send(&alice, &[ix::redeem_yes_no(&user, market_id, p.id, 100)]).unwrap();
+    assert_eq!(h.balance(&pda::ata(&user, &p.vusd_mint)), 350);
+    assert_eq!(h.balance(&pda::ata(&user, &p.yes_mint)), 250);
+    assert_eq!(h.balance(&pda::ata(&user, &p.no_mint)), 250);
+    assert_eq!(h.balance(&p.vusd_vault), 650);
+}
+
+#[test]
+fn mint_yes_no_after_the_deadline_fails_with_market_closed() {
+    let (mut h, alice, market_id, p) = setup();
+    h.warp(DURATION);
+    let result = h.send(&alice, &[ix::mint_yes_no(&alice.pubkey(), market_id, p.id, 10)]);
+    assert_error(result, QuantumError::MarketClosed);
+}
+
+#[test]
+fn swap_exact_in_fills_at_the_constant_product_quote() {
+    let (mut h, alice, market_id, p) = setup();
+    let user = alice.pubkey();
+
+    // 100 vUSD / 200 YES: 50 in buys 200 * 50 / 150 = 66
+    let buy = |min_out| ix::swap_exact_in(&user, market_id, p.id, PoolSide::Yes, SwapDirection::Buy, 50, min_out);
+    assert_error(h.send(&alice, &[buy(67)]), QuantumError::SlippageExceeded);
+    h.send(&alice, &[buy(66)]).unwrap();
+
+    let pool = h.pool(&p.yes_pool);
+    assert_eq!((pool.reserve_token, pool.reserve_vusd), (134, 150));
+    assert_eq!(h.balance(&pda::ata(&user, &p.yes_mint)), 266);
+    assert_eq!(h.balance(&pda::ata(&user, &p.vusd_mint)), 350);
+    assert_eq!(h.balance(&p.yes_vault), 134);
+    assert_eq!(h.balance(&p.vusd_vault), 650);
+
+    // the NO pool is untouched
+    let no = h.pool(&p.no_pool);
+    assert_eq!((no.reserve_token, no.reserve_vusd), (200, 100));
+}
+
+#[test]
+fn swap_exact_out_rounds_the_input_up() {
+    let (mut h, alice, market_id, p) = setup();
+    let user = alice.pubkey();
+
+    // 200 NO / 100 vUSD: 20 out costs ceil(200 * 20 / 80) = 50
+    let sell = |max_in| ix::swap_exact_out(&user, market_id, p.id, PoolSide::No, SwapDirection::Sell, 20, max_in);
+    assert_error(h.send(&alice, &[sell(49)]), QuantumError::SlippageExceeded);
+    h.send(&alice, &[sell(50)]).unwrap();
+
+    let pool = h.pool(&p.no_pool);
+    assert_eq!((pool.reserve_token, pool.reserve_vusd), (250, 80));
+    assert_eq!(h.balance(&pda::ata(&user, &p.no_mint)), 150);
+    assert_eq!(h.balance(&pda::ata(&user, &p.vusd_mint)), 420);
+}
+
+#[test]
+fn swap_after_the_deadline_fails_with_market_closed() {
+    let (mut h, alice, market_id, p) = setup();
+    h.warp(DURATION);
+    let result = h.send(
+        &alice,
+        &[ix::swap_exact_in(&alice.pubkey(), market_id, p.id, PoolSide::Yes, SwapDirection::Buy, 10, 0)],
+    );
+    assert_error(result, QuantumError::MarketClosed);
+}
+
+fn read_twap(h: &mut Harness, payer: &Keypair, proposal_id: u64, window: i64) -> u64 {
+    let meta = h.send(payer, &[ix::yes_twap(proposal_id, window)]).unwrap();
+    assert_eq!(meta.return_data.program_id, quantum_markets::ID);
+    u64::from_le_bytes(meta.return_data.data[..8].try_into().unwrap())
+}
+
+#[test]
+fn yes_twap_weights_prices_by_time_since_creation() {
+    let (mut h, alice, market_id, p) = setup();
+    let opening = PRICE_SCALE / 2;
+    assert_eq!(read_twap(&mut h, &alice, p.id, 0), opening);
+
+    // 100s at the opening price, then 100s at 150 / 134
+    h.warp(100);
+    h.send(
+        &alice,
+        &[ix::swap_exact_in(&alice.pubkey(), market_id, p.id, PoolSide::Yes, SwapDirection::Buy, 50, 0)],
+    )
+    .unwrap();
+    let after = 150 * PRICE_SCALE / 134;
+    h.warp(100);
+
+    let expected = (opening + after) / 2;
+    assert_eq!(read_twap(&mut h, &alice, p.id, 200), expected);
+    // windows reaching before the proposal existed are clamped to its lifetime
+    assert_eq!(read_twap(&mut h, &alice, p.id, 10_000), expected);
+    assert_eq!(read_twap(&mut h, &alice, p.id, 100), after);
+    assert_eq!(h.pool(&p.yes_pool).twap(h.now(), 200).unwrap(), expected);
+
+    let result = h.send(&alice, &[ix::yes_twap(p.id, -1)]);
+    assert_error(result, QuantumError::InvalidWindow);
+}
+
+#[test]
+fn more_swaps_than_observations_in_a_window_still_graduate() {
+    let (mut h, alice, market_id, p) = setup();
+    let swap = |direction, amount| {
+        ix::swap_exact_in(&alice.pubkey(), market_id, p.id, PoolSide::Yes, direction, amount, 0)
+    };
+    h.send(&alice, &[swap(SwapDirection::Buy, 50)]).unwrap();
+    // a round trip every two minutes for two windows: 120 swaps in all
+    for _ in 0..TWAP_WINDOW / 60 {
+        h.warp(60);
+        h.send(&alice, &[swap(SwapDirection::Buy, 10)]).unwrap();
+        h.warp(60);
+        h.send(&alice, &[swap(SwapDirection::Sell, 8)]).unwrap();
+    }
+    let pool = h.pool(&p.yes_pool);
+    assert_eq!(pool.observation_count as usize, OBSERVATIONS);
+    assert!(pool.twap(h.now(), TWAP_WINDOW).unwrap() > ACCEPT_THRESHOLD);
+
+    let admin = h.admin.insecure_clone();
+    h.send(&admin, &[ix::graduate_proposal(market_id, p.id, &[])]).unwrap();
+}
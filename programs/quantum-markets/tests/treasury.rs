@@ -0,0 +1,48 @@
+//! Funding the market treasury: any mint, from anyone, while the market can
+//! still accept or execute a proposal.
+
+#![cfg(feature = "svm-tests")]
+
+mod common;
+
+use common::*;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda;
+use solana_sdk::signature::Signer;
+
+#[test]
+fn anyone_can_fund_the_treasury_in_any_mint() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let (alice, bob) = (h.user(), h.user());
+    let treasury = pda::treasury(&pda::market(market_id));
+    let mint = h.create_mint();
+    h.fund(&bob.pubkey(), &mint, 500);
+
+    // the first funding of a mint creates the treasury's account for it
+    let reward_mint = h.reward_mint;
+    h.send(&alice, &[ix::fund_treasury(&alice.pubkey(), market_id, &reward_mint, 300)]).unwrap();
+    h.send(&bob, &[ix::fund_treasury(&bob.pubkey(), market_id, &mint, 200)]).unwrap();
+    h.send(&bob, &[ix::fund_treasury(&bob.pubkey(), market_id, &mint, 300)]).unwrap();
+
+    assert_eq!(h.balance(&pda::ata(&treasury, &reward_mint)), 300);
+    assert_eq!(h.balance(&pda::ata(&treasury, &mint)), 500);
+    assert_eq!(h.balance(&pda::ata(&bob.pubkey(), &mint)), 0);
+    // treasury funds are not deposits
+    assert_eq!(h.balance(&pda::ata(&pda::market(market_id), &reward_mint)), 0);
+}
+
+#[test]
+fn funding_needs_an_amount_and_a_live_market() {
+    let mut h = Harness::new();
+    let market_id = h.open_market();
+    let alice = h.user();
+    let reward_mint = h.reward_mint;
+    let fund = |amount| ix::fund_treasury(&alice.pubkey(), market_id, &reward_mint, amount);
+
+    assert_error(h.send(&alice, &[fund(0)]), QuantumError::ZeroAmount);
+    // nothing could ever be executed to spend it
+    h.warp(DURATION);
+    assert_error(h.send(&alice, &[fund(100)]), QuantumError::MarketClosed);
+}
@@ -0,0 +1,194 @@
+//! Accounts belonging to another market, proposal or mint are rejected with
+//! a dedicated error before anything moves.
+
+#![cfg(feature = "svm-tests")]
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::*;
+use quantum_markets::errors::QuantumError;
+use quantum_markets_client::instructions as ix;
+use quantum_markets_client::pda::{self, ProposalKeys};
+use quantum_markets_client::{PoolSide, SwapDirection};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// two markets with one proposal each, all made by alice
+struct World {
+    h:       Harness,
+    alice:   Keypair,
+    bob:     Keypair,
+    a:       u64,          // market of `pa`
+    b:       u64,          // market of `pb`
+    pa:      ProposalKeys,
+    pb:      ProposalKeys,
+    foreign: Pubkey,       // mint no market uses
+}
+
+fn world() -> World {
+    let mut h = Harness::new();
+    let (a, b) = (h.open_market(), h.open_market());
+    let (alice, bob) = (h.user(), h.user());
+    let pa = ProposalKeys::new(h.propose(&alice, a, 1_000));
+    let pb = ProposalKeys::new(h.propose(&alice, b, 1_000));
+    // alice holds YES and NO of both proposals
+    for (market_id, p) in [(a, &pa), (b, &pb)] {
+        h.send(&alice, &[ix::mint_yes_no(&alice.pubkey(), market_id, p.id, 10)]).unwrap();
+    }
+    let foreign = h.create_mint();
+    h.fund(&alice.pubkey(), &foreign, USER_FUNDS);
+    World { h, alice, bob, a, b, pa, pb, foreign }
+}
+
+/// `ix` with every `from` account swapped for `to`
+fn replace(mut ix: Instruction, swaps: &[(Pubkey, Pubkey)]) -> Instruction {
+    for meta in &mut ix.accounts {
+        if let Some((_, to)) = swaps.iter().find(|(from, _)| *from == meta.pubkey) {
+            meta.pubkey = *to;
+        }
+    }
+    ix
+}
+
+impl World {
+    fn send(&mut self, ix: Instruction) -> litesvm::types::TransactionResult {
+        let alice = self.alice.insecure_clone();
+        self.h.send(&alice, &[ix])
+    }
+
+    fn ata(&self, mint: &Pubkey) -> Pubkey {
+        pda::ata(&self.alice.pubkey(), mint)
+    }
+}
+
+#[test]
+fn deposits_and_proposals_take_only_the_reward_mint() {
+    let mut w = world();
+    let alice = w.alice.pubkey();
+    let reward_mint = w.h.reward_mint;
+
+    let result = w.send(ix::deposit_to_market(&alice, w.a, &w.foreign, 10));
+    assert_error(result, QuantumError::InvalidRewardMint);
+    let result = w.send(ix::create_proposal(&alice, w.a, &w.foreign, w.h.global().next_proposal_id, payload()));
+    assert_error(result, QuantumError::InvalidRewardMint);
+
+    // paying from an account of another mint or another owner
+    let deposit = ix::deposit_to_market(&alice, w.a, &reward_mint, 10);
+    let other_mint = replace(deposit.clone(), &[(w.ata(&reward_mint), w.ata(&w.foreign))]);
+    assert_error(w.send(other_mint), QuantumError::InvalidTokenAccount);
+    let bobs = pda::ata(&w.bob.pubkey(), &reward_mint);
+    let other_owner = replace(deposit, &[(w.ata(&reward_mint), bobs)]);
+    assert_error(w.send(other_owner), QuantumError::InvalidTokenAccount);
+}
+
+#[test]
+fn proposal_instructions_reject_a_proposal_of_another_market() {
+    let mut w = world();
+    let alice = w.alice.pubkey();
+    let (b, pa) = (w.b, w.pa.id);
+
+    assert_error(w.send(ix::claim_for_proposal(&alice, b, pa)), QuantumError::ProposalMismatch);
+    assert_error(w.send(ix::mint_yes_no(&alice, b, pa, 1)), QuantumError::ProposalMismatch);
+    assert_error(w.send(ix::redeem_yes_no(&alice, b, pa, 1)), QuantumError::ProposalMismatch);
+    let swap = ix::swap_exact_in(&alice, b, pa, PoolSide::Yes, SwapDirection::Buy, 1, 0);
+    assert_error(w.send(swap), QuantumError::ProposalMismatch);
+    assert_error(w.send(ix::graduate_proposal(b, pa, &[])), QuantumError::ProposalMismatch);
+
+    let reward_mint = w.h.reward_mint;
+    let redeem = ix::redeem_winning(&alice, b, &reward_mint, pa, PoolSide::Yes, 1);
+    assert_error(w.send(redeem), QuantumError::ProposalMismatch);
+}
+
+#[test]
+fn proposal_instructions_reject_mints_and_vaults_of_another_proposal() {
+    let mut w = world();
+    let alice = w.alice.pubkey();
+    let (a, pa, pb) = (w.a, w.pa, w.pb);
+    // the user's account follows the substituted mint so only the mint is wrong
+    let mint_swap = |from: Pubkey, to: Pubkey| [(from, to), (pda::ata(&alice, &from), pda::ata(&alice, &to))];
+
+    let claim = replace(ix::claim_for_proposal(&alice, a, pa.id), &mint_swap(pa.vusd_mint, pb.vusd_mint));
+    assert_error(w.send(claim), QuantumError::InvalidProposalMint);
+
+    let mint = ix::mint_yes_no(&alice, a, pa.id, 1);
+    let other_yes = replace(mint.clone(), &mint_swap(pa.yes_mint, pb.yes_mint));
+    assert_error(w.send(other_yes), QuantumError::InvalidProposalMint);
+    let other_vault = replace(mint.clone(), &[(pa.vusd_vault, pb.vusd_vault)]);
+    assert_error(w.send(other_vault), QuantumError::InvalidVault);
+    let other_user_vusd = replace(mint, &[(w.ata(&pa.vusd_mint), w.ata(&pb.vusd_mint))]);
+    assert_error(w.send(other_user_vusd), QuantumError::InvalidTokenAccount);
+
+    let redeem = ix::redeem_yes_no(&alice, a, pa.id, 1);
+    let other_no = replace(redeem.clone(), &[(pa.no_mint, pb.no_mint)]);
+    assert_error(w.send(other_no), QuantumError::InvalidProposalMint);
+    let other_vault = replace(redeem.clone(), &[(pa.vusd_vault, pb.vusd_vault)]);
+    assert_error(w.send(other_vault), QuantumError::InvalidVault);
+    let other_user_yes = replace(redeem, &[(w.ata(&pa.yes_mint), w.ata(&pb.yes_mint))]);
+    assert_error(w.send(other_user_yes), QuantumError::InvalidTokenAccount);
+
+    let reward_mint = w.h.reward_mint;
+    // `pa` is proposal 0, which a market that accepted nothing yet points at
+    let winning = ix::redeem_winning(&alice, a, &reward_mint, pa.id, PoolSide::Yes, 1);
+    let other_vusd = replace(winning.clone(), &[(pa.vusd_mint, pb.vusd_mint)]);
+    assert_error(w.send(other_vusd), QuantumError::InvalidProposalMint);
+    let other_vault = replace(winning.clone(), &[(pa.vusd_vault, pb.vusd_vault)]);
+    assert_error(w.send(other_vault), QuantumError::InvalidVault);
+    let other_user_yes = replace(winning.clone(), &[(w.ata(&pa.yes_mint), w.ata(&pb.yes_mint))]);
+    assert_error(w.send(other_user_yes), QuantumError::InvalidTokenAccount);
+    let other_reward = replace(winning, &[(reward_mint, w.foreign), (w.ata(&reward_mint), w.ata(&w.foreign))]);
+    assert_error(w.send(other_reward), QuantumError::InvalidRewardMint);
+}
+
+#[test]
+fn swaps_and_twap_reads_reject_pools_of_another_proposal() {
+    let mut w = world();
+    let alice = w.alice.pubkey();
+    let (a, pa, pb) = (w.a, w.pa, w.pb);
+    let buy = ix::swap_exact_in(&alice, a, pa.id, PoolSide::Yes, SwapDirection::Buy, 1, 0);
+
+    let other_pool = replace(
+        buy.clone(),
+        &[(pa.yes_pool, pb.yes_pool), (pa.yes_vault, pb.yes_vault), (pa.vusd_vault, pb.vusd_vault)],
+    );
+    assert_error(w.send(other_pool), QuantumError::InvalidPool);
+    let other_vault = replace(buy.clone(), &[(pa.vusd_vault, pb.vusd_vault)]);
+    assert_error(w.send(other_vault), QuantumError::InvalidVault);
+    let other_token = replace(
+        buy,
+        &[(pa.yes_mint, pb.yes_mint), (w.ata(&pa.yes_mint), w.ata(&pb.yes_mint))],
+    );
+    assert_error(w.send(other_token), QuantumError::InvalidProposalMint);
+
+    let twap = replace(ix::yes_twap(pa.id, TWAP_WINDOW), &[(pa.yes_pool, pb.yes_pool)]);
+    assert_error(w.send(twap), QuantumError::InvalidPool);
+    let graduate = replace(ix::graduate_proposal(a, pa.id, &[]), &[(pa.yes_pool, pb.yes_pool)]);
+    assert_error(w.send(graduate), QuantumError::InvalidPool);
+}
+
+#[test]
+fn withdrawals_pay_only_from_the_market_vault() {
+    let mut w = world();
+    let alice = w.alice.pubkey();
+    let reward_mint = w.h.reward_mint;
+    let (a, b) = (w.a, w.b);
+    let vault_b = pda::ata(&pda::market(b), &reward_mint);
+    let bobs = pda::ata(&w.bob.pubkey(), &reward_mint);
+
+    let unclaimed = ix::withdraw_unclaimed(&alice, a, &reward_mint, 1);
+    let other_mint = replace(unclaimed.clone(), &[(reward_mint, w.foreign)]);
+    assert_error(w.send(other_mint), QuantumError::InvalidRewardMint);
+    let other_vault = replace(unclaimed.clone(), &[(pda::ata(&pda::market(a), &reward_mint), vault_b)]);
+    assert_error(w.send(other_vault), QuantumError::InvalidVault);
+    let other_owner = replace(unclaimed, &[(w.ata(&reward_mint), bobs)]);
+    assert_error(w.send(other_owner), QuantumError::InvalidTokenAccount);
+
+    let deposit = ix::withdraw_deposit(&alice, a, &reward_mint, None);
+    // the payout account is created for whichever mint is passed
+    let other_mint = replace(deposit.clone(), &[(reward_mint, w.foreign), (w.ata(&reward_mint), w.ata(&w.foreign))]);
+    assert_error(w.send(other_mint), QuantumError::InvalidRewardMint);
+    let other_vault = replace(deposit, &[(pda::ata(&pda::market(a), &reward_mint), vault_b)]);
+    assert_error(w.send(other_vault), QuantumError::InvalidVault);
+}
@@ -58,7 +58,11 @@ describe("quantum-markets", () => {
       .rpc();
 
     const g = await program.account.globalState.fetch(globalPda);
-    assert.equal(g.nextId.toNumber(), 0);
+    assert.equal(g.nextMarketId.toNumber(), 0);
+    assert.equal(g.nextProposalId.toNumber(), 0);
+    // the deploying wallet is the upgrade authority, so it becomes admin
+    assert.ok(g.admin.equals(payer.publicKey));
+    assert.isFalse(g.paused);
   });
 
   it("Creates the first market", async () => {
@@ -73,7 +77,14 @@ describe("quantum-markets", () => {
       .createMarket(
         /* minDeposit */ new anchor.BN(1000 * DECIMALS),
         /* strikePrice */ new anchor.BN(42 * DECIMALS),
-        /* title */ "My First Market"
+        /* deadline */ new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        /* acceptThreshold */ new anchor.BN(600_000),   // 0.6 vUSD per YES
+        /* twapWindow */ new anchor.BN(600),
+        /* title */ "My First Market",
+        /* lpFeeBps */ 0,
+        /* creatorFeeBps */ 0,
+        /* executionDelay */ new anchor.BN(0),
+        /* oracle */ { kind: { none: {} }, feed: PublicKey.default, maxStaleness: new anchor.BN(0), maxConfidenceBps: 0 }
       )
       .accounts({
         payer: payer.publicKey,
@@ -89,6 +100,9 @@ describe("quantum-markets", () => {
     assert.equal(m.strikePrice.toNumber(), 42 * DECIMALS);
     assert.equal(m.title, "My First Market");
     assert.deepEqual(m.status, { open: {} });
+    assert.ok(m.deadline.toNumber() > m.createdAt.toNumber());
+    assert.equal(m.acceptThreshold.toNumber(), 600_000);
+    assert.equal(m.proposalCount.toNumber(), 0);
     assert.ok(m.marketToken.equals(rewardMint));
     assert.ok(m.resolver.equals(payer.publicKey));
   });
@@ -154,7 +168,7 @@ describe("quantum-markets", () => {
 
   it("Creates proposal-0 (auto-id) and mints vUSD/YES/NO", async () => {
 
-    const idBytes = Buffer.from(Uint8Array.of(1,0,0,0,0,0,0,0));
+    const idBytes = Buffer.from(Uint8Array.of(0,0,0,0,0,0,0,0));   // global.nextProposalId
 
     // derive proposal-0 PDA + mints/auth
     proposalPda = PublicKey.findProgramAddressSync(
@@ -173,13 +187,20 @@ describe("quantum-markets", () => {
       [Buffer.from("no_mint"), idBytes],
       program.programId
     )[0];
+    // one authority per proposal, seeded by the proposal key
     proposalAuthPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("proposal_auth")],
+      [Buffer.from("proposal_auth"), proposalPda.toBuffer()],
       program.programId
     )[0];
 
+    // ProposalPayload v1: Text { uri, content_hash }
+    const uri = Buffer.from("ipfs://hello-world");
+    const uriLen = Buffer.alloc(4);
+    uriLen.writeUInt32LE(uri.length);
+    const payload = Buffer.concat([Buffer.from([1, 0]), uriLen, uri, Buffer.alloc(32)]);
+
     await program.methods
-      .createProposal(Buffer.from("hello-world"))
+      .createProposal(payload)
       .accounts({
         payer:              payer.publicKey,
         market:             marketPda,
@@ -192,6 +213,14 @@ describe("quantum-markets", () => {
         vusdVault: await getAssociatedTokenAddress(vusdMint, proposalAuthPda, true, TOKEN_PROGRAM_ID),
         yesVault:  await getAssociatedTokenAddress(yesMint,  proposalAuthPda, true, TOKEN_PROGRAM_ID),
         noVault:   await getAssociatedTokenAddress(noMint,   proposalAuthPda, true, TOKEN_PROGRAM_ID),
+        yesPool:   PublicKey.findProgramAddressSync(
+          [Buffer.from("pool"), (await getAssociatedTokenAddress(yesMint, proposalAuthPda, true, TOKEN_PROGRAM_ID)).toBuffer()],
+          program.programId
+        )[0],
+        noPool:    PublicKey.findProgramAddressSync(
+          [Buffer.from("pool"), (await getAssociatedTokenAddress(noMint, proposalAuthPda, true, TOKEN_PROGRAM_ID)).toBuffer()],
+          program.programId
+        )[0],
         userYes:   await getAssociatedTokenAddress(yesMint,  payer.publicKey),
         userNo:    await getAssociatedTokenAddress(noMint,   payer.publicKey),
         proposalAuth: proposalAuthPda,
@@ -209,6 +238,11 @@ describe("quantum-markets", () => {
 
     const pcfg = await program.account.proposalConfig.fetch(proposalPda);
     assert.equal(pcfg.marketId.toNumber(), 0);
+    assert.equal(pcfg.authVersion, 1);
+
+    const yesPool = await program.account.pool.fetch(pcfg.yesPool);
+    assert.equal(yesPool.reserveToken.toNumber(), 333_333_333);
+    assert.equal(yesPool.reserveVusd.toNumber(), 166_666_666);
   });
 
   it("Claims vUSD equal to deposit and receives 1 000 vUSD", async () => {
@@ -239,6 +273,28 @@ describe("quantum-markets", () => {
     assert.equal(bal.value.uiAmount, 1000);
   });
 
+  it("Refuses to withdraw deposit already claimed into a proposal", async () => {
+    const record = await program.account.depositRecord.fetch(depositPda);
+    assert.equal(record.maxClaimed.toNumber(), record.amount.toNumber());
+
+    try {
+      await program.methods
+        .withdrawUnclaimed(new anchor.BN(1))
+        .accounts({
+          payer: payer.publicKey,
+          market: marketPda,
+          depositRecord: depositPda,
+          rewardMint,
+          marketVault,
+          userToken: userAta,
+        })
+        .rpc();
+      assert.fail("expected ExceedsUnclaimed");
+    } catch (e) {
+      assert.include(e.toString(), "ExceedsUnclaimed");
+    }
+  });
+
   it("Swaps 200 vUSD → 200 YES + NO, then redeems back", async () => {
     // 1) mintYesNo
     userYesAta = await getAssociatedTokenAddress(yesMint, payer.publicKey);
@@ -249,6 +305,7 @@ describe("quantum-markets", () => {
       .accounts({
         payer: payer.publicKey,
         proposal: proposalPda,
+        market: marketPda,
         vusdMint,
         proposalAuth: proposalAuthPda,
         userVusd: userVusdAta,
@@ -273,6 +330,7 @@ describe("quantum-markets", () => {
       .accounts({
         payer: payer.publicKey,
         proposal: proposalPda,
+        market: marketPda,
         proposalAuth: proposalAuthPda,
         yesMint,
         noMint,
@@ -292,4 +350,113 @@ describe("quantum-markets", () => {
     assert.equal(vusdBal.value.uiAmount, 1000);          // back to original 1 000
   });
 
+  it("Buys YES with 100 vUSD through the YES pool", async () => {
+    const pcfg = await program.account.proposalConfig.fetch(proposalPda);
+    const before = await program.account.pool.fetch(pcfg.yesPool);
+    const amountIn = 100 * DECIMALS;
+    // constant product: out = rToken * in / (rVusd + in)
+    const expectedOut = before.reserveToken
+      .mul(new anchor.BN(amountIn))
+      .div(before.reserveVusd.add(new anchor.BN(amountIn)));
+
+    await program.methods
+      .swapExactIn({ buy: {} }, new anchor.BN(amountIn), expectedOut)
+      .accounts({
+        payer: payer.publicKey,
+        proposal: proposalPda,
+        market: marketPda,
+        pool: pcfg.yesPool,
+        proposalAuth: proposalAuthPda,
+        tokenMint: yesMint,
+        vusdMint,
+        tokenVault: await getAssociatedTokenAddress(yesMint, proposalAuthPda, true, TOKEN_PROGRAM_ID),
+        vusdVault: await getAssociatedTokenAddress(vusdMint, proposalAuthPda, true, TOKEN_PROGRAM_ID),
+        userToken: userYesAta,
+        userVusd: userVusdAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+
+    const yesBal = await provider.connection.getTokenAccountBalance(userYesAta);
+    assert.equal(Number(yesBal.value.amount), 333_333_333 + expectedOut.toNumber());
+    const after = await program.account.pool.fetch(pcfg.yesPool);
+    assert.equal(after.reserveVusd.toNumber(), before.reserveVusd.toNumber() + amountIn);
+  });
+
+  it("Reads the YES TWAP through the view", async () => {
+    const pcfg = await program.account.proposalConfig.fetch(proposalPda);
+    const pool = await program.account.pool.fetch(pcfg.yesPool);
+    const spot = pool.reserveVusd.muln(1_000_000).div(pool.reserveToken);
+
+    // a zero-length window is the current spot price
+    const twap = await program.methods
+      .yesTwap(new anchor.BN(0))
+      .accounts({ proposal: proposalPda, yesPool: pcfg.yesPool })
+      .view();
+    assert.equal(twap.toString(), spot.toString());
+  });
+
+  it("Refuses to graduate a proposal younger than the TWAP window", async () => {
+    const pcfg = await program.account.proposalConfig.fetch(proposalPda);
+    try {
+      await program.methods
+        .graduateProposal()
+        .accounts({ market: marketPda, proposal: proposalPda, yesPool: pcfg.yesPool })
+        .rpc();
+      assert.fail("expected ProposalTooYoung");
+    } catch (e) {
+      assert.include(e.toString(), "ProposalTooYoung");
+    }
+  });
+
+  it("Refuses to resolve a market without an accepted proposal", async () => {
+    try {
+      await program.methods
+        .resolveMarket(true)
+        .accounts({ resolver: payer.publicKey, market: marketPda })
+        .rpc();
+      assert.fail("expected MarketNotAccepted");
+    } catch (e) {
+      assert.include(e.toString(), "MarketNotAccepted");
+    }
+  });
+
+  it("Refuses to refund deposits while the market is open", async () => {
+    try {
+      await program.methods
+        .withdrawDeposit()
+        .accounts({
+          payer: payer.publicKey,
+          market: marketPda,
+          depositRecord: depositPda,
+          acceptedProposal: null,
+          acceptedClaim: null,
+          rewardMint,
+          marketVault,
+          userToken: userAta,
+        })
+        .rpc();
+      assert.fail("expected MarketNotSettled");
+    } catch (e) {
+      assert.include(e.toString(), "MarketNotSettled");
+    }
+  });
+
+  it("Refuses to expire a market before its deadline", async () => {
+    try {
+      await program.methods
+        .expireMarket()
+        .accounts({ market: marketPda })
+        .rpc();
+      assert.fail("expected DeadlineNotReached");
+    } catch (e) {
+      assert.include(e.toString(), "DeadlineNotReached");
+    }
+    const m = await program.account.marketConfig.fetch(marketPda);
+    assert.deepEqual(m.status, { open: {} });
+  });
+
 })